@@ -1,18 +1,110 @@
+// Bevy systems take every resource and query they use as a parameter, and a query's filters
+// are part of its type, so these two lints fire on ordinary systems rather than on bad code
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
 use bevy::prelude::*;
 use bevy::color::palettes::css::AQUAMARINE;
-use bevy::window::{PrimaryWindow, Window};
+use bevy::camera::ScalingMode;
+use bevy::camera::visibility::RenderLayers;
+use bevy::render::render_resource::TextureFormat;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::window::{PrimaryWindow, Window, WindowFocused};
+use bevy::winit::{UpdateMode, WinitSettings};
 use bevy::audio::Volume;
-use rand::{rngs::ThreadRng, thread_rng, Rng};
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::tasks::{block_on, futures::check_ready, IoTaskPool, Task};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // ---------------------------- GAMEPLAY CONSTANTS & COMPONENTS ----------------------------
 // BIRD
 const PIXEL_RATIO: f32 = 4.;
+const BIRD_WIDTH: f32 = 12.;
+const BIRD_HEIGHT: f32 = 8.;
+// Sprite sizes in art pixels; textures are drawn at this size whatever their resolution,
+// so @2x files render at the same world size as the standard ones
+const BIRD_ART_SIZE: Vec2 = Vec2::new(BIRD_WIDTH, BIRD_HEIGHT);
+const PIPE_ART_SIZE: Vec2 = Vec2::new(18., 144.);
+// "bird.png" -> "bird@2x.png", used when the window's scale factor reaches HIDPI_SCALE_FACTOR
+const HIDPI_SUFFIX: &str = "@2x";
+const HIDPI_SCALE_FACTOR: f32 = 1.5;
+const HIDPI_ASSETS: [&str; 5] = ["pipe.png", "bird.png", "red_bird.png", "blue_bird.png", "green_bird.png"];
+const TINY_BIRD_SCALE: f32 = 0.6;
+const GIANT_BIRD_SCALE: f32 = 1.4;
 const FLAP_FORCE: f32 = 500.;
 const GRAVITY: f32 = 2000.;
 const VELOCITY_TO_ROTATION_RATIO: f32 = 7.5;
+// How fast the tilt catches up with the velocity (per second); only the sprite's rotation uses it.
+// Default for rotation_smoothing in assets/config.ron
+const ROTATION_SMOOTHING: f32 = 20.;
+// Fastest the shown tilt may turn, in degrees per second
+const MAX_TILT_SPEED: f32 = 900.;
+// Longest frame physics will simulate in one step (e.g. after restoring a minimized window)
+const MAX_PHYSICS_DELTA: f32 = 1. / 20.;
+const RESPAWN_INVULNERABILITY: f32 = 1.5;
+const RESPAWN_BLINK_INTERVAL: f32 = 0.1;
+//PLAYFIELD (logical world size; the camera letterboxes it into any window)
+const PLAYFIELD_WIDTH: f32 = 800.;
+const PLAYFIELD_HEIGHT: f32 = 600.;
+// Bars are drawn above the world but below the HUD
+const LETTERBOX_Z: f32 = 40.;
+//CAMERA ZOOM (above 1 zooms in; zooming out past the ranked limit shows enough extra pipes to make runs unranked)
+const CAMERA_ZOOM_MIN: f32 = 0.7;
+const CAMERA_ZOOM_MAX: f32 = 1.5;
+const CAMERA_ZOOM_STEP: f32 = 0.05;
+const CAMERA_ZOOM_RANKED_MIN: f32 = 0.9;
+//GAP TELEGRAPH (seconds of scrolling ahead of the view edge a gap is marked, and the marker's size)
+const GAP_TELEGRAPH_LEAD: f32 = 0.6;
+const GAP_TELEGRAPH_SIZE: f32 = 16.;
+//CAMERA DRIFT (the world camera leans a few pixels the way the bird is moving)
+const CAMERA_DRIFT_MAX: f32 = 8.;
+// Bird speed that gives the full lean
+const CAMERA_DRIFT_VELOCITY: f32 = FLAP_FORCE;
+const CAMERA_DRIFT_EASE: f32 = 4.;
+//SQUASH AND STRETCH
+const STRETCH_DURATION: f32 = 0.15;
+const STRETCH_AMOUNT: f32 = 0.25;
+const SQUASH_DURATION: f32 = 0.2;
+const SQUASH_AMOUNT: f32 = -0.3;
+//FLOOR WARNING
+const FLOOR_WARNING_DISTANCE: f32 = 60.;
+const FLOOR_WARNING_MAX_ALPHA: f32 = 0.5;
+const FLOOR_WARNING_TICK_INTERVAL: f32 = 1.;
+//GAP TONE
+const GAP_TONE_FREQUENCY: f32 = 440.;
+const GAP_TONE_VOLUME: f32 = 0.05;
+const GAP_TONE_UPDATE_INTERVAL: f32 = 0.2;
+// Vertical distance (px) that shifts the tone by one octave
+const GAP_TONE_OCTAVE_DISTANCE: f32 = 300.;
+//BLIND RUN (gap tone updated every frame and panned by the next pipe's distance)
+const BLIND_TONE_VOLUME: f32 = 0.12;
+// Pipes this far ahead (px) or more sit fully in the right ear; a pipe at the bird is centered
+const BLIND_PAN_RANGE: f32 = 400.;
+// Emitter x at full pan: the default listener's right ear
+const BLIND_PAN_WIDTH: f32 = 2.;
+// Shrinks emitter and ear distances below 1 so rodio only pans and never attenuates
+const BLIND_SPATIAL_SCALE: f32 = 0.25;
+//COMBO
+const COMBO_WINDOW: f32 = 2.5;
+const COMBO_FLASH_DURATION: f32 = 0.3;
+const COMBO_BAR_WIDTH: f32 = 120.;
+//GAP WHOOSH (how far ahead of a pair's center the anticipation swoosh plays)
+const WHOOSH_LEAD: f32 = OBSTACLE_WIDTH * PIXEL_RATIO * 1.5;
+//PRECISION (distance from the gap center as a fraction of half the gap)
+const PRECISION_PERFECT: f32 = 0.2;
+const PRECISION_GOOD: f32 = 0.5;
+// Consecutive perfect passes that earn a toast mid-run
+const PERFECT_STREAK_MILESTONES: [u32; 3] = [5, 10, 20];
+//RUN LOG
+const RUN_LOG_LEN: usize = 30;
+// Passes with less vertical room than this (px) count as near misses
+const NEAR_MISS_CLEARANCE: f32 = 8.;
+const RUN_DETAILS_SCROLL_STEP: f32 = 90.;
 //OBSTACLE
 const OBSTACLE_AMOUNT: i32 = 5;
 const OBSTACLE_WIDTH: f32 = 32.;
@@ -21,6 +113,231 @@ const OBSTACLE_VERTICAL_OFFSET: f32 = 8.;
 const OBSTACLE_GAP_SIZE: f32 = 25.;
 const OBSTACLE_SPACING: f32 = 60.;
 const OBSTACLE_SCROLL_SPEED: f32 = 150.;
+const RESPAWN_PROTECTION_RADIUS: f32 = OBSTACLE_WIDTH * PIXEL_RATIO * 2.;
+// Share of each new gap offset pulled toward the previous pair's, for gentler transitions
+const OFFSET_SMOOTHING: f32 = 0.3;
+// Upper bound on live pipe entities when streaming fresh spawns
+const STREAM_MAX_OBSTACLES: usize = OBSTACLE_AMOUNT as usize * 2 * 2;
+// Endless gap shrink: 2% per 10 points, down to the floor set in config.ron
+const GAP_SHRINK_STEP: f32 = 0.02;
+const GAP_SHRINK_POINTS: u32 = 10;
+const GAP_SHRINK_FLOOR: f32 = 0.7;
+const GAP_TOAST_DURATION: f32 = 1.5;
+//REST STOPS (Endless leaves one pair's slot empty after every `rest_stop_interval` points)
+const REST_STOP_BANNER_Z: f32 = 5.;
+// Gap trainer slider; the step lands on every difficulty's gap
+const TRAINER_GAP_MIN: f32 = 15.;
+const TRAINER_GAP_MAX: f32 = 35.;
+const TRAINER_GAP_STEP: f32 = 1.25;
+//AUDIO
+const MUSIC_BASE_VOLUME: f32 = 0.1;
+const VOLUME_STEP: f32 = 0.1;
+// Flap playback speed varies by up to this fraction either way
+const FLAP_PITCH_VARIATION: f32 = 0.05;
+// Mixed into the run seed for the flap pitch stream, so pitches never shift the course
+const FLAP_PITCH_SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+// Every sound effect is played once, silently, at startup so its decoder is set up before the first run.
+// Set this environment variable to skip it; starting a run no longer waits after AUDIO_PREWARM_TIMEOUT
+const NO_AUDIO_ENV: &str = "FLAPPY_NO_AUDIO";
+const AUDIO_PREWARM_TIMEOUT: f32 = 3.;
+const PREWARM_SOUNDS: [&str; 6] = ["flap.ogg", "flap_pop.wav", "flap_blip.wav", "point.ogg", "die.ogg", "swoosh.ogg"];
+//PAUSE
+const RESUME_COUNTDOWN: f32 = 3.;
+//MODIFIERS
+const WIND_FORCE: f32 = 250.;
+const WIND_PERIOD: f32 = 4.;
+const FLAP_COOLDOWN: f32 = 0.35;
+const FLIP_DURATION: f32 = 5.;
+// A flip pickup appears in a gap ahead every this many points
+const FLIP_PICKUP_EVERY: u32 = 8;
+const FLIP_PICKUP_SIZE: f32 = 8.;
+//CUSTOM TUNING (multipliers on top of the difficulty preset)
+const TUNE_STEP: f32 = 0.05;
+const TUNE_MIN: f32 = 0.5;
+const TUNE_MAX: f32 = 1.5;
+const TUNE_ROWS: usize = 5;
+//WEEKLY CHALLENGE (drawn from the ISO week number, the same for every player)
+const WEEKLY_MODES: [GameMode; 3] = [GameMode::Endless, GameMode::TimeAttack, GameMode::Checkpoints];
+const WEEKLY_DIFFICULTIES: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+// One or two are drawn each week; Tiny and Giant Bird never come together
+const WEEKLY_MUTATORS: [&str; 7] = ["Tiny Bird", "Giant Bird", "Wind", "Mirror", "Flap Cooldown", "Screen Wrap", "Gravity Flip"];
+// Past weeks listed on the Weekly screen
+const WEEKLY_HISTORY_LEN: usize = 8;
+// Weeks kept in weekly.json and in each profile's weekly bests; older ones are dropped
+const WEEKLY_KEEP_WEEKS: usize = 52;
+// Best weekly run on this machine per week, across all slots
+const WEEKLY_PATH: &str = "saves/weekly.json";
+//CHALLENGE
+const CHALLENGE_STEP: u32 = 5;
+const CHALLENGE_MAX: u32 = 200;
+// Gauntlet start: spacing before each following starting pair, as a fraction of the normal one
+const GAUNTLET_SPACING: [f32; OBSTACLE_AMOUNT as usize - 1] = [0.5, 0.5, 0.5, 0.75];
+//OVERTIME
+const OVERTIME_BONUS: f32 = 1.;
+const OVERTIME_MAX: f32 = 99.;
+const OVERTIME_TOAST_DURATION: f32 = 0.6;
+// Celebration between a survived Time Attack and Game Over
+const FINISH_DURATION: f32 = 1.5;
+const FINISH_GLIDE_RATE: f32 = 6.;
+const CONFETTI_COUNT: usize = 60;
+const CONFETTI_GRAVITY: f32 = 500.;
+// (start second, frequency) of the synthesized victory jingle, a rising C major arpeggio
+const JINGLE_NOTES: [(f32, f32); 4] = [(0.0, 523.25), (0.12, 659.25), (0.24, 783.99), (0.36, 1046.5)];
+const JINGLE_NOTE_MS: u64 = 220;
+//FADE
+const FADE_DURATION: f32 = 0.2;
+//IDLE
+const IDLE_TIMEOUT: f32 = 60.;
+//INPUT DEBOUNCE (seconds every new key is ignored after a screen change)
+const INPUT_DEBOUNCE: f32 = 0.15;
+//NAV REPEAT (a held arrow key presses again after the delay, then every interval; menus only)
+const NAV_REPEAT_DELAY: f32 = 0.35;
+const NAV_REPEAT_INTERVAL: f32 = 0.08;
+const NAV_KEYS: [KeyCode; 4] = [KeyCode::ArrowUp, KeyCode::ArrowDown, KeyCode::ArrowLeft, KeyCode::ArrowRight];
+//UI SCALE
+const UI_SCALE_MIN: f32 = 0.75;
+const UI_SCALE_MAX: f32 = 1.5;
+const UI_SCALE_STEP: f32 = 0.1;
+const UI_HEADING_MAX_SCALE: f32 = 1.0;
+//RUMBLE
+const FLAP_RUMBLE_MS: u64 = 80;
+const DEATH_RUMBLE_MS: u64 = 400;
+const SETTINGS_PATH: &str = "saves/settings.json";
+//TIMED MODES
+const TIME_ATTACK_DURATION: f32 = 60.;
+const TOURNAMENT_ROUNDS: usize = 3;
+const TOURNAMENT_ROUND_DURATION: f32 = 30.;
+//RUN CODES
+const RUN_CODE_PREFIX: &str = "FLAPPY";
+// Recent seeds kept in the settings file, newest first (keys 1-9 and 0 on the Seeds screen)
+const SEED_HISTORY_LEN: usize = 10;
+//REPLAYS
+const REPLAY_VERSION: u32 = 4;
+const REPLAY_DIR: &str = "saves/replays";
+const REPLAY_EXTENSION: &str = "flappyreplay";
+//DIAGNOSTICS
+const SAVES_DIR: &str = "saves";
+const DEBUG_DUMP_PATH: &str = "saves/debug_dump.txt";
+// Written and removed at startup to check the saves directory is writable
+const SAVE_PROBE_FILE: &str = ".write_probe";
+// Held by the running game so a second copy can't write the same slot files
+const SAVE_LOCK_FILE: &str = "saves/.lock";
+const REQUIRED_ASSETS: [&str; 16] = [
+    "pipe.png", "bird.png", "red_bird.png", "blue_bird.png", "green_bird.png",
+    "Background1.png", "Background2.png", "cookd.png",
+    "flap.ogg", "point.ogg", "die.ogg", "swoosh.ogg", "35-Lost-Woods.ogg",
+    "flap_pop.wav", "flap_blip.wav",
+    UI_FONT,
+];
+//GAME CONFIG
+const CONFIG_PATH: &str = "assets/config.ron";
+//TOASTS
+const TOAST_DURATION: f32 = 4.;
+//COSMETICS (bought with coins picked up on level courses)
+const TRAIL_COLORS: [TrailColor; 5] = [TrailColor::Off, TrailColor::Gold, TrailColor::Sky, TrailColor::Rose, TrailColor::Mint];
+const FLAP_SOUNDS: [FlapSound; 3] = [FlapSound::Classic, FlapSound::Pop, FlapSound::Blip];
+const TRAIL_DOT_INTERVAL: f32 = 0.04;
+const TRAIL_DOT_LIFETIME: f32 = 0.45;
+// In art pixels, like COIN_SIZE
+const TRAIL_DOT_SIZE: f32 = 2.;
+//RUN ENTITIES
+// Far above a busy run (pipes, coins, confetti, trail dots); more means a leak
+const RUN_ENTITY_CAP: usize = 400;
+//NAME PROMPT
+const NAME_MAX_LEN: usize = 16;
+// A score that would place this high on the leaderboard asks for a name
+const NAME_PROMPT_TOP: usize = 10;
+//SCORE CARD (exported from Game Over)
+const CARD_DIR: &str = "saves/cards";
+const CARD_SIZE: UVec2 = UVec2::new(600, 320);
+// Frames the offscreen layout gets to draw before it's captured
+const CARD_RENDER_FRAMES: u32 = 3;
+// Layer nothing else is on, so the card camera draws no sprites from the live scene
+const CARD_LAYER: usize = 7;
+//MENU BACKGROUND
+// Close to Background2.png, so menus don't flash the last run's theme color while it loads
+const MENU_CLEAR_COLOR: Color = Color::srgb(0.6, 0.98, 1.0);
+//LOW-POWER MENUS (static screens only redraw on input, or at this rate for toasts and timers)
+const MENU_LOW_POWER_FPS: f64 = 30.;
+const LOW_POWER_STATES: [GameState; 3] = [GameState::MainMenu, GameState::Leaderboard, GameState::Scoreboard];
+//SESSION SUMMARY (shown on the main menu after new runs)
+const SESSION_SUMMARY_DURATION: f32 = 5.;
+// Last part of the duration spent fading out
+const SESSION_SUMMARY_FADE: f32 = 1.5;
+//RUN RECOVERY (marker left behind by runs that never reached Game Over)
+const RUN_MARKER_PATH: &str = "saves/run_in_progress.json";
+const RUN_MARKER_INTERVAL: f32 = 5.;
+//FONTS
+const UI_FONT: &str = "fonts/BBHHegarty-Regular.ttf";
+//LEVELS
+const LEVELS_DIR: &str = "assets/levels";
+const MAX_LISTED_LEVELS: usize = 9;
+const COIN_SIZE: f32 = 6.;
+//DEATH REPLAY
+const DEATH_REPLAY_SECONDS: f32 = 3.;
+const DEATH_REPLAY_SPEED: f32 = 0.35;
+const DEATH_REPLAY_HOLD: f32 = 1.;
+// Fraction of the window the corner view takes up
+const DEATH_REPLAY_SCALE: f32 = 0.3;
+const DEATH_REPLAY_MARGIN: f32 = 20.;
+//RUN HISTORY
+const RUN_HISTORY_LEN: usize = 20;
+// Monthly rollups listed per slot on the Scoreboard
+const SCOREBOARD_MONTHS: usize = 3;
+// Runs per side of the improvement trend (last N vs the N before them)
+const TREND_WINDOW: usize = 5;
+//RUN GRADE (composite of score, survival and precision measured against the slot's own runs)
+// Lowest composite for each letter, best first; anything lower is a C
+const GRADE_THRESHOLDS: [(char, f32); 3] = [('S', 0.85), ('A', 0.65), ('B', 0.4)];
+// Weights of score standing, survival and precision; precision's share moves to score on runs with no passes
+const GRADE_WEIGHTS: (f32, f32, f32) = (0.5, 0.25, 0.25);
+// Below this many runs in history, score is measured against GRADE_STARTER_SCORE instead
+const GRADE_MIN_HISTORY: usize = 5;
+const GRADE_STARTER_SCORE: f32 = 20.;
+//MASTERY (ranked score that masters a mode on each difficulty)
+const MASTERY_THRESHOLDS: [(Difficulty, u32); 4] = [
+    (Difficulty::Easy, 100),
+    (Difficulty::Normal, 60),
+    (Difficulty::Hard, 40),
+    (Difficulty::Insane, 25),
+];
+//UNLOCKS (difficulty, lowest difficulty the score counts on, ranked score that unlocks it)
+const DIFFICULTY_UNLOCKS: [(Difficulty, Difficulty, u32); 2] = [
+    (Difficulty::Hard, Difficulty::Normal, 20),
+    (Difficulty::Insane, Difficulty::Hard, 30),
+];
+const UNLOCK_ALL_ARG: &str = "--unlock-all";
+const SPARKLINE_HEIGHT: f32 = 30.;
+const SPARKLINE_BAR_WIDTH: f32 = 6.;
+//DEATH CHART (runs ending at each score; the last bucket also counts every later one)
+const DEATH_PIPES_LEN: usize = 40;
+const DEATH_CHART_HEIGHT: f32 = 40.;
+const DEATH_CHART_BAR_WIDTH: f32 = 4.;
+//LEADERBOARD FLAGS
+// Run context shown as badges: (flag, legend, badge color)
+const RUN_FLAGS: [(&str, &str, Color); 6] = [
+    ("tiny", "Tiny Bird", Color::srgb(0.3, 0.6, 0.9)),
+    ("giant", "Giant Bird", Color::srgb(0.85, 0.5, 0.2)),
+    ("seeded", "Known layout (run code or seed history)", Color::srgb(0.55, 0.4, 0.8)),
+    ("overtime", "Time Attack overtime", Color::srgb(0.3, 0.7, 0.45)),
+    ("filled", "Playfield stretched to the window", Color::srgb(0.6, 0.6, 0.35)),
+    ("custom", "Difficulty fine-tuned away from its preset", Color::srgb(0.8, 0.35, 0.6)),
+];
+//AVATARS
+const AVATAR_DIR: &str = "avatars";
+// Optional icons; a missing file falls back to a square of the paired color
+const AVATARS: [(&str, Color); 5] = [
+    ("chick.png", Color::srgb(0.98, 0.82, 0.16)),
+    ("frog.png", Color::srgb(0.35, 0.75, 0.27)),
+    ("ghost.png", Color::srgb(0.92, 0.92, 0.98)),
+    ("star.png", Color::srgb(1.0, 0.78, 0.0)),
+    ("heart.png", Color::srgb(0.86, 0.16, 0.24)),
+];
+//THEME SOUNDS (assets/sfx/<theme folder>/<name>.ogg or .wav replaces assets/<file> when present)
+const THEME_SFX_DIR: &str = "sfx";
+const THEME_SFX_FORMATS: [&str; 2] = ["ogg", "wav"];
+// AssetPlugin's default folder, under Bevy's asset base path
+const ASSETS_DIR: &str = "assets";
 
 // ---------------------------- STATES ----------------------------
 // Game screens (states) switched between during app run
@@ -34,19 +351,65 @@ enum GameState {
     DifficultySelect,
     ThemeSelect,
     SkinSelect,
+    AccessibilitySelect,
+    // Follows ThemeSelect; toggles optional rule changes for the next runs
+    Modifiers,
+    // Handicap start score and score goal, reached from Options
+    Challenge,
+    // This week's fixed mode/difficulty/mutator combo and past weeks' results, reached from the main menu
+    Weekly,
+    // Trail colors and flap sounds bought with coins, reached from Options
+    Cosmetics,
     Playing,
     GameOver,
+    // Event log of the run that just ended, reached from Game Over
+    RunDetails,
     Victory,
     Leaderboard,
+    QuitConfirm,
+    RoundSummary,
+    LevelSelect,
+    LevelComplete,
+    TrainerSelect,
+    // Loads the newest replay file, then plays it back inside Playing
+    Replay,
+    // Passes straight back into Playing so OnExit/OnEnter rebuild the run
+    Restarting,
+    // Shown before a run whose mode/difficulty/theme/skin differ from the slot's saved ones
+    OverwriteConfirm,
+    // Recent seeds, reached with S on ModeSelect
+    SeedSelect,
+    // Every slot's stats side by side, reached from the main menu
+    Scoreboard,
+    // Shown at startup when saves/.lock belongs to another instance
+    SaveLocked,
+    // Fine-tunes the selected difficulty, reached with Tab on DifficultySelect
+    DifficultyTune,
+}
+
+// Pause lives inside Playing so pausing doesn't tear the run down
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Playing)]
+enum PauseState {
+    #[default]
+    Running,
+    Paused,
+    // 3-2-1 countdown before play continues; physics stays frozen
+    Resuming,
+    // Accessibility and input settings opened from the pause menu; ESC goes back to Paused
+    Settings,
 }
 
 // ---------------------------- GAME SETTINGS ----------------------------
 // Specific state options
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum GameMode {Endless, TimeAttack, Checkpoints}
+enum GameMode {Endless, TimeAttack, Checkpoints, Tournament, Level, Trainer}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Difficulty {Easy, Normal, Hard}
+// Ordered easiest first, so unlock rules can count scores set on any harder difficulty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Difficulty {Easy, Normal, Hard, Insane}
+
+const DIFFICULTIES: [Difficulty; 4] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Insane];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Theme {Classic, HighContrast, Minimal}
@@ -54,6 +417,48 @@ enum Theme {Classic, HighContrast, Minimal}
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Skin {Classic, Red, Blue, Green}
 
+// Dots left behind the bird; Off is free
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum TrailColor {#[default] Off, Gold, Sky, Rose, Mint}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum FlapSound {#[default] Classic, Pop, Blip}
+
+// How pipes leaving the screen are replaced: reuse the same entities, or despawn and spawn fresh ones ahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SpawnStrategy {#[default] Recycle, Stream}
+
+// Optional rule changes picked on the Modifiers screen; any active one makes runs unranked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+struct RunModifiers {
+    // Gusts push the bird up and down in a slow wave
+    wind: bool,
+    // The playfield is flipped horizontally
+    mirror: bool,
+    // Flaps closer together than FLAP_COOLDOWN are ignored
+    flap_cooldown: bool,
+    // Leaving the bottom of the screen brings the bird back in at the top
+    screen_wrap: bool,
+    // Pickups in the gaps turn gravity upside down for FLIP_DURATION
+    #[serde(default)]
+    gravity_flip: bool,
+}
+
+// Fine-tune on top of the difficulty preset; every field multiplies its DifficultyTuning
+// counterpart, so 1.0 everywhere plays the preset and any other value makes the run "custom"
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct CustomTuning {
+    gap: f32,
+    speed: f32,
+    gravity: f32,
+    flap: f32,
+    offset: f32,
+}
+
+// Size mutator, picked on the difficulty screen and cleared after each run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum BirdSize {#[default] Normal, Tiny, Giant}
+
 #[derive(Resource, Serialize, Deserialize, Clone)]
 struct PlayerProfile {
     name: String,
@@ -61,13 +466,68 @@ struct PlayerProfile {
     total_games: u32,
     average_score: f32,
     longest_survival: f32,
+    #[serde(default)]
+    sessions: u32,
+    #[serde(default)]
+    best_session: u32,
+    #[serde(default)]
+    tournament_best: u32,
+    // Index into AVATARS
+    #[serde(default)]
+    avatar: usize,
+    // Scores of the most recent ranked runs, oldest first
+    #[serde(default)]
+    history: Vec<u32>,
+    // Lines up with the end of history; true marks a run cut short by a crash
+    #[serde(default)]
+    history_interrupted: Vec<bool>,
+    // Every run ever added to history, rolled up per UTC month ("2026-10"), so trends outlive its cap
+    #[serde(default)]
+    monthly: std::collections::BTreeMap<String, MonthStats>,
+    // Best average gap-center precision of a ranked run, in percent
+    #[serde(default)]
+    best_precision: f32,
+    // Best ranked score per mastery_key
+    #[serde(default)]
+    mode_bests: std::collections::HashMap<String, u32>,
+    // mastery_keys whose MASTERY_THRESHOLDS score has been reached
+    #[serde(default)]
+    mastered: Vec<String>,
+    // Difficulties earned through DIFFICULTY_UNLOCKS
+    #[serde(default)]
+    unlocked: Vec<Difficulty>,
+    // Most perfectly centered passes in a row in a ranked run
+    #[serde(default)]
+    best_perfect_streak: u32,
+    // Ranked deaths by score at death (died at pipe index + 1); only as long as the highest one
+    #[serde(default)]
+    death_pipes: Vec<u32>,
+    // Best weekly challenge score per week key ("2026-W07")
+    #[serde(default)]
+    weekly_bests: std::collections::HashMap<String, u32>,
+    // Unspent coins from level courses
+    #[serde(default)]
+    coins: u32,
+    // Bought cosmetics by cosmetic_key; free ones are never listed
+    #[serde(default)]
+    owned_cosmetics: Vec<String>,
+    #[serde(default)]
+    trail: TrailColor,
+    #[serde(default)]
+    flap_sound: FlapSound,
 }
 
-#[derive(Resource)]
-struct SaveSelectOrigin {
-    origin_state: GameState,
+// Screens the player passed through on the way to the current menu screen; ESC goes back to the last one
+#[derive(Resource, Default)]
+struct NavStack {
+    screens: Vec<GameState>,
 }
 
+// Set once Game Over's one-shots (death replay, name prompt, score card) ran for this run,
+// so coming back from Run Details only rebuilds the screen
+#[derive(Resource, Default, PartialEq)]
+struct GameOverSeen(bool);
+
 #[derive(Resource, Serialize, Deserialize, Clone)]
 struct SaveSlot {
     slot_number: u8,
@@ -78,15 +538,110 @@ struct SaveSlot {
         skin: Skin,
     score: u32,
     survival_time: f32,
+    #[serde(default)]
+    photosensitive_safe: bool,
+    #[serde(default)]
+    bird_size: BirdSize,
+    #[serde(default)]
+    floor_warning: Option<bool>,
+    #[serde(default = "default_true")]
+    floor_warning_sound: bool,
+    #[serde(default)]
+    gap_tone: bool,
+    #[serde(default)]
+    blind_run: bool,
+    #[serde(default)]
+    spawn_strategy: SpawnStrategy,
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    reduce_motion: bool,
+    #[serde(default)]
+    overtime: bool,
+    #[serde(default = "default_true")]
+    record_marker: bool,
+    #[serde(default)]
+    gap_telegraph: bool,
+    #[serde(default)]
+    fill_window: bool,
+    #[serde(default = "default_camera_zoom")]
+    camera_zoom: f32,
+    #[serde(default)]
+    custom_tuning: CustomTuning,
+    // RUN_FLAGS that applied to the saved run
+    #[serde(default)]
+    flags: Vec<String>,
+    // Name typed on Game Over for the saved run, shown on the leaderboard instead of the profile's
+    #[serde(default)]
+    run_name: Option<String>,
 }
 
-#[derive(Resource)]
+#[derive(Resource, Clone, Debug)]
 struct GameSettings {
     current_slot: Option<u8>,
     selected_mode: GameMode,
     selected_difficulty: Difficulty,
     selected_theme: Theme,
     selected_skin: Skin,
+    seed: Option<u64>,
+    photosensitive_safe: bool,
+    bird_size: BirdSize,
+    // None follows the difficulty default (off on Hard)
+    floor_warning: Option<bool>,
+    floor_warning_sound: bool,
+    gap_tone: bool,
+    // Audio-only play: the gap tone is forced on, follows the gap every frame and pans with distance
+    blind_run: bool,
+    spawn_strategy: SpawnStrategy,
+    // File stem of the level played in GameMode::Level
+    selected_level: Option<String>,
+    // Replaces slow-motion effects with still frames
+    reduce_motion: bool,
+    modifiers: RunModifiers,
+    // Time Attack variant where every scored pipe adds OVERTIME_BONUS seconds
+    overtime: bool,
+    // Marks the pipe pair where the profile's Endless record ends
+    record_marker: bool,
+    // Assist: the next gap is marked on the right edge before its pipes come into view; makes runs unranked
+    gap_telegraph: bool,
+    // Challenge play: runs start at start_score and end in Victory at score_goal; either makes runs unranked
+    start_score: u32,
+    score_goal: Option<u32>,
+    // Challenge play: the starting pairs come packed tighter, easing into normal spacing
+    gauntlet_start: bool,
+    // Casual: the world fills the window instead of the letterboxed PLAYFIELD_* area
+    fill_window: bool,
+    // View scale of the world camera; the playfield itself never changes size
+    camera_zoom: f32,
+    custom_tuning: CustomTuning,
+    // Week key of the weekly challenge being played; such runs only count toward its best
+    weekly: Option<String>,
+}
+
+// Designed course loaded from assets/levels/<name>.json, in the same units as the OBSTACLE_* constants
+#[derive(Deserialize, Clone)]
+struct LevelDef {
+    name: String,
+    pipes: Vec<LevelPipe>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct LevelPipe {
+    y_offset: f32,
+    gap_size: f32,
+    // Distance from the previous pipe
+    spacing: f32,
+    #[serde(default)]
+    coin: bool,
+}
+
+// A finished run's course seed, so the same layout can be played again
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct SeedRecord {
+    seed: u64,
+    mode: GameMode,
+    difficulty: Difficulty,
+    score: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -95,6 +650,18 @@ struct LeaderboardEntry {
     score: u32,
     mode: GameMode,
     difficulty: Difficulty,
+    #[serde(default)]
+    flags: Vec<String>,
+    #[serde(default)]
+    avatar: usize,
+}
+
+#[derive(Resource, Default)]
+struct LeaderboardFilter {
+    // Hides runs with any flag
+    vanilla_only: bool,
+    // Shows this week's weekly challenge bests instead
+    weekly: bool,
 }
 
 #[derive(Component)]
@@ -106,6 +673,28 @@ struct OptionsMarker;
 #[derive(Component)]
 struct SaveSelectMarker;
 
+// Container of the Saves screen's slot rows, rebuilt when SaveSlotChanged is set
+#[derive(Component)]
+struct SlotList;
+
+// Saves screen line that walks through a slot copy or restore
+#[derive(Component)]
+struct SlotActionPrompt;
+
+// Multi-key actions on the Saves screen: C, then the source and destination digits,
+// or U, then the digit of the slot whose previous save comes back
+#[derive(Resource, Default, Clone, Copy, PartialEq)]
+enum SlotAction {
+    #[default]
+    Idle,
+    CopyFrom,
+    CopyTo(u8),
+    // Destination already holds a profile; waits for Y
+    CopyConfirm(u8, u8),
+    RestorePick,
+    RestoreConfirm(u8),
+}
+
 #[derive(Component)]
 struct ModeSelectMarker;
 
@@ -119,850 +708,10557 @@ struct ThemeSelectMarker;
 struct SkinSelectMarker;
 
 #[derive(Component)]
-struct GameOverMarker;
+struct AccessibilityMarker;
 
 #[derive(Component)]
-struct VictoryScreenMarker;
+struct ModifiersMarker;
 
 #[derive(Component)]
-struct LeaderboardMarker;
+struct ChallengeMarker;
+
+#[derive(Component)]
+struct DifficultyTuneMarker;
+
+#[derive(Component)]
+struct WeeklyMarker;
+
+#[derive(Component)]
+struct CosmeticsMarker;
+
+// 0 = trail, 1 = flap sound
+#[derive(Component)]
+struct CosmeticRow(usize);
+
+#[derive(Component)]
+struct CosmeticsStatus;
 
+// Item shown on each Cosmetics row, which may not be owned yet
 #[derive(Resource, Default)]
-struct SaveSlotChanged {
-    changed: bool,
+struct CosmeticsBrowse {
+    trail: usize,
+    flap: usize,
+    // Slot being shopped for, read once on entering the screen and kept up to date by purchases
+    save: Option<SaveSlot>,
 }
 
-#[derive(Resource)]
-pub struct Score {
-    pub current: u32,
-    pub best: u32,
-    pub scored_pipes: Vec<Entity>,
+// Trail color for the current run (None when off or with reduce motion)
+#[derive(Resource, Default)]
+struct BirdTrail {
+    color: Option<Color>,
+    since_dot: f32,
 }
 
-#[derive(Resource)]
-pub struct GameManager {
-    pub pipe_image: Handle<Image>,
-    pub window_dimensions: Vec2,
+#[derive(Component)]
+struct TrailDot {
+    age: f32,
 }
 
+// Settings from before a weekly challenge run, put back on the main menu
 #[derive(Resource)]
-pub struct SoundEffects {
-    pub flap: Handle<AudioSource>,
-    pub point: Handle<AudioSource>,
-    pub die: Handle<AudioSource>,
-    pub swoosh: Handle<AudioSource>,
-}
+struct WeeklyRestore(GameSettings);
 
-#[derive(Resource)]
-struct TimeAttackState {
-    remaining: f32,
+#[derive(Component)]
+struct TuneRow(usize);
+
+// Estimated gap, speed and flap height of the tuned difficulty
+#[derive(Component)]
+struct TuneSummary;
+
+// Full-screen black node drawn above every screen
+#[derive(Component)]
+struct FadeOverlay;
+
+#[derive(Component)]
+struct GameOverMarker;
+
+#[derive(Component)]
+struct RunDetailsMarker;
+
+// Scrollable event list on the Run Details page
+#[derive(Component)]
+struct RunDetailsList;
+
+#[derive(Component)]
+struct VictoryScreenMarker;
+
+#[derive(Component)]
+struct LeaderboardMarker;
+
+#[derive(Component)]
+struct ScoreboardMarker;
+
+// Main menu notice shown while progress can't be saved to disk
+#[derive(Component)]
+struct SaveBannerMarker;
+
+#[derive(Resource, Default)]
+struct SaveBanner {
+    dismissed: bool,
 }
 
+#[derive(Component)]
+struct QuitConfirmMarker;
+
+#[derive(Component)]
+struct SaveLockedMarker;
+
+// Contents of a lock file held by someone else, until the player decides what to do
 #[derive(Resource)]
-struct CheckpointsState {
-    checkpoints: Vec<u32>,
-    current_checkpoint_index: usize,
-    completed: bool,
-    last_checkpoint_score: u32,
+struct SaveLock {
+    conflict: Option<String>,
 }
 
-#[derive(Resource, Clone, Copy)]
-struct DifficultyTuning {
-    gap_size: f32,
-    scroll_speed: f32,
-    gravity_mult: f32,
-    flap_mult: f32,
-    vertical_offset: f32,
-}
+#[derive(Component)]
+struct OverwriteConfirmMarker;
 
 #[derive(Component)]
-struct Bird {
-    pub velocity: f32,
-}
+struct SeedSelectMarker;
 
 #[derive(Component)]
-struct ScoreDisplay;
+struct RoundSummaryMarker;
 
 #[derive(Component)]
-struct BestScoreDisplay;
+struct LevelSelectMarker;
 
 #[derive(Component)]
-struct TimeDisplay;
+struct LevelCompleteMarker;
 
 #[derive(Component)]
-struct CheckpointDisplay;
+struct TrainerSelectMarker;
 
 #[derive(Component)]
-struct VictoryMessage;
+struct TrainerGapText;
 
 #[derive(Component)]
-struct Background;
+struct TrainerSlider;
 
 #[derive(Component)]
-struct Obstacle {
-    pipe_direction: f32,
-    scored: bool,
+struct PauseMenuMarker;
+
+#[derive(Component)]
+struct PauseRow(usize);
+
+#[derive(Component)]
+struct PauseSettingsMarker;
+
+#[derive(Component)]
+struct PauseSettingsRow(usize);
+
+#[derive(Component)]
+struct MusicTrack;
+
+#[derive(Component)]
+struct ResumeCountdownMarker;
+
+#[derive(Resource, Default)]
+struct SaveSlotChanged {
+    changed: bool,
 }
 
-// ---------------------------- IMPLEMENTATIONS ----------------------------
-// Defines main funcionalities for default values
-impl Default for PlayerProfile {
-    fn default() -> Self {
-        Self {
-            name: String::from("Player"),
-            high_score: 0,
-            total_games: 0,
-            average_score: 0.0,
-            longest_survival: 0.0,
-        }
-    }
+// Sent whenever a run leaves the Playing state. Runs quit or restarted from the
+// pause menu (and replays) are abandoned: they are not deaths, don't touch the
+// profile and don't count toward session stats
+#[derive(Message)]
+struct RunEnded {
+    score: u32,
+    // Picked up on a level course
+    coins: u32,
+    abandoned: bool,
+    // The run was played with its week's exact challenge settings and seed (weekly runs only)
+    weekly_match: bool,
 }
 
-impl Default for SaveSelectOrigin {
-    fn default() -> Self {
-        Self {
-            origin_state: GameState::MainMenu,
-        }
-    }
+// Set by the pause menu right before leaving a run on purpose
+#[derive(Resource)]
+struct AbandonedRun;
+
+// Machine-wide volume and controller rumble multipliers, persisted outside the save slots
+#[derive(Resource, Serialize, Deserialize, Clone)]
+struct AudioSettings {
+    music_volume: f32,
+    sfx_volume: f32,
+    #[serde(default = "default_rumble_strength")]
+    rumble_strength: f32,
+    // Seconds without input before Game Over (and menus, if enabled) return to the main menu
+    #[serde(default = "default_idle_timeout")]
+    idle_timeout: f32,
+    #[serde(default)]
+    idle_in_menus: bool,
+    // Multiplies every UI font size, UI_SCALE_MIN..=UI_SCALE_MAX
+    #[serde(default = "default_ui_scale")]
+    ui_scale: f32,
+    #[serde(default)]
+    seed_history: Vec<SeedRecord>,
+    #[serde(default = "default_true")]
+    flap_pitch_variation: bool,
+    // Slot picked most recently, for the main menu greeting
+    #[serde(default)]
+    last_slot: Option<u8>,
+    #[serde(default = "default_true")]
+    greeting: bool,
+    // Starts on the HighContrast (dark) theme in the evening when no slot sets one
+    #[serde(default)]
+    evening_theme: bool,
+    // Asks for a name on Game Over when a score makes the leaderboard
+    #[serde(default = "default_true")]
+    name_prompt: bool,
+    // Exports a score card for every finished run, not only on request
+    #[serde(default)]
+    auto_score_card: bool,
+    // Which key flaps; the default accepts all three
+    #[serde(default)]
+    flap_key: FlapKey,
+    // Static menu screens stop redrawing continuously, see LOW_POWER_STATES
+    #[serde(default = "default_true")]
+    low_power_menus: bool,
 }
 
-impl Default for GameSettings {
-    fn default() -> Self {
-        Self {
-            current_slot: None,
-            selected_mode: GameMode::Endless,
-            selected_difficulty: Difficulty::Normal,
-            selected_theme: Theme::Classic,
-            selected_skin: Skin::Classic,
-        }
-    }
+// Flap key presets picked in Options, for players who'd rather not use Space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum FlapKey {
+    #[default]
+    Any,
+    Space,
+    ArrowUp,
+    W,
 }
 
-impl Default for Score {
-    fn default() -> Self {
-        Self {
-            current: 0,
-            best: 0,
-            scored_pipes: Vec::new(),
-        }
-    }
+// Actions that accept several keys, so alternate layouts work out of the box
+// Advance is MenuConfirm plus the chosen flap key, for screens where Space moves on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum InputAction {Flap, MenuConfirm, Advance}
+
+#[derive(Resource)]
+struct KeyBindings {
+    keys: std::collections::HashMap<InputAction, Vec<KeyCode>>,
+    flap_key: FlapKey,
 }
 
-impl CheckpointsState {
-    fn new(difficulty: Difficulty) -> Self {
-        let checkpoints = match difficulty {
-            Difficulty::Easy => vec![5, 10, 15, 20],
-            Difficulty::Normal => vec![10, 20, 30, 40],
-            Difficulty::Hard => vec![15, 30, 45, 60],
-        };
-        Self {
-            checkpoints,
-            current_checkpoint_index: 0,
-            completed: false,
-            last_checkpoint_score: 0,
-        }
-    }
+// Normal-difficulty baseline read from assets/config.ron; the other difficulties scale from it
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+struct GameConfig {
+    gap_size: f32,
+    scroll_speed: f32,
+    vertical_offset: f32,
+    gravity_mult: f32,
+    flap_mult: f32,
+    ducking: SfxDucking,
+    // Skips DIFFICULTY_UNLOCKS, same as launching with --unlock-all
+    unlock_all: bool,
+    // Bird's horizontal position as a fraction of the playfield width from the left
+    bird_anchor: f32,
+    // Endless points between rest stops; 0 turns them off
+    rest_stop_interval: u32,
+    flap: FlapConfig,
+    // Per-second easing of the bird's tilt toward its velocity; 0 snaps it every frame
+    rotation_smoothing: f32,
+    gap_shrink: GapShrink,
+}
 
-    fn target_score(&self) -> u32 {
-        if self.current_checkpoint_index < self.checkpoints.len() {
-            self.checkpoints[self.current_checkpoint_index]
-        } else {
-            self.checkpoints.last().copied().unwrap_or(0)
-        }
-    }
+// Endless gaps shrink by GAP_SHRINK_STEP every GAP_SHRINK_POINTS, down to `floor` x the gap
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+struct GapShrink {
+    enabled: bool,
+    floor: f32,
+}
 
-    fn is_final_checkpoint(&self) -> bool {
-        self.current_checkpoint_index >= self.checkpoints.len() - 1
-    }
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum FlapModel {#[default] Classic, Impulse}
+
+// Flap model per difficulty, plus the Impulse model's constants
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+struct FlapConfig {
+    easy: FlapModel,
+    normal: FlapModel,
+    hard: FlapModel,
+    insane: FlapModel,
+    // Velocity added by one flap, as a fraction of the flap force
+    impulse: f32,
+    // Physics steps one flap is spread over, 1-3
+    ticks: u32,
 }
 
-// ----------------- SERIALIZATION & DESERIALIZATION ---------------
-// Custom serialization/deserialization for enums
-impl Serialize for GameMode {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(match self {
-            GameMode::Endless => "Endless",
-            GameMode::TimeAttack => "TimeAttack",
-            GameMode::Checkpoints => "Checkpoints",
-        })
-    }
+// When a sound starts, still-playing sounds of lower priority drop to `amount` of their
+// volume for `duration` seconds; the priorities are only compared with each other
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+struct SfxDucking {
+    amount: f32,
+    duration: f32,
+    flap: u8,
+    coin: u8,
+    point: u8,
+    milestone: u8,
+    die: u8,
 }
 
-impl<'de> Deserialize<'de> for GameMode {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "Endless" => Ok(GameMode::Endless),
-            "TimeAttack" => Ok(GameMode::TimeAttack),
-            "Checkpoints" => Ok(GameMode::Checkpoints),
-            _ => Err(serde::de::Error::custom("Invalid game mode")),
-        }
-    }
+// Tags a one-shot sound effect for the ducking bus
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+enum SfxKind {Flap, Coin, Point, Milestone, Die}
+
+struct SfxVoice {
+    entity: Entity,
+    kind: SfxKind,
+    // Seconds of ducking left; 0 means playing at full volume
+    ducked: f32,
 }
 
-impl Serialize for Difficulty {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(match self {
-            Difficulty::Easy => "Easy",
-            Difficulty::Normal => "Normal",
-            Difficulty::Hard => "Hard",
-        })
-    }
+// Tagged sounds that are still playing, so newer louder ones can duck them
+#[derive(Resource, Default)]
+struct SfxBus {
+    voices: Vec<SfxVoice>,
 }
 
-impl<'de> Deserialize<'de> for Difficulty {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "Easy" => Ok(Difficulty::Easy),
-            "Normal" => Ok(Difficulty::Normal),
-            "Hard" => Ok(Difficulty::Hard),
-            _ => Err(serde::de::Error::custom("Invalid difficulty")),
-        }
-    }
+// Fonts shared by every screen; both fall back to Bevy's built-in font if the file is missing
+#[derive(Resource)]
+struct UiFonts {
+    title: Handle<Font>,
+    body: Handle<Font>,
+    // Mirrors AudioSettings::ui_scale; every TextFont size goes through scaled()
+    scale: f32,
 }
 
-impl Serialize for Theme {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(match self {
-            Theme::Classic => "Classic",
-            Theme::HighContrast => "HighContrast",
-            Theme::Minimal => "Minimal",
-        })
-    }
+// HUD text rewrites; F3 shows the per-second rate in a corner overlay
+#[derive(Resource, Default)]
+struct HudStats {
+    updates: u32,
+    elapsed: f32,
+    per_second: u32,
+    visible: bool,
+    // Longest frame of the running second and of the last whole one, in seconds
+    worst_frame: f32,
+    last_worst_frame: f32,
+    // RunScoped entities alive at the last roll; warned about once past RUN_ENTITY_CAP
+    run_entities: usize,
+    over_cap: bool,
 }
 
-impl<'de> Deserialize<'de> for Theme {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "Classic" => Ok(Theme::Classic),
-            "HighContrast" => Ok(Theme::HighContrast),
-            "Minimal" => Ok(Theme::Minimal),
-            _ => Err(serde::de::Error::custom("Invalid theme")),
-        }
-    }
+// Sound effects still waiting to be loaded and played silently
+#[derive(Resource, Default)]
+struct AudioPrewarm {
+    pending: Vec<Handle<AudioSource>>,
+    // Strong handles to the played clips, so they stay loaded until a run asks for them
+    warm: Vec<Handle<AudioSource>>,
+    elapsed: f32,
 }
 
-impl Serialize for Skin {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(match self {
-            Skin::Classic => "Classic",
-            Skin::Red => "Red",
-            Skin::Blue => "Blue",
-            Skin::Green => "Green",
-        })
+impl AudioPrewarm {
+    fn is_done(&self) -> bool {
+        self.pending.is_empty() || self.elapsed >= AUDIO_PREWARM_TIMEOUT
     }
 }
 
-impl<'de> Deserialize<'de> for Skin {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        match s.as_str() {
-            "Classic" => Ok(Skin::Classic),
-            "Red" => Ok(Skin::Red),
-            "Blue" => Ok(Skin::Blue),
-            "Green" => Ok(Skin::Green),
-            _ => Err(serde::de::Error::custom("Invalid skin")),
-        }
-    }
+#[derive(Component)]
+struct HudStatsOverlay;
+
+// On every top-level entity a run spawns (bird, pipes, pickups, particles, HUD);
+// cleanup_game despawns exactly these. Toasts and self-despawning sounds outlive the run
+#[derive(Component)]
+struct RunScoped;
+
+// Values the main HUD texts currently show
+#[derive(Default)]
+struct HudShown {
+    score: Option<u32>,
+    best: Option<u32>,
+    combo: Option<u32>,
 }
 
-// ---------------------------- MAIN ----------------------------
-// Entry point of the application, adding each system and updating accordingly
-fn main() {
-    App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: String::from("Flappy Bird"),
-                        position: WindowPosition::Centered(MonitorSelection::Primary),
-                        resolution: (800, 600).into(),
-                        ..Default::default()
-                    }),
-                    ..Default::default()
-                })
-                .set(ImagePlugin::default_nearest()),
-        )
-        .init_state::<GameState>()
-        .init_resource::<GameSettings>()
-        .init_resource::<SaveSlotChanged>()
-        .init_resource::<SaveSelectOrigin>()
-        .add_systems(Startup, (setup_save_system, setup_main_menu))
-        .add_systems(OnEnter(GameState::MainMenu), setup_main_menu_ui)
-        .add_systems(OnExit(GameState::MainMenu), cleanup_menu::<MainMenuMarker>)
-        .add_systems(OnEnter(GameState::Options), setup_options_ui)
-        .add_systems(OnExit(GameState::Options), cleanup_menu::<OptionsMarker>)  
-        .add_systems(OnEnter(GameState::SaveSelect), setup_save_select_ui)
-        .add_systems(OnExit(GameState::SaveSelect), cleanup_menu::<SaveSelectMarker>)
-        .add_systems(OnEnter(GameState::Leaderboard), setup_leaderboard_ui)
-        .add_systems(OnExit(GameState::Leaderboard), cleanup_menu::<LeaderboardMarker>)
-        .add_systems(OnEnter(GameState::ModeSelect), setup_mode_select_ui)
-        .add_systems(OnExit(GameState::ModeSelect), cleanup_menu::<ModeSelectMarker>)
-        .add_systems(OnEnter(GameState::DifficultySelect), setup_difficulty_select_ui)
-        .add_systems(OnExit(GameState::DifficultySelect), cleanup_menu::<DifficultySelectMarker>)
-        .add_systems(OnEnter(GameState::ThemeSelect), setup_theme_select_ui)
-        .add_systems(OnExit(GameState::ThemeSelect), cleanup_menu::<ThemeSelectMarker>)
-        .add_systems(OnEnter(GameState::SkinSelect), setup_skin_select_ui)
-        .add_systems(OnExit(GameState::SkinSelect), cleanup_menu::<SkinSelectMarker>)
-        .add_systems(OnEnter(GameState::Playing), (setup_level, reset_on_play_start).chain())
-        .add_systems(OnExit(GameState::Playing), cleanup_game)
-        .add_systems(OnEnter(GameState::GameOver), setup_game_over_ui)
-        .add_systems(OnExit(GameState::GameOver), cleanup_menu::<GameOverMarker>)
-        .add_systems(OnEnter(GameState::Victory), setup_victory_ui)
-        .add_systems(OnExit(GameState::Victory), cleanup_menu::<VictoryScreenMarker>)
-        .add_systems(Update, (
-            main_menu_system.run_if(in_state(GameState::MainMenu)),
-            options_system.run_if(in_state(GameState::Options)),
-            save_select_system.run_if(in_state(GameState::SaveSelect)),
-            refresh_save_select_ui.run_if(in_state(GameState::SaveSelect)),
-            mode_select_system.run_if(in_state(GameState::ModeSelect)),
-            difficulty_select_system.run_if(in_state(GameState::DifficultySelect)),
-            theme_select_system.run_if(in_state(GameState::ThemeSelect)),
-            skin_select_system.run_if(in_state(GameState::SkinSelect)),
-            update_bird.run_if(in_state(GameState::Playing)),
-            update_obstacles.run_if(in_state(GameState::Playing)),
-            update_ui.run_if(in_state(GameState::Playing)),
-            update_time_attack.run_if(in_state(GameState::Playing)),
-            update_checkpoints.run_if(in_state(GameState::Playing)),
-            handle_escape_in_checkpoint.run_if(in_state(GameState::Playing)),
-            handle_game_over.run_if(in_state(GameState::GameOver)),
-            handle_victory.run_if(in_state(GameState::Victory)),
-            leaderboard_system.run_if(in_state(GameState::Leaderboard)),
-        ))
-        .run();
+#[derive(Resource, Default)]
+struct MenuCursor {
+    index: usize,
 }
 
-fn load_leaderboard() -> Vec<LeaderboardEntry> {
-    let mut entries = Vec::new();
+#[derive(Resource)]
+struct ResumeCountdown {
+    remaining: f32,
+    buffered_flap: bool,
+}
 
-    for slot in 1..=3 {
-        if let Some(save) = load_save_slot(slot) {
-            entries.push(LeaderboardEntry {
-                name: save.profile.name.clone(),
-                score: save.score,
-                mode: save.mode,
-                difficulty: save.difficulty,
-            });
-        }
-    }
+// Flap pressed during the resume countdown, performed on the first running frame
+#[derive(Resource)]
+struct HeldFlap;
+
+// Input and frame delta for one simulation frame. Sampled once per running frame
+// (or fed back from a replay) so a run can be re-simulated exactly from its seed
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Default)]
+struct SimStep {
+    dt: f32,
+    flap: bool,
+}
 
-    // Sort descending by score
-    entries.sort_by(|a, b| b.score.cmp(&a.score));
-    entries
+// Every step of the current run, kept after it ends so Game Over can export it
+#[derive(Resource, Default)]
+struct RunRecording {
+    bird_size: BirdSize,
+    steps: Vec<SimStep>,
 }
 
-fn setup_leaderboard_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let entries = load_leaderboard();
-    let window = window_query.single().expect("Missing primary window");
-    let window_width = window.width();
-    let window_height = window.height();
+// Shareable .flappyreplay file: the course seed, the settings that shape the
+// simulation and the full step log
+#[derive(Serialize, Deserialize)]
+struct ReplayFile {
+    version: u32,
+    seed: u64,
+    mode: GameMode,
+    difficulty: Difficulty,
+    theme: Theme,
+    skin: Skin,
+    bird_size: BirdSize,
+    spawn_strategy: SpawnStrategy,
+    level: Option<String>,
+    #[serde(default)]
+    trainer_gap: Option<f32>,
+    #[serde(default)]
+    modifiers: RunModifiers,
+    #[serde(default)]
+    overtime: bool,
+    #[serde(default)]
+    start_score: u32,
+    #[serde(default)]
+    score_goal: Option<u32>,
+    #[serde(default)]
+    gauntlet_start: bool,
+    #[serde(default)]
+    fill_window: bool,
+    #[serde(default)]
+    custom_tuning: CustomTuning,
+    // The recording player's config.ron; older files play back on the viewer's own
+    #[serde(default)]
+    config: Option<GameConfig>,
+    score: u32,
+    steps: Vec<SimStep>,
+}
 
-    // Background
-    commands.spawn((
-        Sprite {
-            image: asset_server.load("Background2.png"),
-            custom_size: Some(Vec2::new(window_width, window_height)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-        Background,
-        LeaderboardMarker,
-    ));
+// Read first so files from other versions are rejected before parsing the rest
+#[derive(Deserialize)]
+struct ReplayHeader {
+    version: u32,
+}
 
-    // UI Container
-    commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
-        LeaderboardMarker,
-    ))
-    .with_children(|parent| {
-        parent.spawn((
-            Text::new("LEADERBOARD"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 48.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextShadow::default(),
-            Node { margin: UiRect::all(Val::Px(20.0)), ..default() },
-        ));
+// Present while a replay is being watched; the player's own settings come back on the menu
+#[derive(Resource)]
+struct ReplayPlayback {
+    steps: Vec<SimStep>,
+    cursor: usize,
+    recorded_score: u32,
+    restore: GameSettings,
+    restore_config: GameConfig,
+    restore_trainer: TrainerTuning,
+}
 
-        for (i, entry) in entries.iter().enumerate() {
-            parent.spawn((
-                Text::new(format!(
-                    "{}. {} - {} pts [{:?} {:?}]",
-                    i + 1,
-                    entry.name,
-                    entry.score,
-                    entry.mode,
-                    entry.difficulty
-                )),
-                TextFont { 
-                    font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                    font_size: 28.0, 
-                    ..default() 
-                },
-                TextColor(Color::srgb(0.9, 0.9, 0.9)),
-                TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
-                TextShadow::default(),
-                Node { margin: UiRect::all(Val::Px(5.0)), ..default() },
-            ));
-        }
+// Bird poses from the last few seconds of play, oldest first
+#[derive(Resource, Default)]
+struct DeathTrail {
+    elapsed: f32,
+    samples: std::collections::VecDeque<(f32, Vec3, Quat)>,
+}
 
-        parent.spawn((
-            Text::new("Retrun [ESC]"),
-            TextFont { 
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0, 
-                ..default() 
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node { margin: UiRect::top(Val::Px(20.0)), ..default() },
-        ));
-    });
+// Frozen snapshot of the moment a run ended, shown on Game Over
+#[derive(Resource)]
+struct DeathReplay {
+    samples: Vec<(f32, Vec3, Quat)>,
+    pipes: Vec<Transform>,
+    pipe_image: Handle<Image>,
+    bird_image: Handle<Image>,
+    bird_scale: Vec3,
+    // Playfield size of the run, so the corner view frames the same area
+    field: Vec2,
 }
 
-fn leaderboard_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::MainMenu);
-    }
+// Seconds since the last input on an idle-eligible screen
+#[derive(Resource, Default)]
+struct IdleTimer {
+    elapsed: f32,
 }
 
-// Save system setup
-fn setup_save_system(_commands: Commands) {
-    // Create saves directory if it doesn't exist
-    if let Err(e) = fs::create_dir_all("saves") {
-        eprintln!("Failed to create saves directory: {}", e);
-    }
+// Set on every GameState change so one key press can't carry through several screens
+#[derive(Resource, Default)]
+struct InputDebounce {
+    remaining: f32,
+    // Keys down during the change; they only act again after being released
+    held: Vec<KeyCode>,
 }
 
-fn setup_main_menu(mut commands: Commands) {
-    // Basic 2D camera for UI
-    commands.spawn(Camera2d);
+// The arrow key being held in a menu and when it next repeats
+#[derive(Resource, Default)]
+struct NavRepeat {
+    key: Option<KeyCode>,
+    held: f32,
+    next: f32,
 }
 
-fn load_save_slot(slot: u32) -> Option<SaveSlot> {
-    let path = format!("saves/slot_{}.json", slot);
-    if Path::new(&path).exists() {
-        if let Ok(contents) = fs::read_to_string(&path) {
-            serde_json::from_str(&contents).ok()
-        } else {
-            None
-        }
-    } else {
-        None
-    }
+// Leaderboard name being typed on Game Over for the run just saved to `slot`
+#[derive(Resource)]
+struct NamePrompt {
+    slot: u8,
+    default: String,
+    text: String,
+    // Until something is typed, SPACE keeps the default name
+    edited: bool,
 }
 
-fn save_to_slot(slot: &SaveSlot) -> Result<(), Box<dyn std::error::Error>> {
-    let path = format!("saves/slot_{}.json", slot.slot_number);
-    let json = serde_json::to_string_pretty(slot)?;
-    fs::write(&path, json)?;
-    Ok(())
+#[derive(Component)]
+struct NamePromptText;
+
+// Queued state change played through a fade to black; 0 alpha is clear, 1 fully black
+#[derive(Resource, Default)]
+struct ScreenFade {
+    target: Option<GameState>,
+    alpha: f32,
 }
 
-fn cleanup_menu<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
-    for entity in &query {
-        commands.entity(entity).despawn();
-    }
+// Per-run clocks for wind and flap cooldown, reset when a run starts
+#[derive(Resource)]
+struct ModifierState {
+    elapsed: f32,
+    since_flap: f32,
+    // Score the last flip pickup was spawned at
+    flip_pickup_score: u32,
 }
 
-fn cleanup_game(
-    mut commands: Commands,
-    bird_query: Query<Entity, With<Bird>>,
-    obstacle_query: Query<Entity, With<Obstacle>>,
-    ui_query: Query<Entity, Or<(With<ScoreDisplay>, With<BestScoreDisplay>, With<TimeDisplay>, With<CheckpointDisplay>, With<VictoryMessage>)>>,
-    background_query: Query<Entity, With<Background>>,
-) {
-    // Tear down everything that belongs to a run before returning to menus
-    for entity in &bird_query {
-        commands.entity(entity).despawn();
-    }
-    for entity in &obstacle_query {
-        commands.entity(entity).despawn();
-    }
-    for entity in &ui_query {
-        commands.entity(entity).despawn();
-    }
-    for entity in &background_query {
-        commands.entity(entity).despawn();
-    }
+// Aggregates for the current app session, summarized on quit
+#[derive(Resource, Default)]
+struct SessionStats {
+    runs: u32,
+    total_points: u32,
+    best_run: u32,
+    total_coins: u32,
+    // Runs already covered by a main menu summary
+    summarized_runs: u32,
+}
 
-    commands.remove_resource::<TimeAttackState>();
-    commands.remove_resource::<CheckpointsState>();
+#[derive(Component)]
+struct SessionSummaryOverlay {
+    remaining: f32,
 }
 
-// Main Menu UI
-fn setup_main_menu_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>, settings: Res<GameSettings>) {
-    // Neutral background for menus so theme colors from gameplay don't stick
-    let window = window_query.single().expect("Missing primary window");
-    let window_width = window.width();
-    let window_height = window.height();
+#[derive(Resource, Default)]
+struct OptionsChanged {
+    changed: bool,
+}
 
-    commands.spawn((
-                Sprite {
-                    image: asset_server.load("Background2.png"),
-                    custom_size: Some(Vec2::new(window_width, window_height)),
-                    ..default()
-                },
-                Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-                Background,
-                MainMenuMarker,
-            ));
+// Feedback line for run code copy/paste, shown on Options and Game Over
+#[derive(Resource, Default)]
+struct RunCodeStatus {
+    message: Option<String>,
+}
 
-    // Loop menu music
+#[derive(Component)]
+struct RunCodeStatusDisplay;
+
+#[derive(Resource, Debug)]
+pub struct Score {
+    pub current: u32,
+    pub best: u32,
+    pub scored_pipes: Vec<Entity>,
+    pub combo: u32,
+    pub combo_timer: f32,
+    pub combo_flash: f32,
+    pub precision: PrecisionStats,
+    pub log: RunLog,
+    // Unlocks already announced this run
+    unlocks: Vec<Difficulty>,
+    // Started on an edited config.ron or had it reloaded mid-run, so the run isn't ranked
+    custom_config: bool,
+    // Set as a ranked run is saved, from the profile as it was before this run joined it
+    grade: Option<char>,
+}
+
+// Compact post-mortem of the current run; only kept until Game Over is left
+#[derive(Default, Debug)]
+pub struct RunLog {
+    // Simulated seconds since the run started, used as event timestamps
+    elapsed: f32,
+    events: std::collections::VecDeque<(f32, RunEvent)>,
+    flaps: u32,
+    near_misses: u32,
+    // Tightest pass of the run in px
+    closest: Option<f32>,
+    // Pair the bird is flying through and its smallest clearance so far
+    threading: Option<(u32, f32)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum RunEvent {
+    // Consecutive flaps share one entry
+    Flaps(u32),
+    Passed { pipe: u32, clearance: f32 },
+    NearMiss { pipe: u32, clearance: f32 },
+    Pickup(&'static str),
+    // pipe is the (pair, center) that was hit; None for the floor or ceiling
+    Death { bird: Vec2, velocity: f32, pipe: Option<(u32, Vec2)> },
+}
+
+// How close to the gap center each pipe was passed this run
+#[derive(Clone, Copy, Default, Debug)]
+pub struct PrecisionStats {
+    pub perfect: u32,
+    pub good: u32,
+    pub ok: u32,
+    // Sum of per-pipe accuracy, 1.0 being dead center
+    pub total: f32,
+    // Perfect passes in a row; any other pass or a death resets it
+    pub streak: u32,
+    pub best_streak: u32,
+}
+
+#[derive(Resource)]
+pub struct GameManager {
+    pub pipe_image: Handle<Image>,
+    pub window_dimensions: Vec2,
+}
+
+#[derive(Resource)]
+pub struct SoundEffects {
+    pub flap: Handle<AudioSource>,
+    pub point: Handle<AudioSource>,
+    pub die: Handle<AudioSource>,
+    pub swoosh: Handle<AudioSource>,
+    // Notes of the Time Attack victory jingle, in JINGLE_NOTES order
+    pub jingle: Vec<Handle<Pitch>>,
+}
+
+#[derive(Resource)]
+struct TimeAttackState {
+    remaining: f32,
+    duration: f32,
+    // Score already turned into overtime seconds
+    credited_score: u32,
+}
+
+// Rounds played so far in a Tournament; lives across Playing sessions until the menu
+#[derive(Resource, Default)]
+struct TournamentState {
+    round: usize,
+    scores: Vec<u32>,
+}
+
+// Progress through the level being played; the spawner hands out its pipes in order
+#[derive(Resource)]
+struct LevelRun {
+    def: LevelDef,
+    next_pipe: usize,
+    coins: u32,
+    elapsed: f32,
+}
+
+// Shown on the Level Complete screen after the run has been torn down
+#[derive(Resource)]
+struct LevelResult {
+    name: String,
+    score: u32,
+    coins: u32,
+    total_coins: u32,
+    time: f32,
+}
+
+// Run-scoped gap shrink progress, read whenever pipes are recycled or streamed in
+#[derive(Resource)]
+struct GapProgress {
+    floor: Option<f32>,
+    step: u32,
+    factor: f32,
+    last_offset: f32,
+}
+
+#[derive(Resource)]
+struct CheckpointsState {
+    checkpoints: Vec<u32>,
+    current_checkpoint_index: usize,
+    completed: bool,
+    last_checkpoint_score: u32,
+}
+
+// Seeded generator for pipe offsets so a run can be replayed from its code
+#[derive(Resource)]
+struct CourseRng {
+    seed: u64,
+    rng: StdRng,
+    // Separate stream for flap pitches, so toggling the variation keeps layouts identical
+    pitch: StdRng,
+}
+
+// Gap trainer override: Normal tuning with every pipe at this gap, no ramp
+#[derive(Resource, Clone, Copy)]
+struct TrainerTuning {
+    gap_size: f32,
+}
+
+// HUD colors per theme, picked at run start
+#[derive(Resource, Clone, Copy)]
+struct ThemePalette {
+    hud_text: Color,
+    accent: Color,
+    shadow: bool,
+    // Clear color, also used for the letterbox bars
+    background: Color,
+    // Secondary text and the backing behind text on the Game Over screen
+    muted: Color,
+    panel: Color,
+}
+
+#[derive(Resource, Clone, Copy, Debug)]
+struct DifficultyTuning {
+    gap_size: f32,
+    scroll_speed: f32,
+    gravity_mult: f32,
+    flap_mult: f32,
+    vertical_offset: f32,
+    // Distance between neighbouring pipe pairs, shared by spawning and recycling
+    spacing: f32,
+    flap: FlapStyle,
+}
+
+// How a flap changes the bird's velocity. Classic sets it to the flap force outright;
+// Impulse adds `strength` x the force to the current velocity over `ticks` physics steps
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FlapStyle {
+    Classic,
+    Impulse { strength: f32, ticks: u32 },
+}
+
+#[derive(Component)]
+struct Bird {
+    pub velocity: f32,
+    // Shown nose angle in degrees, eased toward the velocity's so it doesn't snap or jitter with the frame rate
+    tilt: f32,
+    // Impulse flap still being applied: velocity added per step and steps left
+    flap_boost: f32,
+    flap_ticks: u32,
+}
+
+// Purely visual scale pulse on the bird; positive amount stretches vertically,
+// negative squashes. Collisions keep using the bird's logical size
+#[derive(Component)]
+struct ScaleAnimation {
+    elapsed: f32,
+    duration: f32,
+    amount: f32,
+}
+
+// Temporary pipe immunity after a respawn, blinking the sprite while active
+#[derive(Component)]
+struct Invulnerable {
+    timer: Timer,
+    blink: Timer,
+}
+
+#[derive(Component)]
+struct ScoreDisplay;
+
+#[derive(Component)]
+struct BestScoreDisplay;
+
+// Red edge overlay that fades in as the bird nears the floor
+#[derive(Component)]
+struct FloorWarning {
+    tick_cooldown: f32,
+}
+
+// Looping sine tone whose pitch follows the next gap (sonification)
+#[derive(Component)]
+struct GapTone {
+    update_timer: Timer,
+}
+
+// Marks the spatial gap tone of a blind run, driven by update_blind_tone instead
+#[derive(Component)]
+struct BlindTone;
+
+#[derive(Component)]
+struct ComboBarFrame;
+
+#[derive(Component)]
+struct ComboBar;
+
+#[derive(Component)]
+struct ComboDisplay;
+
+#[derive(Component)]
+struct TimeDisplay;
+
+#[derive(Component)]
+struct CheckpointDisplay;
+
+#[derive(Component)]
+struct LevelDisplay;
+
+#[derive(Component)]
+struct TrainerDisplay;
+
+#[derive(Component)]
+struct GapToast {
+    remaining: f32,
+}
+
+// Gravity is upside down on the bird while this lasts: it falls up, flaps push
+// it down, and the top of the screen becomes the lethal edge
+#[derive(Component)]
+struct GravityFlip {
+    remaining: f32,
+}
+
+#[derive(Component)]
+struct FlipPickup;
+
+// Marker on the view's edge at the height of the next gap still out of view
+#[derive(Component)]
+struct GapTelegraph;
+
+// Line through the gap of pair `target`, where the profile's Endless record ends
+#[derive(Component)]
+struct RecordMarker {
+    target: u32,
+}
+
+#[derive(Component)]
+struct FlipIndicator;
+
+#[derive(Component)]
+struct Coin;
+
+// Time Attack survived: gameplay is frozen while the celebration plays, then Game Over
+#[derive(Resource)]
+struct TimeAttackFinish {
+    timer: Timer,
+    notes_played: usize,
+}
+
+// Pooled confetti sprite, hidden until a Time Attack finish
+#[derive(Component)]
+struct Confetti {
+    velocity: Vec2,
+    spin: f32,
+}
+
+#[derive(Component)]
+struct FinishBanner;
+
+// Floats over the empty slot of a parked pair
+#[derive(Component)]
+struct RestStopBanner;
+
+// "+1s" under the Time Attack timer when overtime adds a second
+#[derive(Component)]
+struct OvertimeToast {
+    remaining: f32,
+}
+
+// Config reload result or save failure; outlives screen changes and despawns itself
+#[derive(Component)]
+struct Toast {
+    remaining: f32,
+}
+
+// Bird in the Game Over corner view; `time` is the playback position in trail seconds
+#[derive(Component)]
+struct DeathReplayBird {
+    time: f32,
+}
+
+#[derive(Component)]
+struct Background;
+#[derive(Component)]
+struct Obstacle {
+    pipe_direction: f32,
+    scored: bool,
+    // Half the gap this pipe was placed for, to find the gap center when scoring
+    gap_size: f32,
+    // 1-based position of the pair in the run; passing pair n makes the score n
+    index: u32,
+    // Gap whoosh already played for this pair
+    whooshed: bool,
+    // Rest stop: moved out of view for one pass through the pool, holding no index
+    parked: bool,
+}
+
+// ---------------------------- IMPLEMENTATIONS ----------------------------
+// Defines main funcionalities for default values
+impl Default for PlayerProfile {
+    fn default() -> Self {
+        Self {
+            name: String::from("Player"),
+            high_score: 0,
+            total_games: 0,
+            average_score: 0.0,
+            longest_survival: 0.0,
+            sessions: 0,
+            best_session: 0,
+            tournament_best: 0,
+            avatar: 0,
+            history: Vec::new(),
+            history_interrupted: Vec::new(),
+            monthly: std::collections::BTreeMap::new(),
+            best_precision: 0.0,
+            mode_bests: std::collections::HashMap::new(),
+            mastered: Vec::new(),
+            unlocked: Vec::new(),
+            best_perfect_streak: 0,
+            death_pipes: Vec::new(),
+            weekly_bests: std::collections::HashMap::new(),
+            coins: 0,
+            owned_cosmetics: Vec::new(),
+            trail: TrailColor::Off,
+            flap_sound: FlapSound::Classic,
+        }
+    }
+}
+
+impl NavStack {
+    // The screen ESC returns to, or the usual parent when the stack is empty
+    fn back(&self, fallback: GameState) -> GameState {
+        self.screens.last().copied().unwrap_or(fallback)
+    }
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            current_slot: None,
+            selected_mode: GameMode::Endless,
+            selected_difficulty: Difficulty::Normal,
+            selected_theme: Theme::Classic,
+            selected_skin: Skin::Classic,
+            seed: None,
+            photosensitive_safe: false,
+            bird_size: BirdSize::Normal,
+            floor_warning: None,
+            floor_warning_sound: true,
+            gap_tone: false,
+            blind_run: false,
+            spawn_strategy: SpawnStrategy::Recycle,
+            selected_level: None,
+            reduce_motion: false,
+            modifiers: RunModifiers::default(),
+            overtime: false,
+            record_marker: true,
+            gap_telegraph: false,
+            start_score: 0,
+            score_goal: None,
+            gauntlet_start: false,
+            fill_window: false,
+            camera_zoom: 1.0,
+            custom_tuning: CustomTuning::default(),
+            weekly: None,
+        }
+    }
+}
+
+impl Default for CustomTuning {
+    fn default() -> Self {
+        Self { gap: 1.0, speed: 1.0, gravity: 1.0, flap: 1.0, offset: 1.0 }
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self {
+            current: 0,
+            best: 0,
+            scored_pipes: Vec::new(),
+            combo: 0,
+            combo_timer: 0.0,
+            combo_flash: 0.0,
+            precision: PrecisionStats::default(),
+            log: RunLog::default(),
+            unlocks: Vec::new(),
+            custom_config: false,
+            grade: None,
+        }
+    }
+}
+
+impl PrecisionStats {
+    // offset is the distance from the gap center as a fraction of half the gap
+    fn record(&mut self, offset: f32) {
+        if offset <= PRECISION_PERFECT {
+            self.perfect += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else if offset <= PRECISION_GOOD {
+            self.good += 1;
+            self.streak = 0;
+        } else {
+            self.ok += 1;
+            self.streak = 0;
+        }
+        self.total += (1.0 - offset).clamp(0.0, 1.0);
+    }
+
+    // Average accuracy in percent, None before the first pipe
+    fn average(&self) -> Option<f32> {
+        let pipes = self.perfect + self.good + self.ok;
+        (pipes > 0).then(|| self.total / pipes as f32 * 100.0)
+    }
+}
+
+impl RunLog {
+    fn push(&mut self, event: RunEvent) {
+        if let (RunEvent::Flaps(count), Some((_, RunEvent::Flaps(total)))) = (event, self.events.back_mut()) {
+            *total += count;
+            return;
+        }
+        // A pair the bird died in was never passed
+        if let RunEvent::Death { .. } = event {
+            self.threading = None;
+        }
+        self.events.push_back((self.elapsed, event));
+        if self.events.len() > RUN_LOG_LEN {
+            self.events.pop_front();
+        }
+    }
+
+    fn flap(&mut self) {
+        self.flaps += 1;
+        self.push(RunEvent::Flaps(1));
+    }
+
+    // Called every step with the pair the bird overlaps (and its clearance); the pass
+    // is logged once the bird is out of that pair
+    fn thread(&mut self, overlap: Option<(u32, f32)>) {
+        match (self.threading, overlap) {
+            (Some((pipe, closest)), Some((current, clearance))) if pipe == current => {
+                self.threading = Some((pipe, closest.min(clearance)));
+            }
+            (previous, current) => {
+                if let Some((pipe, clearance)) = previous {
+                    self.closest = Some(self.closest.map_or(clearance, |closest| closest.min(clearance)));
+                    if clearance < NEAR_MISS_CLEARANCE {
+                        self.near_misses += 1;
+                        self.push(RunEvent::NearMiss { pipe, clearance });
+                    } else {
+                        self.push(RunEvent::Passed { pipe, clearance });
+                    }
+                }
+                self.threading = current;
+            }
+        }
+    }
+}
+
+impl RunEvent {
+    fn describe(&self) -> String {
+        match *self {
+            RunEvent::Flaps(1) => "Flap".to_string(),
+            RunEvent::Flaps(count) => format!("Flap x{}", count),
+            RunEvent::Passed { pipe, clearance } => format!("Passed pipe {} with {:.0}px to spare", pipe, clearance),
+            RunEvent::NearMiss { pipe, clearance } => format!("NEAR MISS: pipe {} with {:.0}px to spare", pipe, clearance),
+            RunEvent::Pickup(name) => format!("Picked up {}", name),
+            RunEvent::Death { bird, velocity, pipe: Some((pipe, center)) } => format!(
+                "Hit the {} pipe of pair {} at ({:.0}, {:.0}), moving {:.0} px/s; pipe center ({:.0}, {:.0})",
+                if center.y > bird.y { "top" } else { "bottom" },
+                pipe,
+                bird.x,
+                bird.y,
+                velocity,
+                center.x,
+                center.y,
+            ),
+            RunEvent::Death { bird, velocity, pipe: None } => format!(
+                "Hit the {} at ({:.0}, {:.0}), moving {:.0} px/s",
+                if bird.y < 0.0 { "floor" } else { "ceiling" },
+                bird.x,
+                bird.y,
+                velocity,
+            ),
+        }
+    }
+}
+
+impl ScaleAnimation {
+    fn new(duration: f32, amount: f32) -> Self {
+        Self { elapsed: 0.0, duration, amount }
+    }
+
+    // Multiplier for the base scale, easing out back to 1
+    fn factor(&self) -> Vec3 {
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        let strength = self.amount * (1.0 - t) * (1.0 - t);
+        Vec3::new(1.0 - strength / 2.0, 1.0 + strength, 1.0)
+    }
+}
+
+impl BirdSize {
+    fn scale(self) -> f32 {
+        match self {
+            BirdSize::Normal => 1.0,
+            BirdSize::Tiny => TINY_BIRD_SCALE,
+            BirdSize::Giant => GIANT_BIRD_SCALE,
+        }
+    }
+
+    // Pressing the active size's key again goes back to Normal
+    fn toggled(self, size: BirdSize) -> BirdSize {
+        if self == size { BirdSize::Normal } else { size }
+    }
+}
+
+impl GameSettings {
+    fn floor_warning_enabled(&self) -> bool {
+        self.floor_warning.unwrap_or(self.selected_difficulty < Difficulty::Hard)
+    }
+}
+
+impl CourseRng {
+    fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            pitch: StdRng::seed_from_u64(seed ^ FLAP_PITCH_SALT),
+        }
+    }
+
+    fn restart(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+        self.pitch = StdRng::seed_from_u64(self.seed ^ FLAP_PITCH_SALT);
+    }
+}
+
+impl Invulnerable {
+    fn after_respawn() -> Self {
+        Self {
+            timer: Timer::from_seconds(RESPAWN_INVULNERABILITY, TimerMode::Once),
+            blink: Timer::from_seconds(RESPAWN_BLINK_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            rumble_strength: default_rumble_strength(),
+            idle_timeout: default_idle_timeout(),
+            idle_in_menus: false,
+            ui_scale: default_ui_scale(),
+            seed_history: Vec::new(),
+            flap_pitch_variation: true,
+            last_slot: None,
+            greeting: true,
+            evening_theme: false,
+            name_prompt: true,
+            auto_score_card: false,
+            flap_key: FlapKey::Any,
+            low_power_menus: true,
+        }
+    }
+}
+
+impl AudioSettings {
+    // Theme for a session or slot without a saved one
+    fn default_theme(&self, clock: &LocalClock) -> Theme {
+        if self.evening_theme && is_evening(clock.hour()) { Theme::HighContrast } else { Theme::Classic }
+    }
+
+    fn music(&self) -> Volume {
+        Volume::Linear(MUSIC_BASE_VOLUME * self.music_volume)
+    }
+
+    fn sfx(&self, base: f32) -> Volume {
+        Volume::Linear(base * self.sfx_volume)
+    }
+
+    // Rumbles every connected gamepad; a no-op without one or with rumble off
+    fn rumble(
+        &self,
+        rumble_requests: &mut MessageWriter<GamepadRumbleRequest>,
+        gamepads: &Query<Entity, With<Gamepad>>,
+        strong: f32,
+        weak: f32,
+        duration_ms: u64,
+    ) {
+        if self.rumble_strength <= 0.0 {
+            return;
+        }
+        for gamepad in gamepads.iter() {
+            rumble_requests.write(GamepadRumbleRequest::Add {
+                gamepad,
+                duration: Duration::from_millis(duration_ms),
+                intensity: GamepadRumbleIntensity {
+                    strong_motor: strong * self.rumble_strength,
+                    weak_motor: weak * self.rumble_strength,
+                },
+            });
+        }
+    }
+}
+
+impl Default for TrainerTuning {
+    // Starts on Hard's gap, the one players usually want to drill
+    fn default() -> Self {
+        Self {
+            gap_size: OBSTACLE_GAP_SIZE * 0.75,
+        }
+    }
+}
+
+impl Theme {
+    fn palette(self) -> ThemePalette {
+        match self {
+            Theme::Classic => ThemePalette {
+                hud_text: Color::srgb(1.0, 0.992, 0.816),
+                accent: AQUAMARINE.into(),
+                shadow: true,
+                background: Color::BLACK,
+                muted: Color::srgb(0.9, 0.9, 0.9),
+                panel: Color::BLACK.with_alpha(0.2),
+            },
+            Theme::HighContrast => ThemePalette {
+                hud_text: Color::srgb(1.0, 1.0, 0.0),
+                accent: Color::srgb(0.0, 1.0, 1.0),
+                shadow: true,
+                background: Color::BLACK,
+                muted: Color::WHITE,
+                panel: Color::BLACK.with_alpha(0.75),
+            },
+            // Near-white background: dark text, shadows would only smear it
+            Theme::Minimal => ThemePalette {
+                hud_text: Color::srgb(0.12, 0.12, 0.12),
+                accent: Color::srgb(0.0, 0.45, 0.4),
+                shadow: false,
+                background: Color::srgb(0.95, 0.95, 0.95),
+                muted: Color::srgb(0.3, 0.3, 0.3),
+                panel: Color::srgb(0.95, 0.95, 0.95).with_alpha(0.8),
+            },
+        }
+    }
+}
+
+impl Theme {
+    fn sfx_folder(self) -> &'static str {
+        match self {
+            Theme::Classic => "classic",
+            Theme::HighContrast => "high_contrast",
+            Theme::Minimal => "minimal",
+        }
+    }
+
+    // Themed variant of a sound effect, or the default one when the theme doesn't ship it
+    fn sound(self, asset_server: &AssetServer, file: &str) -> Handle<AudioSource> {
+        let name = Path::new(file).file_stem().and_then(|stem| stem.to_str()).unwrap_or(file);
+        let themed = THEME_SFX_FORMATS
+            .iter()
+            .map(|format| format!("{}/{}/{}.{}", THEME_SFX_DIR, self.sfx_folder(), name, format))
+            .find(|themed| asset_file(themed).is_file());
+        asset_server.load(themed.unwrap_or_else(|| file.to_string()))
+    }
+}
+
+// Where the AssetServer reads an asset from, whatever the working directory is
+fn asset_file(path: impl AsRef<Path>) -> PathBuf {
+    bevy::asset::io::file::FileAssetReader::get_base_path().join(ASSETS_DIR).join(path)
+}
+
+impl ThemePalette {
+    fn text_shadow(&self) -> TextShadow {
+        if self.shadow {
+            TextShadow::default()
+        } else {
+            TextShadow { color: Color::NONE, ..default() }
+        }
+    }
+}
+
+impl GapProgress {
+    fn new(floor: Option<f32>) -> Self {
+        Self {
+            floor,
+            step: 0,
+            factor: 1.0,
+            last_offset: 0.0,
+        }
+    }
+
+    fn gap_size(&self, tuning: &DifficultyTuning) -> f32 {
+        tuning.gap_size * self.factor
+    }
+
+    // Shrink state follows from the score alone, so a run started at a higher score
+    // matches one that played up to it. Returns whether the gaps just tightened
+    fn sync(&mut self, score: u32) -> bool {
+        let Some(floor) = self.floor else { return false; };
+        let step_count = score / GAP_SHRINK_POINTS;
+        if step_count <= self.step || self.factor <= floor {
+            return false;
+        }
+        self.step = step_count;
+        self.factor = (1.0 - GAP_SHRINK_STEP * step_count as f32).max(floor);
+        true
+    }
+
+    // Consecutive gaps lean toward the previous one and may only drift as far as the bird can
+    // reach through the current gap, so a tightened course never asks for a climb that no longer fits
+    fn next_offset(&mut self, rand: &mut StdRng, tuning: &DifficultyTuning) -> f32 {
+        let max_shift = max_gap_shift(tuning, self.gap_size(tuning));
+        let offset = generate_offset(rand, tuning.vertical_offset)
+            .lerp(self.last_offset, OFFSET_SMOOTHING)
+            .clamp(self.last_offset - max_shift, self.last_offset + max_shift);
+        self.last_offset = offset;
+        offset
+    }
+}
+
+impl LevelDef {
+    // Rejects levels that would spawn overlapping pipes or gaps off screen
+    fn validate(&self) -> Result<(), String> {
+        if self.pipes.is_empty() {
+            return Err("no pipes".to_string());
+        }
+        for (i, pipe) in self.pipes.iter().enumerate() {
+            if !pipe.gap_size.is_finite() || pipe.gap_size <= 0.0 {
+                return Err(format!("pipe {}: gap_size must be positive", i + 1));
+            }
+            if !pipe.spacing.is_finite() || pipe.spacing < OBSTACLE_WIDTH {
+                return Err(format!("pipe {}: spacing must be at least {}", i + 1, OBSTACLE_WIDTH));
+            }
+            if !pipe.y_offset.is_finite() || pipe.y_offset.abs() > OBSTACLE_HEIGHT / 2. {
+                return Err(format!("pipe {}: y_offset must be within +/-{}", i + 1, OBSTACLE_HEIGHT / 2.));
+            }
+        }
+        Ok(())
+    }
+
+    fn total_coins(&self) -> u32 {
+        self.pipes.iter().filter(|pipe| pipe.coin).count() as u32
+    }
+}
+
+impl TournamentState {
+    fn total(&self) -> u32 {
+        self.scores.iter().sum()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.scores.len() >= TOURNAMENT_ROUNDS
+    }
+}
+
+impl CheckpointsState {
+    fn new(difficulty: Difficulty) -> Self {
+        let checkpoints = match difficulty {
+            Difficulty::Easy => vec![5, 10, 15, 20],
+            Difficulty::Normal => vec![10, 20, 30, 40],
+            Difficulty::Hard => vec![15, 30, 45, 60],
+            Difficulty::Insane => vec![20, 40, 60, 80],
+        };
+        Self {
+            checkpoints,
+            current_checkpoint_index: 0,
+            completed: false,
+            last_checkpoint_score: 0,
+        }
+    }
+
+    fn target_score(&self) -> u32 {
+        if self.current_checkpoint_index < self.checkpoints.len() {
+            self.checkpoints[self.current_checkpoint_index]
+        } else {
+            self.checkpoints.last().copied().unwrap_or(0)
+        }
+    }
+
+    fn is_final_checkpoint(&self) -> bool {
+        self.current_checkpoint_index >= self.checkpoints.len() - 1
+    }
+}
+
+impl ScreenFade {
+    // Fades out, switches state, then fades back in; ignored while another switch is pending
+    fn request_transition(&mut self, target: GameState) {
+        if self.target.is_none() {
+            self.target = Some(target);
+        }
+    }
+}
+
+impl CustomTuning {
+    fn is_custom(&self) -> bool {
+        *self != Self::default()
+    }
+
+    // Rows of the fine-tune screen, in TuneRow order
+    fn value_mut(&mut self, row: usize) -> &mut f32 {
+        match row {
+            0 => &mut self.gap,
+            1 => &mut self.speed,
+            2 => &mut self.gravity,
+            3 => &mut self.flap,
+            _ => &mut self.offset,
+        }
+    }
+
+    // Steps are rounded to whole percents so returning to 100% compares equal to the preset
+    fn adjust(&mut self, row: usize, step: f32) {
+        let value = self.value_mut(row);
+        *value = ((*value + step).clamp(TUNE_MIN, TUNE_MAX) * 100.0).round() / 100.0;
+    }
+
+    // Hand-edited saves can't push a value past the screen's range
+    fn clamped(mut self) -> Self {
+        for row in 0..TUNE_ROWS {
+            self.adjust(row, 0.0);
+        }
+        self
+    }
+
+    fn apply(&self, tuning: DifficultyTuning) -> DifficultyTuning {
+        DifficultyTuning {
+            gap_size: tuning.gap_size * self.gap,
+            scroll_speed: tuning.scroll_speed * self.speed,
+            gravity_mult: tuning.gravity_mult * self.gravity,
+            flap_mult: tuning.flap_mult * self.flap,
+            vertical_offset: tuning.vertical_offset * self.offset,
+            ..tuning
+        }
+    }
+}
+
+impl GameSettings {
+    fn is_challenge(&self) -> bool {
+        self.start_score > 0 || self.score_goal.is_some() || self.gauntlet_start
+    }
+
+    fn zoomed_out(&self) -> bool {
+        self.camera_zoom < CAMERA_ZOOM_RANKED_MIN
+    }
+
+    // The trainer sets its own gap and ignores the fine-tune
+    fn is_custom(&self) -> bool {
+        self.selected_mode != GameMode::Trainer && self.custom_tuning.is_custom()
+    }
+
+    fn challenge_label(&self) -> String {
+        let goal = self.score_goal.map_or("None".to_string(), |goal| goal.to_string());
+        let gauntlet = if self.gauntlet_start { "  /  Gauntlet" } else { "" };
+        format!("Start {}  /  Goal {}{}", self.start_score, goal, gauntlet)
+    }
+}
+
+impl RunModifiers {
+    fn any(&self) -> bool {
+        self.wind || self.mirror || self.flap_cooldown || self.screen_wrap || self.gravity_flip
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        [
+            (self.wind, "Wind"),
+            (self.mirror, "Mirror"),
+            (self.flap_cooldown, "Flap Cooldown"),
+            (self.screen_wrap, "Screen Wrap"),
+            (self.gravity_flip, "Gravity Flip"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect()
+    }
+}
+
+impl FromWorld for KeyBindings {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.get_resource::<AudioSettings>().map_or(FlapKey::Any, |audio| audio.flap_key))
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            gap_size: OBSTACLE_GAP_SIZE,
+            scroll_speed: OBSTACLE_SCROLL_SPEED,
+            vertical_offset: OBSTACLE_VERTICAL_OFFSET,
+            gravity_mult: 1.0,
+            flap_mult: 1.0,
+            ducking: SfxDucking::default(),
+            unlock_all: false,
+            bird_anchor: 0.5,
+            rest_stop_interval: 25,
+            flap: FlapConfig::default(),
+            rotation_smoothing: ROTATION_SMOOTHING,
+            gap_shrink: GapShrink::default(),
+        }
+    }
+}
+
+impl Default for GapShrink {
+    fn default() -> Self {
+        Self { enabled: false, floor: GAP_SHRINK_FLOOR }
+    }
+}
+
+impl Default for FlapConfig {
+    fn default() -> Self {
+        Self {
+            easy: FlapModel::Classic,
+            normal: FlapModel::Classic,
+            hard: FlapModel::Classic,
+            insane: FlapModel::Classic,
+            impulse: 1.0,
+            ticks: 1,
+        }
+    }
+}
+
+impl FlapConfig {
+    fn style(&self, difficulty: Difficulty) -> FlapStyle {
+        let model = match difficulty {
+            Difficulty::Easy => self.easy,
+            Difficulty::Normal => self.normal,
+            Difficulty::Hard => self.hard,
+            Difficulty::Insane => self.insane,
+        };
+        match model {
+            FlapModel::Classic => FlapStyle::Classic,
+            FlapModel::Impulse => FlapStyle::Impulse { strength: self.impulse, ticks: self.ticks },
+        }
+    }
+}
+
+impl Default for SfxDucking {
+    fn default() -> Self {
+        Self {
+            amount: 0.5,
+            duration: 0.3,
+            flap: 0,
+            coin: 1,
+            point: 2,
+            milestone: 3,
+            die: 4,
+        }
+    }
+}
+
+impl SfxDucking {
+    fn priority(&self, kind: SfxKind) -> u8 {
+        match kind {
+            SfxKind::Flap => self.flap,
+            SfxKind::Coin => self.coin,
+            SfxKind::Point => self.point,
+            SfxKind::Milestone => self.milestone,
+            SfxKind::Die => self.die,
+        }
+    }
+}
+
+impl SfxVoice {
+    // Runs the duck down by dt and returns the volume scale to apply, None once the voice is
+    // back at its own level. The last call of a duck returns 1.0 to restore it
+    fn fade(&mut self, dt: f32, amount: f32) -> Option<f32> {
+        if self.ducked <= 0.0 {
+            return None;
+        }
+        self.ducked = (self.ducked - dt).max(0.0);
+        Some(if self.ducked > 0.0 { amount } else { 1.0 })
+    }
+}
+
+impl GameConfig {
+    // Ranked runs need the shipped gameplay values; sound ducking and unlocks don't change a run
+    // Gap shrink only ever makes Endless harder, so it stays ranked like the other extras
+    fn is_stock(&self) -> bool {
+        Self { ducking: SfxDucking::default(), unlock_all: false, gap_shrink: GapShrink::default(), ..*self } == Self::default()
+    }
+
+    fn bird_x(&self, field_width: f32) -> f32 {
+        (self.bird_anchor - 0.5) * field_width
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let positive = [
+            ("gap_size", self.gap_size),
+            ("scroll_speed", self.scroll_speed),
+            ("gravity_mult", self.gravity_mult),
+            ("flap_mult", self.flap_mult),
+        ];
+        for (name, value) in positive {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(format!("{} must be a positive number", name));
+            }
+        }
+        if !self.vertical_offset.is_finite() || self.vertical_offset < 0.0 {
+            return Err("vertical_offset can't be negative".to_string());
+        }
+        if self.gap_size >= OBSTACLE_HEIGHT {
+            return Err(format!("gap_size must be below {}", OBSTACLE_HEIGHT));
+        }
+        if !(0.0..=1.0).contains(&self.ducking.amount) {
+            return Err("ducking.amount must be between 0 and 1".to_string());
+        }
+        if !self.ducking.duration.is_finite() || self.ducking.duration < 0.0 {
+            return Err("ducking.duration can't be negative".to_string());
+        }
+        if !(0.2..=0.8).contains(&self.bird_anchor) {
+            return Err("bird_anchor must be between 0.2 and 0.8".to_string());
+        }
+        // Only one pair can be parked at a time, so stops closer than a full pool would be skipped
+        if self.rest_stop_interval != 0 && self.rest_stop_interval <= OBSTACLE_AMOUNT as u32 {
+            return Err(format!("rest_stop_interval must be 0 or above {}", OBSTACLE_AMOUNT));
+        }
+        if !self.flap.impulse.is_finite() || self.flap.impulse <= 0.0 {
+            return Err("flap.impulse must be a positive number".to_string());
+        }
+        if !(1..=3).contains(&self.flap.ticks) {
+            return Err("flap.ticks must be between 1 and 3".to_string());
+        }
+        if !self.rotation_smoothing.is_finite() || self.rotation_smoothing < 0.0 {
+            return Err("rotation_smoothing can't be negative".to_string());
+        }
+        if !(0.5..=1.0).contains(&self.gap_shrink.floor) {
+            return Err("gap_shrink.floor must be between 0.5 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl FromWorld for UiFonts {
+    fn from_world(world: &mut World) -> Self {
+        let font = if asset_file(UI_FONT).exists() {
+            world.resource::<AssetServer>().load(UI_FONT)
+        } else {
+            eprintln!("Missing {}, using the default font", UI_FONT);
+            Handle::default()
+        };
+        Self {
+            title: font.clone(),
+            body: font,
+            scale: world.get_resource::<AudioSettings>().map_or(1.0, |audio| audio.ui_scale),
+        }
+    }
+}
+
+impl UiFonts {
+    fn scaled(&self, base: f32) -> f32 {
+        base * self.scale
+    }
+
+    // Title-font headers only shrink, leaving room for larger body text
+    fn heading(&self, base: f32) -> f32 {
+        base * self.scale.min(UI_HEADING_MAX_SCALE)
+    }
+
+    // Spacing between rows of text grows and shrinks with it
+    fn margin(&self, base: f32) -> Val {
+        Val::Px(base * self.scale)
+    }
+}
+
+// Full-screen centered column shared by the menu screens. A screen whose rows get
+// taller than the window (large text) wraps into a second column instead of spilling
+fn menu_root_node() -> Node {
+    Node {
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        align_content: AlignContent::Center,
+        flex_direction: FlexDirection::Column,
+        flex_wrap: FlexWrap::Wrap,
+        column_gap: Val::Px(24.0),
+        ..default()
+    }
+}
+
+impl FlapKey {
+    fn next(self) -> Self {
+        match self {
+            FlapKey::Any => FlapKey::Space,
+            FlapKey::Space => FlapKey::ArrowUp,
+            FlapKey::ArrowUp => FlapKey::W,
+            FlapKey::W => FlapKey::Any,
+        }
+    }
+
+    fn key_code(self) -> Option<KeyCode> {
+        match self {
+            FlapKey::Any => None,
+            FlapKey::Space => Some(KeyCode::Space),
+            FlapKey::ArrowUp => Some(KeyCode::ArrowUp),
+            FlapKey::W => Some(KeyCode::KeyW),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            FlapKey::Any => "SPACE/UP/W",
+            FlapKey::Space => "SPACE",
+            FlapKey::ArrowUp => "UP",
+            FlapKey::W => "W",
+        }
+    }
+}
+
+impl KeyBindings {
+    fn new(flap_key: FlapKey) -> Self {
+        let confirm = vec![KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space];
+        let mut advance = confirm.clone();
+        advance.extend(flap_key.key_code().filter(|key| !confirm.contains(key)));
+        Self {
+            keys: [
+                (InputAction::Flap, flap_key.key_code().map_or_else(|| vec![KeyCode::Space, KeyCode::ArrowUp, KeyCode::KeyW], |key| vec![key])),
+                (InputAction::MenuConfirm, confirm),
+                (InputAction::Advance, advance),
+            ]
+            .into_iter()
+            .collect(),
+            flap_key,
+        }
+    }
+
+    // Key hint for screens that take Advance, e.g. "SPACE/UP"
+    fn advance_hint(&self) -> String {
+        match self.flap_key {
+            FlapKey::ArrowUp | FlapKey::W => format!("SPACE/{}", self.flap_key.label()),
+            FlapKey::Any | FlapKey::Space => "SPACE".to_string(),
+        }
+    }
+
+    fn just_pressed(&self, keyboard: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        self.keys[&action].iter().any(|key| keyboard.just_pressed(*key))
+    }
+
+    // Clears the press so no other system can read it as a second action this frame
+    fn consume(&self, keyboard: &mut ButtonInput<KeyCode>, action: InputAction) -> bool {
+        let pressed = self.just_pressed(keyboard, action);
+        if pressed {
+            for key in &self.keys[&action] {
+                keyboard.clear_just_pressed(*key);
+            }
+        }
+        pressed
+    }
+}
+
+// Number-row and numpad digits are interchangeable in menus
+const DIGIT_KEYS: [(KeyCode, KeyCode); 10] = [
+    (KeyCode::Digit0, KeyCode::Numpad0),
+    (KeyCode::Digit1, KeyCode::Numpad1),
+    (KeyCode::Digit2, KeyCode::Numpad2),
+    (KeyCode::Digit3, KeyCode::Numpad3),
+    (KeyCode::Digit4, KeyCode::Numpad4),
+    (KeyCode::Digit5, KeyCode::Numpad5),
+    (KeyCode::Digit6, KeyCode::Numpad6),
+    (KeyCode::Digit7, KeyCode::Numpad7),
+    (KeyCode::Digit8, KeyCode::Numpad8),
+    (KeyCode::Digit9, KeyCode::Numpad9),
+];
+
+fn digit_just_pressed(keyboard: &ButtonInput<KeyCode>, digit: usize) -> bool {
+    let (row, numpad) = DIGIT_KEYS[digit];
+    keyboard.just_pressed(row) || keyboard.just_pressed(numpad)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_camera_zoom() -> f32 {
+    1.0
+}
+
+fn default_rumble_strength() -> f32 {
+    1.0
+}
+
+fn default_idle_timeout() -> f32 {
+    IDLE_TIMEOUT
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+// ----------------- SERIALIZATION & DESERIALIZATION ---------------
+// Custom serialization/deserialization for enums
+impl Serialize for GameMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(match self {
+            GameMode::Endless => "Endless",
+            GameMode::TimeAttack => "TimeAttack",
+            GameMode::Checkpoints => "Checkpoints",
+            GameMode::Tournament => "Tournament",
+            GameMode::Level => "Level",
+            GameMode::Trainer => "Trainer",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for GameMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "Endless" => Ok(GameMode::Endless),
+            "TimeAttack" => Ok(GameMode::TimeAttack),
+            "Checkpoints" => Ok(GameMode::Checkpoints),
+            "Tournament" => Ok(GameMode::Tournament),
+            "Level" => Ok(GameMode::Level),
+            "Trainer" => Ok(GameMode::Trainer),
+            _ => Err(serde::de::Error::custom("Invalid game mode")),
+        }
+    }
+}
+
+impl Serialize for Difficulty {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Insane => "Insane",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Difficulty {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "Easy" => Ok(Difficulty::Easy),
+            "Normal" => Ok(Difficulty::Normal),
+            "Hard" => Ok(Difficulty::Hard),
+            "Insane" => Ok(Difficulty::Insane),
+            _ => Err(serde::de::Error::custom("Invalid difficulty")),
+        }
+    }
+}
+
+impl Serialize for Theme {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(match self {
+            Theme::Classic => "Classic",
+            Theme::HighContrast => "HighContrast",
+            Theme::Minimal => "Minimal",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Theme {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "Classic" => Ok(Theme::Classic),
+            "HighContrast" => Ok(Theme::HighContrast),
+            "Minimal" => Ok(Theme::Minimal),
+            _ => Err(serde::de::Error::custom("Invalid theme")),
+        }
+    }
+}
+
+impl Serialize for Skin {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(match self {
+            Skin::Classic => "Classic",
+            Skin::Red => "Red",
+            Skin::Blue => "Blue",
+            Skin::Green => "Green",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Skin {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "Classic" => Ok(Skin::Classic),
+            "Red" => Ok(Skin::Red),
+            "Blue" => Ok(Skin::Blue),
+            "Green" => Ok(Skin::Green),
+            _ => Err(serde::de::Error::custom("Invalid skin")),
+        }
+    }
+}
+
+impl Serialize for SpawnStrategy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(match self {
+            SpawnStrategy::Recycle => "Recycle",
+            SpawnStrategy::Stream => "Stream",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SpawnStrategy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "Recycle" => Ok(SpawnStrategy::Recycle),
+            "Stream" => Ok(SpawnStrategy::Stream),
+            _ => Err(serde::de::Error::custom("Invalid spawn strategy")),
+        }
+    }
+}
+
+impl Serialize for BirdSize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(match self {
+            BirdSize::Normal => "Normal",
+            BirdSize::Tiny => "Tiny",
+            BirdSize::Giant => "Giant",
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for BirdSize {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "Normal" => Ok(BirdSize::Normal),
+            "Tiny" => Ok(BirdSize::Tiny),
+            "Giant" => Ok(BirdSize::Giant),
+            _ => Err(serde::de::Error::custom("Invalid bird size")),
+        }
+    }
+}
+
+// ---------------------------- MAIN ----------------------------
+// Entry point of the application, adding each system and updating accordingly
+fn main() {
+    if std::env::args().any(|arg| arg == "--diagnostics") {
+        print_diagnostics();
+        return;
+    }
+    if std::env::args().any(|arg| arg == "--validate-saves") {
+        // Read-only, so the saves folder isn't probed or created
+        let mut report = Vec::new();
+        let ok = validate_saves(&SaveWriter::default(), &mut report);
+        println!("{}", report.join("\n"));
+        if !ok {
+            std::process::exit(1);
+        }
+        return;
+    }
+    install_crash_hook(SAVES_DIR);
+    let saves = SaveWriter::open();
+
+    App::new()
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: String::from("Flappy Bird"),
+                        position: WindowPosition::Centered(MonitorSelection::Primary),
+                        resolution: (800, 600).into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .set(ImagePlugin::default_nearest()),
+        )
+        .init_state::<GameState>()
+        .add_sub_state::<PauseState>()
+        .init_resource::<MenuCursor>()
+        .init_resource::<HudStats>()
+        .init_resource::<NavRepeat>()
+        .init_resource::<AudioPrewarm>()
+        .init_resource::<SlotAction>()
+        .init_resource::<SfxBus>()
+        .init_resource::<ScreenFade>()
+        .init_resource::<IdleTimer>()
+        .init_resource::<InputDebounce>()
+        .insert_resource(SaveLock { conflict: acquire_save_lock(&saves) })
+        // After the lock, so a marker still being written by another instance is left alone
+        .insert_resource(recover_interrupted_run(&saves))
+        .init_resource::<RunMarkerWriter>()
+        .init_resource::<LeaderboardFilter>()
+        .insert_resource(load_audio_settings(&saves))
+        // After the audio settings, which hold the flap key preset
+        .init_resource::<KeyBindings>()
+        .insert_resource(load_weekly_board(&saves))
+        .insert_resource(saves)
+        .init_resource::<UiFonts>()
+        .init_resource::<DeathTrail>()
+        .insert_resource(LocalClock::detect())
+        .insert_resource(load_game_config().unwrap_or_else(|e| {
+            eprintln!("Ignoring {}: {}", CONFIG_PATH, e);
+            GameConfig::default()
+        }))
+        .init_resource::<GameSettings>()
+        .init_resource::<SaveSlotChanged>()
+        .init_resource::<NavStack>()
+        .init_resource::<GameOverSeen>()
+        .init_resource::<SaveBanner>()
+        .init_resource::<OptionsChanged>()
+        .init_resource::<RunCodeStatus>()
+        .init_resource::<SessionStats>()
+        .init_resource::<CosmeticsBrowse>()
+        .init_resource::<SimStep>()
+        .init_resource::<RunRecording>()
+        .init_resource::<TrainerTuning>()
+        .add_message::<RunEnded>()
+        .add_systems(Startup, (setup_main_menu, setup_screen_fade, setup_hud_stats_overlay, open_save_lock_screen, start_audio_prewarm))
+        .add_systems(Update, prewarm_audio.run_if(|prewarm: Res<AudioPrewarm>| !prewarm.pending.is_empty()))
+        .add_systems(OnEnter(GameState::MainMenu), (setup_main_menu_ui, clear_tournament, clear_replay, clear_weekly, clear_run_log, show_session_summary, show_interrupted_run))
+        .add_systems(OnExit(GameState::MainMenu), cleanup_menu::<MainMenuMarker>)
+        .add_systems(OnEnter(GameState::Options), setup_options_ui)
+        .add_systems(OnExit(GameState::Options), (cleanup_menu::<OptionsMarker>, clear_run_code_status))
+        .add_systems(OnEnter(GameState::SaveSelect), (flush_save_writes, reset_menu_cursor, reset_slot_action, setup_save_select_ui).chain())
+        .add_systems(OnExit(GameState::SaveSelect), cleanup_menu::<SaveSelectMarker>)
+        .add_systems(OnEnter(GameState::Leaderboard), setup_leaderboard_ui)
+        .add_systems(OnExit(GameState::Leaderboard), cleanup_menu::<LeaderboardMarker>)
+        .add_systems(OnEnter(GameState::ModeSelect), setup_mode_select_ui)
+        .add_systems(OnExit(GameState::ModeSelect), cleanup_menu::<ModeSelectMarker>)
+        .add_systems(OnEnter(GameState::DifficultySelect), setup_difficulty_select_ui)
+        .add_systems(OnExit(GameState::DifficultySelect), cleanup_menu::<DifficultySelectMarker>)
+        .add_systems(OnEnter(GameState::ThemeSelect), setup_theme_select_ui)
+        .add_systems(OnExit(GameState::ThemeSelect), cleanup_menu::<ThemeSelectMarker>)
+        .add_systems(OnEnter(GameState::SkinSelect), setup_skin_select_ui)
+        .add_systems(OnExit(GameState::SkinSelect), cleanup_menu::<SkinSelectMarker>)
+        .add_systems(OnEnter(GameState::AccessibilitySelect), setup_accessibility_ui)
+        .add_systems(OnExit(GameState::AccessibilitySelect), cleanup_menu::<AccessibilityMarker>)
+        .add_systems(OnEnter(GameState::Modifiers), setup_modifiers_ui)
+        .add_systems(OnExit(GameState::Modifiers), cleanup_menu::<ModifiersMarker>)
+        .add_systems(OnEnter(GameState::Challenge), setup_challenge_ui)
+        .add_systems(OnExit(GameState::Challenge), cleanup_menu::<ChallengeMarker>)
+        .add_systems(OnEnter(GameState::DifficultyTune), (reset_menu_cursor, setup_difficulty_tune_ui).chain())
+        .add_systems(OnExit(GameState::DifficultyTune), cleanup_menu::<DifficultyTuneMarker>)
+        .add_systems(OnEnter(GameState::Weekly), setup_weekly_ui)
+        .add_systems(OnEnter(GameState::Cosmetics), (reset_menu_cursor, setup_cosmetics_ui).chain())
+        .add_systems(OnExit(GameState::Cosmetics), cleanup_menu::<CosmeticsMarker>)
+        .add_systems(OnExit(GameState::Weekly), cleanup_menu::<WeeklyMarker>)
+        .add_systems(OnEnter(GameState::Playing), (apply_weekly_challenge, setup_level, ensure_single_bird, reset_on_play_start).chain())
+        .add_systems(OnExit(GameState::Playing), (record_run_end, capture_death_replay, cleanup_game, clear_run_marker).chain())
+        .add_systems(Update, write_run_marker.run_if(in_state(PauseState::Running)))
+        .add_systems(Update, (reset_menu_clear_color, apply_update_mode))
+        .add_systems(Update, (track_navigation, update_breadcrumb.run_if(state_changed::<GameState>.or(resource_changed::<GameSettings>))))
+        .add_systems(Update, capture_score_card.run_if(resource_exists::<ScoreCardCapture>))
+        .add_systems(OnEnter(GameState::GameOver), (
+            setup_game_over_ui,
+            (setup_death_replay, open_name_prompt, auto_score_card, mark_game_over_seen)
+                .chain()
+                .run_if(resource_equals(GameOverSeen(false))),
+        ))
+        .add_systems(OnExit(GameState::GameOver), (cleanup_menu::<GameOverMarker>, clear_run_code_status))
+        .add_systems(OnEnter(GameState::RunDetails), setup_run_details_ui)
+        .add_systems(OnExit(GameState::RunDetails), cleanup_menu::<RunDetailsMarker>)
+        .add_systems(OnEnter(GameState::Victory), setup_victory_ui)
+        .add_systems(OnExit(GameState::Victory), cleanup_menu::<VictoryScreenMarker>)
+        .add_systems(OnEnter(GameState::RoundSummary), (record_tournament_round, setup_round_summary_ui).chain())
+        .add_systems(OnExit(GameState::RoundSummary), cleanup_menu::<RoundSummaryMarker>)
+        .add_systems(OnEnter(GameState::LevelSelect), setup_level_select_ui)
+        .add_systems(OnExit(GameState::LevelSelect), cleanup_menu::<LevelSelectMarker>)
+        .add_systems(OnEnter(GameState::TrainerSelect), setup_trainer_select_ui)
+        .add_systems(OnExit(GameState::TrainerSelect), cleanup_menu::<TrainerSelectMarker>)
+        .add_systems(OnEnter(GameState::LevelComplete), setup_level_complete_ui)
+        .add_systems(OnExit(GameState::LevelComplete), cleanup_menu::<LevelCompleteMarker>)
+        .add_systems(OnEnter(PauseState::Paused), setup_pause_menu_ui)
+        .add_systems(OnExit(PauseState::Paused), cleanup_menu::<PauseMenuMarker>)
+        .add_systems(OnEnter(PauseState::Settings), setup_pause_settings_ui)
+        .add_systems(OnExit(PauseState::Settings), cleanup_menu::<PauseSettingsMarker>)
+        .add_systems(Update, pause_settings_system.run_if(in_state(PauseState::Settings)))
+        .add_systems(OnEnter(PauseState::Resuming), setup_resume_countdown)
+        .add_systems(OnExit(PauseState::Resuming), cleanup_menu::<ResumeCountdownMarker>)
+        .add_systems(OnEnter(GameState::Restarting), restart_run)
+        .add_systems(OnEnter(GameState::Replay), start_replay)
+        .add_systems(OnEnter(GameState::QuitConfirm), setup_quit_confirm_ui)
+        .add_systems(OnExit(GameState::QuitConfirm), cleanup_menu::<QuitConfirmMarker>)
+        .add_systems(OnEnter(GameState::SaveLocked), setup_save_locked_ui)
+        .add_systems(OnExit(GameState::SaveLocked), cleanup_menu::<SaveLockedMarker>)
+        .add_systems(OnEnter(GameState::OverwriteConfirm), setup_overwrite_confirm_ui)
+        .add_systems(OnExit(GameState::OverwriteConfirm), cleanup_menu::<OverwriteConfirmMarker>)
+        .add_systems(OnEnter(GameState::SeedSelect), setup_seed_select_ui)
+        .add_systems(OnExit(GameState::SeedSelect), cleanup_menu::<SeedSelectMarker>)
+        .add_systems(OnEnter(GameState::Scoreboard), setup_scoreboard_ui)
+        .add_systems(OnExit(GameState::Scoreboard), cleanup_menu::<ScoreboardMarker>)
+        .add_systems(Update, (
+            main_menu_system.run_if(in_state(GameState::MainMenu)),
+            options_system.run_if(in_state(GameState::Options)),
+            refresh_options_ui.run_if(in_state(GameState::Options)),
+            (save_select_system, refresh_save_select_ui).chain().run_if(in_state(GameState::SaveSelect)),
+            mode_select_system.run_if(in_state(GameState::ModeSelect)),
+            difficulty_select_system.run_if(in_state(GameState::DifficultySelect)),
+            refresh_difficulty_select_ui.run_if(in_state(GameState::DifficultySelect)),
+            theme_select_system.run_if(in_state(GameState::ThemeSelect)),
+            skin_select_system.run_if(in_state(GameState::SkinSelect)),
+            accessibility_system.run_if(in_state(GameState::AccessibilitySelect)),
+            refresh_accessibility_ui.run_if(in_state(GameState::AccessibilitySelect)),
+            (name_prompt_system, handle_game_over, score_card_system).chain().run_if(in_state(GameState::GameOver)),
+            handle_victory.run_if(in_state(GameState::Victory)),
+            (leaderboard_system, refresh_leaderboard_ui).run_if(in_state(GameState::Leaderboard)),
+            scoreboard_system.run_if(in_state(GameState::Scoreboard)),
+            quit_confirm_system.run_if(in_state(GameState::QuitConfirm)),
+            round_summary_system.run_if(in_state(GameState::RoundSummary)),
+            level_select_system.run_if(in_state(GameState::LevelSelect)),
+            handle_level_complete.run_if(in_state(GameState::LevelComplete)),
+            trainer_select_system.run_if(in_state(GameState::TrainerSelect)),
+        ))
+        .add_systems(Update, (sample_sim_step, apply_modifiers, (
+            (update_bird, tilt_bird, update_bird_trail).chain(),
+            vary_flap_pitch,
+            drift_camera,
+            update_gravity_flip,
+            animate_bird_scale,
+            update_invulnerability,
+            update_obstacles,
+            (update_record_marker, update_gap_telegraph, update_rest_stop_banner),
+            update_ui,
+            update_combo,
+            update_floor_warning,
+            (update_gap_tone, update_blind_tone),
+            update_time_attack,
+            update_checkpoints,
+            update_level,
+            update_gap_progress,
+            check_score_goal,
+            check_difficulty_unlocks,
+            announce_perfect_streak,
+            record_death_trail,
+        )).chain().run_if(in_state(PauseState::Running).and(not(resource_exists::<TimeAttackFinish>))))
+        .add_systems(Update, (
+            start_time_attack_finish.run_if(resource_added::<TimeAttackFinish>),
+            update_time_attack_finish.run_if(resource_exists::<TimeAttackFinish>),
+        ).chain().run_if(in_state(PauseState::Running)))
+        .add_systems(Update, pause_input.run_if(in_state(PauseState::Running).or(in_state(PauseState::Resuming))))
+        .add_systems(Update, (pause_menu_system, apply_music_volume).run_if(in_state(PauseState::Paused)))
+        .add_systems(Update, resume_countdown_system.run_if(in_state(PauseState::Resuming)))
+        .add_systems(Update, update_death_replay.run_if(in_state(GameState::GameOver)))
+        .add_systems(Update, overwrite_confirm_system.run_if(in_state(GameState::OverwriteConfirm)))
+        .add_systems(Update, save_locked_system.run_if(in_state(GameState::SaveLocked)))
+        .add_systems(Update, seed_select_system.run_if(in_state(GameState::SeedSelect)))
+        .add_systems(Update, relaunch_last_seed.run_if(
+            in_state(GameState::Options)
+                .or(in_state(GameState::ModeSelect))
+                .or(in_state(GameState::DifficultySelect))
+                .or(in_state(GameState::ThemeSelect))
+                .or(in_state(GameState::SkinSelect))
+                .or(in_state(GameState::AccessibilitySelect))
+                .or(in_state(GameState::Modifiers))
+                .or(in_state(GameState::Challenge))
+                .or(in_state(GameState::SeedSelect)),
+        ))
+        .add_systems(Update, run_details_system.run_if(in_state(GameState::RunDetails)))
+        .add_systems(Update, (modifiers_system, refresh_modifiers_ui).run_if(in_state(GameState::Modifiers)))
+        .add_systems(Update, (challenge_system, refresh_challenge_ui).run_if(in_state(GameState::Challenge)))
+        .add_systems(Update, difficulty_tune_system.run_if(in_state(GameState::DifficultyTune)))
+        .add_systems(Update, weekly_system.run_if(in_state(GameState::Weekly)))
+        .add_systems(Update, cosmetics_system.run_if(in_state(GameState::Cosmetics)))
+        .add_systems(Update, (accumulate_session_stats, record_weekly_result, bank_run_coins))
+        .add_systems(Update, update_screen_fade)
+        .add_systems(Update, idle_return_system)
+        .add_systems(PreUpdate, apply_input_debounce.after(bevy::input::InputSystems))
+        .add_systems(PreUpdate, apply_nav_repeat.after(apply_input_debounce).run_if(not(in_state(PauseState::Running))))
+        .add_systems(StateTransition, start_input_debounce
+            .run_if(state_changed::<GameState>)
+            .after(bevy::state::state::StateTransitionSystems::EnterSchedules))
+        .add_systems(Update, fallback_ui_fonts)
+        .add_systems(Update, (reload_game_config, write_debug_dump, poll_save_writes, write_weekly_board, update_toast, fade_session_summary, update_sfx_bus, update_hud_stats))
+        .add_systems(Last, ((save_session_on_exit, release_save_lock).chain(), update_crash_context))
+        .add_systems(Update, update_run_code_status.run_if(in_state(GameState::Options).or(in_state(GameState::GameOver))))
+        .run();
+}
+
+fn load_leaderboard(saves: &SaveWriter) -> Vec<LeaderboardEntry> {
+    let mut entries = Vec::new();
+
+    for slot in 1..=3 {
+        if let Some(save) = saves.load(slot) {
+            entries.push(LeaderboardEntry {
+                name: save.run_name.clone().unwrap_or_else(|| save.profile.name.clone()),
+                score: save.score,
+                mode: save.mode,
+                difficulty: save.difficulty,
+                flags: entry_flags(&save),
+                avatar: save.profile.avatar,
+            });
+        }
+    }
+
+    // Sort descending by score
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+    entries
+}
+
+// Flags recorded with the run; saves from before flags existed fall back to their bird size
+fn entry_flags(save: &SaveSlot) -> Vec<String> {
+    if !save.flags.is_empty() {
+        return save.flags.clone();
+    }
+    match save.bird_size {
+        BirdSize::Normal => Vec::new(),
+        BirdSize::Tiny => vec!["tiny".to_string()],
+        BirdSize::Giant => vec!["giant".to_string()],
+    }
+}
+
+fn mastery_key(mode: GameMode, difficulty: Difficulty) -> String {
+    format!("{:?}/{:?}", mode, difficulty)
+}
+
+// Best of the fine-tuned runs on a difficulty, kept apart from its preset best
+fn custom_key(mode: GameMode, difficulty: Difficulty) -> String {
+    format!("{}/custom", mastery_key(mode, difficulty))
+}
+
+fn record_mastery(profile: &mut PlayerProfile, mode: GameMode, difficulty: Difficulty, score: u32) {
+    let key = mastery_key(mode, difficulty);
+    let best = profile.mode_bests.entry(key.clone()).or_insert(0);
+    *best = (*best).max(score);
+    let threshold = MASTERY_THRESHOLDS.iter().find(|(d, _)| *d == difficulty).map(|(_, t)| *t);
+    if threshold.is_some_and(|threshold| *best >= threshold) && !profile.mastered.contains(&key) {
+        profile.mastered.push(key);
+    }
+}
+
+// Difficulties whose unlock rule a ranked score on `difficulty` satisfies
+fn earned_unlocks(difficulty: Difficulty, score: u32) -> impl Iterator<Item = Difficulty> {
+    DIFFICULTY_UNLOCKS
+        .into_iter()
+        .filter(move |(_, from, threshold)| difficulty >= *from && score >= *threshold)
+        .map(|(unlock, _, _)| unlock)
+}
+
+fn record_unlocks(profile: &mut PlayerProfile, difficulty: Difficulty, score: u32) {
+    for unlock in earned_unlocks(difficulty, score) {
+        if !profile.unlocked.contains(&unlock) {
+            profile.unlocked.push(unlock);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug)]
+struct MonthStats {
+    runs: u32,
+    best: u32,
+    total: u64,
+}
+
+impl MonthStats {
+    fn average(&self) -> f32 {
+        if self.runs == 0 { 0.0 } else { self.total as f32 / self.runs as f32 }
+    }
+}
+
+// "2026-10" for the current UTC month
+fn month_key() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, _) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}", year, month)
+}
+
+impl PlayerProfile {
+    // Crashed and custom runs count toward the month but, like high scores, never its best
+    fn push_history(&mut self, score: u32, interrupted: bool, custom: bool) {
+        let month = self.monthly.entry(month_key()).or_default();
+        month.runs += 1;
+        if !interrupted && !custom {
+            month.best = month.best.max(score);
+        }
+        month.total += score as u64;
+
+        self.compact_history();
+        self.history.push(score);
+        self.history_interrupted.push(interrupted);
+        self.compact_history();
+    }
+
+    // Keeps the newest RUN_HISTORY_LEN runs, since the monthly rollup already counts the rest,
+    // and the newest WEEKLY_KEEP_WEEKS weekly bests
+    fn compact_history(&mut self) {
+        // Saves from before the flags have none for their older runs
+        let missing = self.history.len().saturating_sub(self.history_interrupted.len());
+        self.history_interrupted.splice(0..0, std::iter::repeat_n(false, missing));
+        let excess = self.history.len().saturating_sub(RUN_HISTORY_LEN);
+        self.history.drain(..excess);
+        let excess = self.history_interrupted.len() - self.history.len();
+        self.history_interrupted.drain(..excess);
+        keep_recent_weeks(&mut self.weekly_bests);
+    }
+
+    fn record_death(&mut self, score: u32) {
+        let index = (score as usize).min(DEATH_PIPES_LEN - 1);
+        if self.death_pipes.len() <= index {
+            self.death_pipes.resize(index + 1, 0);
+        }
+        self.death_pipes[index] += 1;
+    }
+
+    // Score the profile most often dies at; ties go to the earlier pipe
+    fn usual_death(&self) -> Option<u32> {
+        let most = self.death_pipes.iter().copied().max().filter(|most| *most > 0)?;
+        self.death_pipes.iter().position(|count| *count == most).map(|index| index as u32)
+    }
+}
+
+impl SaveSlot {
+    // Saves from before unlocks existed earn them from their per-difficulty bests,
+    // or from the high score and last difficulty if they predate those too
+    fn earn_past_unlocks(&mut self) {
+        let bests: Vec<(Difficulty, u32)> = DIFFICULTIES
+            .into_iter()
+            .flat_map(|difficulty| {
+                let suffix = format!("/{:?}", difficulty);
+                self.profile.mode_bests.iter().filter(move |(key, _)| key.ends_with(&suffix)).map(move |(_, best)| (difficulty, *best))
+            })
+            .collect();
+        for (difficulty, best) in bests {
+            record_unlocks(&mut self.profile, difficulty, best);
+        }
+        if self.profile.mode_bests.is_empty() {
+            let high_score = self.profile.high_score;
+            record_unlocks(&mut self.profile, self.difficulty, high_score);
+        }
+    }
+}
+
+fn unlock_all(config: &GameConfig) -> bool {
+    config.unlock_all || std::env::args().any(|arg| arg == UNLOCK_ALL_ARG)
+}
+
+// What it takes to play a difficulty the current slot hasn't unlocked yet; None once it's playable
+fn difficulty_lock(settings: &GameSettings, saves: &SaveWriter, config: &GameConfig, difficulty: Difficulty) -> Option<String> {
+    let (_, from, threshold) = DIFFICULTY_UNLOCKS.into_iter().find(|(unlock, _, _)| *unlock == difficulty)?;
+    let unlocked = unlock_all(config)
+        || settings
+            .current_slot
+            .and_then(|slot| saves.load(slot))
+            .is_some_and(|save| save.profile.unlocked.contains(&difficulty));
+    if unlocked {
+        return None;
+    }
+    Some(format!("score {}+ on {:?} to unlock", threshold, from))
+}
+
+// Same toast wherever a locked difficulty is asked for: its row, a pasted code or a saved seed
+fn refuse_locked(commands: &mut Commands, fonts: &UiFonts, toast_query: &Query<Entity, With<Toast>>, difficulty: Difficulty, lock: String) {
+    spawn_toast(commands, fonts, toast_query, format!("{:?} is locked - {}", difficulty, lock), Color::srgb(0.9, 0.35, 0.3));
+}
+
+// Appended to a menu entry once the current slot has mastered it
+fn mastered_badge(settings: &GameSettings, saves: &SaveWriter, mode: GameMode, difficulty: Difficulty) -> &'static str {
+    let mastered = settings
+        .current_slot
+        .and_then(|slot| saves.load(slot))
+        .is_some_and(|save| save.profile.mastered.contains(&mastery_key(mode, difficulty)));
+    if mastered { "   [MASTERED]" } else { "" }
+}
+
+fn run_flags(settings: &GameSettings) -> Vec<String> {
+    let mut flags = Vec::new();
+    match settings.bird_size {
+        BirdSize::Normal => {}
+        BirdSize::Tiny => flags.push("tiny".to_string()),
+        BirdSize::Giant => flags.push("giant".to_string()),
+    }
+    if settings.seed.is_some() {
+        flags.push("seeded".to_string());
+    }
+    if settings.overtime && settings.selected_mode == GameMode::TimeAttack {
+        flags.push("overtime".to_string());
+    }
+    if settings.fill_window {
+        flags.push("filled".to_string());
+    }
+    if settings.is_custom() {
+        flags.push("custom".to_string());
+    }
+    flags
+}
+
+fn setup_leaderboard_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    filter: Res<LeaderboardFilter>,
+    saves: Res<SaveWriter>,
+) {
+    let weekly = current_weekly_challenge();
+    let entries: Vec<_> = if filter.weekly { load_weekly_leaderboard(&saves, &weekly) } else { load_leaderboard(&saves) }
+        .into_iter()
+        .filter(|entry| !filter.vanilla_only || entry.flags.is_empty())
+        .collect();
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    // Background
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        LeaderboardMarker,
+    ));
+
+    // UI Container
+    commands.spawn((
+        menu_root_node(),
+        LeaderboardMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new(if filter.weekly { "WEEKLY LEADERBOARD" } else { "LEADERBOARD" }),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(48.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node { margin: UiRect::all(Val::Px(20.0)), ..default() },
+        ));
+
+        if filter.weekly {
+            parent.spawn((
+                Text::new(weekly.describe()),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(AQUAMARINE.into()),
+                TextShadow::default(),
+                Node { margin: UiRect::bottom(fonts.margin(10.0)), ..default() },
+            ));
+        }
+
+        if let Some(reason) = saves.store().session_only_reason() {
+            parent.spawn((
+                Text::new(format!("Session only - {}, entries are lost on exit", reason)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(18.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.35, 0.3)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+                TextShadow::default(),
+                Node { margin: UiRect::bottom(fonts.margin(10.0)), ..default() },
+            ));
+        }
+
+        // First runs, or a filter that hides everything, still get a readable board
+        if entries.is_empty() {
+            let message = if filter.weekly {
+                "No scores this week yet - play the Weekly Challenge to get on the board!"
+            } else if filter.vanilla_only && !load_leaderboard(&saves).is_empty() {
+                "No unflagged runs yet - turn off Vanilla Only [V] to see every run"
+            } else {
+                "No scores yet - play a game to get on the board!"
+            };
+            parent.spawn((
+                Text::new(message),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextLayout::new_with_justify(Justify::Center),
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+                TextShadow::default(),
+                Node { margin: UiRect::vertical(fonts.margin(40.0)), ..default() },
+            ));
+        }
+
+        for (i, entry) in entries.iter().enumerate() {
+            parent.spawn(Node {
+                align_items: AlignItems::Center,
+                margin: UiRect::all(Val::Px(5.0)),
+                ..default()
+            })
+            .with_children(|row| {
+                spawn_avatar(row, &asset_server, entry.avatar, 28.0);
+                row.spawn((
+                    Text::new(format!(
+                        "{}. {} - {} pts [{:?} {:?}]",
+                        i + 1,
+                        entry.name,
+                        entry.score,
+                        entry.mode,
+                        entry.difficulty,
+                    )),
+                    TextFont {
+                        font: fonts.body.clone(),
+                        font_size: fonts.scaled(28.0),
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+                    TextShadow::default(),
+                ));
+                for flag in &entry.flags {
+                    let color = RUN_FLAGS
+                        .iter()
+                        .find(|(name, _, _)| name == flag)
+                        .map_or(Color::srgb(0.5, 0.5, 0.5), |(_, _, color)| *color);
+                    row.spawn((
+                        Text::new(flag.to_uppercase()),
+                        TextFont {
+                            font: fonts.body.clone(),
+                            font_size: fonts.scaled(14.0),
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextBackgroundColor(color),
+                        Node {
+                            margin: UiRect::left(Val::Px(6.0)),
+                            ..default()
+                        },
+                    ));
+                }
+            });
+        }
+
+        let legend = RUN_FLAGS
+            .iter()
+            .map(|(name, description, _)| format!("{} = {}", name.to_uppercase(), description))
+            .collect::<Vec<_>>()
+            .join("   ");
+        parent.spawn((
+            Text::new(legend),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(16.0),
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            Node { margin: UiRect::top(Val::Px(20.0)), ..default() },
+        ));
+
+        parent.spawn((
+            Text::new(format!(
+                "Vanilla Only [V]: {}   {} [W]   Return [ESC]",
+                if filter.vanilla_only { "On" } else { "Off" },
+                if filter.weekly { "All Runs" } else { "Weekly" },
+            )),
+            TextFont { 
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0), 
+                ..default() 
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node { margin: UiRect::top(Val::Px(20.0)), ..default() },
+        ));
+    });
+}
+
+fn leaderboard_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut filter: ResMut<LeaderboardFilter>,
+    mut flag: ResMut<OptionsChanged>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::MainMenu));
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyV) {
+        filter.vanilla_only = !filter.vanilla_only;
+        flag.changed = true;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyW) {
+        filter.weekly = !filter.weekly;
+        flag.changed = true;
+    }
+}
+
+fn refresh_leaderboard_ui(
+    mut commands: Commands,
+    query: Query<Entity, With<LeaderboardMarker>>,
+    mut flag: ResMut<OptionsChanged>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    filter: Res<LeaderboardFilter>,
+    saves: Res<SaveWriter>,
+) {
+    if flag.changed {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+
+        setup_leaderboard_ui(commands, asset_server, fonts, window_query, filter, saves);
+
+        flag.changed = false;
+    }
+}
+
+// ---------------------------- SCOREBOARD ----------------------------
+// One column per slot for couch play; the best high score (ties included) is highlighted
+fn setup_scoreboard_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    saves: Res<SaveWriter>,
+) {
+    let slots: Vec<Option<SaveSlot>> = (1..=3).map(|slot| saves.load(slot)).collect();
+    let best = slots.iter().flatten().map(|save| save.profile.high_score).max().unwrap_or(0);
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        ScoreboardMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        ScoreboardMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("SCOREBOARD"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::FlexStart,
+            ..default()
+        })
+        .with_children(|row| {
+            for (i, save) in slots.iter().enumerate() {
+                let winner = save.as_ref().is_some_and(|save| best > 0 && save.profile.high_score == best);
+                row.spawn((
+                    Node {
+                        width: Val::Px(220.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(12.0)),
+                        margin: UiRect::horizontal(Val::Px(10.0)),
+                        border: UiRect::all(Val::Px(3.0)),
+                        ..default()
+                    },
+                    BorderColor::all(if winner { Color::srgb(1.0, 0.84, 0.0) } else { Color::NONE }),
+                    BackgroundColor(Color::BLACK.with_alpha(0.3)),
+                ))
+                .with_children(|column| spawn_scoreboard_column(column, &asset_server, &fonts, i + 1, save.as_ref(), winner));
+            }
+        });
+
+        parent.spawn((
+            Text::new("\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+// Newest months first: runs, best and average per month
+fn monthly_lines(profile: &PlayerProfile) -> String {
+    profile
+        .monthly
+        .iter()
+        .rev()
+        .take(SCOREBOARD_MONTHS)
+        .map(|(month, stats)| format!("\n{}: {} runs, best {}, avg {:.1}", month, stats.runs, stats.best, stats.average()))
+        .collect()
+}
+
+// Empty slots still get a column so the layout doesn't shift as profiles are created
+fn spawn_scoreboard_column(
+    column: &mut ChildSpawnerCommands,
+    asset_server: &AssetServer,
+    fonts: &UiFonts,
+    slot: usize,
+    save: Option<&SaveSlot>,
+    winner: bool,
+) {
+    let (name, stats) = match save {
+        Some(save) => {
+            let profile = &save.profile;
+            (profile.name.clone(), format!(
+                "High score: {}\nGames: {}\nAverage: {:.1}\nLongest: {:.1}s\nPrecision: {:.0}%\nPerfect streak: {}\nSessions: {}{}",
+                profile.high_score,
+                profile.total_games,
+                profile.average_score,
+                profile.longest_survival,
+                profile.best_precision,
+                profile.best_perfect_streak,
+                profile.sessions,
+                monthly_lines(profile),
+            ))
+        }
+        None => ("Empty".to_string(), "No games yet".to_string()),
+    };
+
+    column.spawn((
+        Text::new(format!("Slot {}", slot)),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(20.0),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.992, 0.816)),
+        TextShadow::default(),
+    ));
+    match save {
+        // spawn_avatar leaves a right margin for row layouts; it's harmless centered here
+        Some(save) => spawn_avatar(column, asset_server, save.profile.avatar, 64.0),
+        None => {
+            column.spawn(Node {
+                width: Val::Px(64.0),
+                height: Val::Px(64.0),
+                ..default()
+            });
+        }
+    }
+    column.spawn((
+        Text::new(if winner { format!("{} - Leader", name) } else { name }),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(26.0),
+            ..default()
+        },
+        TextColor(if winner { Color::srgb(1.0, 0.84, 0.0) } else { AQUAMARINE.into() }),
+        TextShadow::default(),
+        Node {
+            margin: UiRect::vertical(fonts.margin(8.0)),
+            ..default()
+        },
+    ));
+    column.spawn((
+        Text::new(stats),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(18.0),
+            ..default()
+        },
+        TextColor(if save.is_some() { Color::srgb(1.0, 0.992, 0.816) } else { Color::srgb(0.6, 0.6, 0.6) }),
+        TextShadow::default(),
+    ));
+
+    let Some(profile) = save.map(|save| &save.profile) else { return; };
+    let Some(usual) = profile.usual_death() else { return; };
+    column.spawn((
+        Text::new(format!("Deaths by pipe (most: pipe {})", usual + 1)),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(14.0),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.992, 0.816)),
+        TextShadow::default(),
+        Node {
+            margin: UiRect::top(fonts.margin(8.0)),
+            ..default()
+        },
+    ));
+    spawn_death_chart(column, &profile.death_pipes, usual as usize);
+}
+
+// One bar per pipe index, the most common death highlighted
+fn spawn_death_chart(parent: &mut ChildSpawnerCommands, deaths: &[u32], usual: usize) {
+    let most = deaths.iter().copied().max().unwrap_or(0).max(1);
+    parent.spawn(Node {
+        height: Val::Px(DEATH_CHART_HEIGHT),
+        align_items: AlignItems::FlexEnd,
+        column_gap: Val::Px(1.0),
+        margin: UiRect::top(Val::Px(4.0)),
+        ..default()
+    })
+    .with_children(|chart| {
+        for (index, &count) in deaths.iter().enumerate() {
+            chart.spawn((
+                Node {
+                    width: Val::Px(DEATH_CHART_BAR_WIDTH),
+                    height: Val::Px((count as f32 / most as f32 * DEATH_CHART_HEIGHT).max(1.0)),
+                    ..default()
+                },
+                BackgroundColor(if index == usual { Color::srgb(0.9, 0.35, 0.3) } else { Color::srgb(0.9, 0.9, 0.9) }),
+            ));
+        }
+    });
+}
+
+fn scoreboard_system(keyboard: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::MainMenu);
+    }
+}
+
+// ---------------------------- AVATARS ----------------------------
+// Square avatar icon placed left of a row's text
+fn spawn_avatar(parent: &mut ChildSpawnerCommands, asset_server: &AssetServer, avatar: usize, size: f32) {
+    let (file, fallback) = AVATARS[avatar % AVATARS.len()];
+    let node = Node {
+        width: Val::Px(size),
+        height: Val::Px(size),
+        margin: UiRect::right(Val::Px(12.0)),
+        ..default()
+    };
+    if asset_file(AVATAR_DIR).join(file).exists() {
+        parent.spawn((ImageNode::new(asset_server.load(format!("{}/{}", AVATAR_DIR, file))), node));
+    } else {
+        parent.spawn((node, BackgroundColor(fallback)));
+    }
+}
+
+// Cycles the avatar of a saved slot and queues it for writing
+fn cycle_avatar(saves: &mut SaveWriter, slot: u8, step: isize) -> bool {
+    let Some(mut save) = saves.load(slot) else { return false; };
+    let count = AVATARS.len() as isize;
+    save.profile.avatar = (save.profile.avatar as isize + step).rem_euclid(count) as usize;
+    saves.write(save);
+    true
+}
+
+// Row of bars scaled to the best score; green when a run matched or beat the one before it
+// Interrupted runs (flags aligned with the end of scores) are drawn amber
+fn spawn_sparkline(parent: &mut ChildSpawnerCommands, scores: &[u32], interrupted: &[bool], max_height: f32) {
+    let best = scores.iter().copied().max().unwrap_or(0).max(1);
+    parent.spawn(Node {
+        height: Val::Px(max_height),
+        align_items: AlignItems::FlexEnd,
+        column_gap: Val::Px(2.0),
+        margin: UiRect { left: Val::Px(52.0), top: Val::Px(4.0), ..default() },
+        ..default()
+    })
+    .with_children(|chart| {
+        let mut previous = None;
+        let offset = scores.len().saturating_sub(interrupted.len());
+        for (index, &score) in scores.iter().enumerate() {
+            let cut_short = index >= offset && interrupted[index - offset];
+            let color = match previous {
+                _ if cut_short => Color::srgb(0.95, 0.65, 0.2),
+                None => Color::srgb(0.9, 0.9, 0.9),
+                Some(before) if score >= before => Color::srgb(0.4, 0.85, 0.4),
+                Some(_) => Color::srgb(0.9, 0.35, 0.3),
+            };
+            chart.spawn((
+                Node {
+                    width: Val::Px(SPARKLINE_BAR_WIDTH),
+                    height: Val::Px((score as f32 / best as f32 * max_height).max(2.0)),
+                    ..default()
+                },
+                BackgroundColor(color),
+            ));
+            previous = Some(score);
+        }
+    });
+}
+
+// Percent change of the last TREND_WINDOW runs' average over the TREND_WINDOW before them.
+// Callers check for 2 * TREND_WINDOW runs first; shorter histories compare what they have
+fn improvement_rate(history: &[u32]) -> f32 {
+    let window = TREND_WINDOW.min(history.len() / 2);
+    if window == 0 {
+        return 0.0;
+    }
+    let average = |runs: &[u32]| runs.iter().sum::<u32>() as f32 / runs.len() as f32;
+    let recent = average(&history[history.len() - window..]);
+    let previous = average(&history[history.len() - 2 * window..history.len() - window]);
+    if previous == 0.0 {
+        return if recent > 0.0 { 100.0 } else { 0.0 };
+    }
+    (recent - previous) / previous * 100.0
+}
+
+fn trend_label(history: &[u32]) -> (String, Color) {
+    if history.len() < 2 * TREND_WINDOW {
+        return (format!("Trend: {}/{} runs", history.len(), 2 * TREND_WINDOW), Color::srgb(0.9, 0.9, 0.9));
+    }
+    let rate = improvement_rate(history);
+    if rate >= 0.0 {
+        (format!("Trend: ^ {:.0}%", rate), Color::srgb(0.4, 0.85, 0.4))
+    } else {
+        (format!("Trend: v {:.0}%", -rate), Color::srgb(0.9, 0.35, 0.3))
+    }
+}
+
+fn reset_menu_cursor(mut cursor: ResMut<MenuCursor>) {
+    cursor.index = 0;
+}
+
+// ---------------------------- SESSION ----------------------------
+// Tracks runs for the current session and shows/saves a summary when quitting
+fn record_run_end(
+    mut commands: Commands,
+    score: Res<Score>,
+    mut run_ended: MessageWriter<RunEnded>,
+    mut settings: ResMut<GameSettings>,
+    tournament: Option<Res<TournamentState>>,
+    abandoned: Option<Res<AbandonedRun>>,
+    replay: Option<Res<ReplayPlayback>>,
+    course: Option<Res<CourseRng>>,
+    mut audio: ResMut<AudioSettings>,
+    level: Option<Res<LevelRun>>,
+    mut saves: ResMut<SaveWriter>,
+) {
+    // Watching a replay counts as abandoned too
+    let abandoned = abandoned.is_some() || replay.is_some();
+    commands.remove_resource::<AbandonedRun>();
+    let coins = level.map_or(0, |level| level.coins);
+    // Checked now, before the bird size is cleared below
+    let weekly_match = settings.weekly.as_deref().and_then(parse_week_key).is_some_and(|(year, week)| {
+        let challenge = weekly_challenge(year, week);
+        challenge.matches(&settings) && course.as_ref().is_some_and(|course| course.seed == challenge.seed)
+    });
+    run_ended.write(RunEnded { score: score.current, coins, abandoned, weekly_match });
+
+    // Level courses come from files and tournament rounds offset the seed, so neither is kept
+    let seeded_mode = !matches!(settings.selected_mode, GameMode::Level | GameMode::Tournament);
+    if let Some(course) = course.filter(|_| seeded_mode && !abandoned) {
+        let record = SeedRecord {
+            seed: course.seed,
+            mode: settings.selected_mode,
+            difficulty: settings.selected_difficulty,
+            score: score.current,
+        };
+        audio.seed_history.retain(|old| (old.seed, old.mode, old.difficulty) != (record.seed, record.mode, record.difficulty));
+        audio.seed_history.insert(0, record);
+        audio.seed_history.truncate(SEED_HISTORY_LEN);
+        save_audio_settings(&mut saves, &audio);
+    }
+
+    // Mutators only last for a single run (a whole tournament counts as one);
+    // an abandoned run keeps them for the retry
+    let more_rounds = tournament.is_some_and(|t| t.scores.len() + 1 < TOURNAMENT_ROUNDS);
+    if !more_rounds && !abandoned {
+        settings.bird_size = BirdSize::Normal;
+    }
+}
+
+fn accumulate_session_stats(mut run_ended: MessageReader<RunEnded>, mut stats: ResMut<SessionStats>) {
+    for run in run_ended.read().filter(|run| !run.abandoned) {
+        stats.runs += 1;
+        stats.total_points += run.score;
+        stats.best_run = stats.best_run.max(run.score);
+        stats.total_coins += run.coins;
+    }
+}
+
+// Back on the main menu after new runs: a short recap that fades out on its own
+fn show_session_summary(mut commands: Commands, fonts: Res<UiFonts>, mut stats: ResMut<SessionStats>) {
+    if stats.runs == stats.summarized_runs {
+        return;
+    }
+    stats.summarized_runs = stats.runs;
+    let summary = format!(
+        "Session summary: {} run{}, best score {}, total coins {}",
+        stats.runs,
+        if stats.runs == 1 { "" } else { "s" },
+        stats.best_run,
+        stats.total_coins
+    );
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(10),
+        MainMenuMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new(summary),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.6)),
+            TextShadow::default(),
+            SessionSummaryOverlay { remaining: SESSION_SUMMARY_DURATION },
+        ));
+    });
+}
+
+// Reduce motion drops the fade; the summary just disappears when its time is up
+fn fade_session_summary(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    mut overlay_query: Query<(Entity, &mut SessionSummaryOverlay, &mut TextColor, &mut TextBackgroundColor)>,
+) {
+    for (entity, mut overlay, mut color, mut background) in overlay_query.iter_mut() {
+        overlay.remaining -= time.delta_secs();
+        if overlay.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let alpha = if settings.reduce_motion { 1.0 } else { (overlay.remaining / SESSION_SUMMARY_FADE).min(1.0) };
+        color.0.set_alpha(alpha);
+        background.0.set_alpha(alpha * 0.6);
+    }
+}
+
+fn format_play_time(seconds: f32) -> String {
+    let total = seconds as u32;
+    format!("{}m {:02}s", total / 60, total % 60)
+}
+
+fn setup_quit_confirm_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    stats: Res<SessionStats>,
+    settings: Res<GameSettings>,
+    time: Res<Time<Real>>,
+    saves: Res<SaveWriter>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        QuitConfirmMarker,
+    ));
+
+    let mut lines = vec![
+        format!("Runs played: {}", stats.runs),
+        format!("Total points: {}", stats.total_points),
+        format!("Best run: {}", stats.best_run),
+        format!("Time played: {}", format_play_time(time.elapsed_secs())),
+    ];
+    if let Some(save) = settings.current_slot.and_then(|slot| saves.load(slot)) {
+        lines.push(format!("Best session: {}", save.profile.best_session.max(stats.total_points)));
+    }
+
+    commands.spawn((
+        menu_root_node(),
+        QuitConfirmMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("SESSION SUMMARY"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        for line in lines {
+            parent.spawn((
+                Text::new(line),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(32.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new("\nQuit Game [Q]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn open_save_lock_screen(lock: Res<SaveLock>, mut next_state: ResMut<NextState<GameState>>) {
+    if lock.conflict.is_some() {
+        next_state.set(GameState::SaveLocked);
+    }
+}
+
+fn setup_save_locked_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    lock: Res<SaveLock>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window.width(), window.height())),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        SaveLockedMarker,
+    ));
+
+    let holder = lock.conflict.as_deref().unwrap_or_default().trim().replace('\n', ", ");
+    commands.spawn((
+        menu_root_node(),
+        SaveLockedMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("SAVES IN USE"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(format!(
+                "Another instance may be running ({}).\nTwo games writing the same slots can corrupt them.",
+                holder,
+            )),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+        ));
+
+        parent.spawn((
+            Text::new("\nIt crashed or is closed - clear the lock and continue [C]\nContinue without saving [R]\nQuit [Q]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn save_locked_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut app_exit: MessageWriter<AppExit>,
+    mut lock: ResMut<SaveLock>,
+    saves: Res<SaveWriter>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        if let Err(e) = take_save_lock() {
+            eprintln!("Failed to take over {}: {}", SAVE_LOCK_FILE, e);
+        }
+    } else if keyboard.just_pressed(KeyCode::KeyR) {
+        // Same as a read-only folder: slots are read from disk but changes stay in memory
+        saves.store().keep_in_memory("another instance is using the saves folder".to_string());
+    } else {
+        if keyboard.just_pressed(KeyCode::KeyQ) {
+            app_exit.write(AppExit::Success);
+        }
+        return;
+    }
+    lock.conflict = None;
+    next_state.set(GameState::MainMenu);
+}
+
+fn quit_confirm_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut app_exit: MessageWriter<AppExit>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::MainMenu);
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyQ) {
+        app_exit.write(AppExit::Success);
+    }
+}
+
+// Runs on any exit (menu quit or window close): log the summary and fold it into the profile
+fn save_session_on_exit(
+    mut app_exit: MessageReader<AppExit>,
+    stats: Res<SessionStats>,
+    settings: Res<GameSettings>,
+    time: Res<Time<Real>>,
+    mut saves: ResMut<SaveWriter>,
+    mut board: ResMut<WeeklyBoard>,
+) {
+    if app_exit.read().last().is_none() {
+        return;
+    }
+    saves.flush();
+    board.flush(&saves);
+
+    println!(
+        "Session summary: {} runs, {} points, best run {}, played {}",
+        stats.runs,
+        stats.total_points,
+        stats.best_run,
+        format_play_time(time.elapsed_secs())
+    );
+
+    if stats.runs == 0 {
+        return;
+    }
+    let Some(slot_num) = settings.current_slot else { return; };
+    let store = saves.store();
+    let Some(mut save) = store.load_slot(slot_num) else { return; };
+
+    save.profile.sessions += 1;
+    save.profile.best_session = save.profile.best_session.max(stats.total_points);
+    if let Err(e) = store.write_slot(&save) {
+        eprintln!("Failed to save session stats: {}", e);
+    }
+}
+
+fn setup_main_menu(mut commands: Commands, audio: Res<AudioSettings>, mut settings: ResMut<GameSettings>, clock: Res<LocalClock>) {
+    // Basic 2D camera for UI
+    commands.spawn(Camera2d);
+    settings.selected_theme = audio.default_theme(&clock);
+}
+
+// Runs set ClearColor to their theme; every other screen gets the menu color back in the
+// frame it opens, before anything is drawn
+fn reset_menu_clear_color(state: Res<State<GameState>>, mut clear_color: ResMut<ClearColor>) {
+    if *state.get() != GameState::Playing && clear_color.0 != MENU_CLEAR_COLOR {
+        clear_color.0 = MENU_CLEAR_COLOR;
+    }
+}
+
+// Key presses wake the app at once, so menu input isn't delayed; fades and every other screen run continuously
+fn apply_update_mode(state: Res<State<GameState>>, audio: Res<AudioSettings>, fade: Res<ScreenFade>, mut winit: ResMut<WinitSettings>) {
+    let idle = audio.low_power_menus && LOW_POWER_STATES.contains(state.get()) && fade.target.is_none() && fade.alpha == 0.0;
+    let mode = if idle {
+        UpdateMode::reactive_low_power(Duration::from_secs_f64(1.0 / MENU_LOW_POWER_FPS))
+    } else {
+        UpdateMode::Continuous
+    };
+    if winit.focused_mode != mode {
+        winit.focused_mode = mode;
+    }
+}
+
+// The overlay lives for the whole app; update_screen_fade only changes its alpha
+fn setup_screen_fade(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.0)),
+        GlobalZIndex(i32::MAX),
+        FadeOverlay,
+    ));
+}
+
+// Lives for the whole app like the fade overlay; hidden until F3
+fn setup_hud_stats_overlay(mut commands: Commands, fonts: Res<UiFonts>) {
+    commands.spawn((
+        Text::new("HUD updates/s: -   Worst frame: -   Run entities: -"),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.992, 0.816)),
+        TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(5.0),
+            right: Val::Px(5.0),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX - 1),
+        Visibility::Hidden,
+        HudStatsOverlay,
+    ));
+}
+
+// Every theme's variant of every sound effect, skipped under FLAPPY_NO_AUDIO
+fn start_audio_prewarm(asset_server: Res<AssetServer>, mut prewarm: ResMut<AudioPrewarm>) {
+    if std::env::var_os(NO_AUDIO_ENV).is_some() {
+        return;
+    }
+    for theme in [Theme::Classic, Theme::HighContrast, Theme::Minimal] {
+        for file in PREWARM_SOUNDS {
+            let handle = theme.sound(&asset_server, file);
+            if !prewarm.pending.contains(&handle) {
+                prewarm.pending.push(handle);
+            }
+        }
+    }
+}
+
+// Plays each clip at zero volume once it has loaded and keeps it; files that fail to load are dropped
+fn prewarm_audio(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    asset_server: Res<AssetServer>,
+    mut prewarm: ResMut<AudioPrewarm>,
+) {
+    prewarm.elapsed += time.delta_secs();
+    let AudioPrewarm { pending, warm, .. } = &mut *prewarm;
+    pending.retain(|handle| {
+        if asset_server.is_loaded(handle) {
+            commands.spawn((
+                AudioPlayer::new(handle.clone()),
+                PlaybackSettings {
+                    volume: Volume::SILENT,
+                    ..PlaybackSettings::DESPAWN
+                },
+            ));
+            warm.push(handle.clone());
+            return false;
+        }
+        !asset_server.load_state(handle).is_failed()
+    });
+}
+
+// Reduce motion switches instantly instead of fading
+fn update_screen_fade(
+    time: Res<Time>,
+    mut fade: ResMut<ScreenFade>,
+    mut next_state: ResMut<NextState<GameState>>,
+    settings: Res<GameSettings>,
+    mut overlay_query: Query<&mut BackgroundColor, With<FadeOverlay>>,
+) {
+    let step = time.delta_secs() / FADE_DURATION;
+    if let Some(target) = fade.target {
+        fade.alpha = (fade.alpha + step).min(1.0);
+        if settings.reduce_motion || fade.alpha >= 1.0 {
+            next_state.set(target);
+            fade.target = None;
+        }
+    } else {
+        fade.alpha = (fade.alpha - step).max(0.0);
+    }
+    if settings.reduce_motion {
+        fade.alpha = 0.0;
+    }
+
+    for mut color in overlay_query.iter_mut() {
+        color.0 = Color::BLACK.with_alpha(fade.alpha);
+    }
+}
+
+// Runs right after the change, before the new screen's systems see this frame's presses.
+// Gameplay skips the grace period so an early flap isn't lost, but still waits for held keys
+fn start_input_debounce(
+    state: Res<State<GameState>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut debounce: ResMut<InputDebounce>,
+) {
+    debounce.remaining = if *state.get() == GameState::Playing { 0.0 } else { INPUT_DEBOUNCE };
+    debounce.held = keyboard.get_pressed().copied().collect();
+    for key in debounce.held.iter() {
+        keyboard.clear_just_pressed(*key);
+    }
+}
+
+// Filters the keyboard once per frame before anything reads it, so no screen needs its own check
+fn apply_input_debounce(
+    time: Res<Time<Real>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut debounce: ResMut<InputDebounce>,
+) {
+    debounce.held.retain(|key| keyboard.pressed(*key));
+    let blocked: Vec<KeyCode> = keyboard
+        .get_just_pressed()
+        .copied()
+        .filter(|key| debounce.remaining > 0.0 || debounce.held.contains(key))
+        .collect();
+    for key in blocked {
+        keyboard.clear_just_pressed(key);
+    }
+    debounce.remaining = (debounce.remaining - time.delta_secs()).max(0.0);
+}
+
+// Turns a held arrow key into repeated presses, so every screen's just_pressed checks repeat
+// without changes. Keys held through a screen change wait for release like the debounce
+fn apply_nav_repeat(
+    time: Res<Time<Real>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    debounce: Res<InputDebounce>,
+    mut repeat: ResMut<NavRepeat>,
+) {
+    if let Some(key) = NAV_KEYS.into_iter().find(|key| keyboard.just_pressed(*key)) {
+        *repeat = NavRepeat { key: Some(key), held: 0.0, next: NAV_REPEAT_DELAY };
+        return;
+    }
+    let Some(key) = repeat.key else { return; };
+    if !keyboard.pressed(key) || debounce.held.contains(&key) {
+        repeat.key = None;
+        return;
+    }
+
+    repeat.held += time.delta_secs();
+    if repeat.held >= repeat.next {
+        repeat.next += NAV_REPEAT_INTERVAL;
+        // Release and press again: just_pressed is set, and the release is hidden
+        keyboard.release(key);
+        keyboard.press(key);
+        keyboard.clear_just_released(key);
+    }
+}
+
+// Never fires in Playing; menus other than Game Over only when idle_in_menus is on
+fn idle_return_system(
+    time: Res<Time>,
+    state: Res<State<GameState>>,
+    audio: Res<AudioSettings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+    mut idle: ResMut<IdleTimer>,
+    mut fade: ResMut<ScreenFade>,
+) {
+    let eligible = match state.get() {
+        GameState::GameOver | GameState::RunDetails => true,
+        GameState::SaveSelect
+        | GameState::ModeSelect
+        | GameState::DifficultySelect
+        | GameState::ThemeSelect
+        | GameState::Leaderboard
+        | GameState::Scoreboard => audio.idle_in_menus,
+        _ => false,
+    };
+    let active = keyboard.get_pressed().next().is_some()
+        || mouse.get_pressed().next().is_some()
+        || touches.iter().next().is_some()
+        || gamepads.iter().any(|gamepad| gamepad.get_pressed().next().is_some());
+    if !eligible || active || state.is_changed() || audio.idle_timeout <= 0.0 {
+        idle.elapsed = 0.0;
+        return;
+    }
+
+    idle.elapsed += time.delta_secs();
+    if idle.elapsed >= audio.idle_timeout {
+        idle.elapsed = 0.0;
+        fade.request_transition(GameState::MainMenu);
+    }
+}
+
+// A font that exists but fails to parse is swapped for the default one, including on text already spawned
+fn fallback_ui_fonts(
+    asset_server: Res<AssetServer>,
+    mut fonts: ResMut<UiFonts>,
+    mut text_query: Query<&mut TextFont>,
+) {
+    if !asset_server.load_state(&fonts.body).is_failed() {
+        return;
+    }
+    eprintln!("Failed to load {}, using the default font", UI_FONT);
+    let failed = fonts.body.clone();
+    *fonts = UiFonts { title: Handle::default(), body: Handle::default(), scale: fonts.scale };
+    for mut text_font in text_query.iter_mut().filter(|text_font| text_font.font == failed) {
+        text_font.font = Handle::default();
+    }
+}
+
+// Slot JSON as the game reads it, with older saves brought up to date
+fn parse_save_slot(contents: &str) -> Result<SaveSlot, serde_json::Error> {
+    let mut save: SaveSlot = serde_json::from_str(contents)?;
+    save.earn_past_unlocks();
+    // A hand-edited or runaway file is cut back on load and written compacted next save
+    save.profile.compact_history();
+    Ok(save)
+}
+
+// ---------------------------- SAVE BACKEND ----------------------------
+// Slot files normally live on disk. If the saves directory can't be written at startup they
+// are kept in memory for the session instead, and every write still reports the failure
+#[derive(Default)]
+enum SaveBackend {
+    #[default]
+    Disk,
+    Memory {
+        reason: String,
+        // None marks a slot deleted this session
+        files: std::collections::HashMap<String, Option<String>>,
+    },
+}
+
+// Every save file goes through here. SaveWriter shares it with its IO tasks. The locks only
+// guard the backend's state and are never held across a filesystem call, so a slow disk
+// under one task can't stall a frame reading another slot
+#[derive(Default)]
+struct SaveStore {
+    backend: Mutex<SaveBackend>,
+    // Slot files already backed up this session
+    backups: Mutex<Vec<String>>,
+}
+
+impl SaveStore {
+    // Creates the saves directory and proves it's writable, before anything reads a slot
+    fn open() -> Self {
+        let store = SaveStore::default();
+        let probe = Path::new(SAVES_DIR).join(SAVE_PROBE_FILE);
+        let result = fs::create_dir_all(SAVES_DIR)
+            .and_then(|_| fs::write(&probe, ""))
+            .and_then(|_| fs::remove_file(&probe));
+        if let Err(e) = result {
+            eprintln!("{} is not writable ({}); progress is kept for this session only", SAVES_DIR, e);
+            store.keep_in_memory(format!("{} is not writable", SAVES_DIR));
+        }
+        store
+    }
+
+    // A panicked write can't leave the backend half-changed, so a poisoned lock is still usable
+    fn backend(&self) -> std::sync::MutexGuard<'_, SaveBackend> {
+        self.backend.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Files already on disk can still be read, but changes stay in memory
+    fn keep_in_memory(&self, reason: String) {
+        *self.backend() = SaveBackend::Memory { reason, files: std::collections::HashMap::new() };
+    }
+
+    // Why progress is session-only, or None while saving to disk works
+    fn session_only_reason(&self) -> Option<String> {
+        match &*self.backend() {
+            SaveBackend::Disk => None,
+            SaveBackend::Memory { reason, .. } => Some(reason.clone()),
+        }
+    }
+
+    fn read_file(&self, path: &str) -> Option<String> {
+        if let SaveBackend::Memory { files, .. } = &*self.backend()
+            && let Some(file) = files.get(path)
+        {
+            return file.clone();
+        }
+        fs::read_to_string(path).ok()
+    }
+
+    // Keeps the files in memory when saves are session-only and returns why they didn't reach
+    // the disk; None means the caller writes them to disk itself
+    fn keep_files(&self, files: Vec<(String, Option<String>)>) -> Option<String> {
+        match &mut *self.backend() {
+            SaveBackend::Disk => None,
+            SaveBackend::Memory { reason, files: kept } => {
+                kept.extend(files);
+                Some(format!("{} (kept for this session only)", reason))
+            }
+        }
+    }
+
+    fn write_file(&self, path: &str, contents: String) -> Result<(), String> {
+        if let Some(reason) = self.keep_files(vec![(path.to_string(), Some(contents.clone()))]) {
+            return Err(reason);
+        }
+        if !owns_save_lock() {
+            return Err("another instance is using the saves folder".to_string());
+        }
+        write_atomic(path, &contents).map_err(|e| e.to_string())
+    }
+
+    fn load_slot(&self, slot: u8) -> Option<SaveSlot> {
+        parse_save_slot(&self.read_file(&slot_path(slot))?).ok()
+    }
+
+    fn load_slot_backup(&self, slot: u8) -> Option<SaveSlot> {
+        parse_save_slot(&self.read_file(&slot_backup_path(&slot_path(slot)))?).ok()
+    }
+
+    fn write_slot(&self, save: &SaveSlot) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(save).map_err(|e| e.to_string())?;
+        self.write_slot_file(save.slot_number, json)
+    }
+
+    // True the first time a slot is overwritten (or restored) this session
+    fn first_write(&self, path: &str) -> bool {
+        let mut backups = self.backups.lock().unwrap_or_else(|e| e.into_inner());
+        let first = !backups.iter().any(|done| done == path);
+        if first {
+            backups.push(path.to_string());
+        }
+        first
+    }
+
+    fn write_slot_file(&self, slot: u8, contents: String) -> Result<(), String> {
+        let path = slot_path(slot);
+        // The first overwrite of a slot each session keeps the old file as its one backup,
+        // so a session on the wrong slot can be rolled back from the Saves screen
+        if self.first_write(&path) {
+            if self.session_only_reason().is_some() {
+                if let Some(old) = self.read_file(&path) {
+                    self.keep_files(vec![(slot_backup_path(&path), Some(old))]);
+                }
+            } else if owns_save_lock() && Path::new(&path).exists() {
+                fs::copy(&path, slot_backup_path(&path)).map_err(|e| format!("backup failed: {}", e))?;
+            }
+        }
+        self.write_file(&path, contents)
+    }
+
+    // Swaps a slot with its backup, so restoring twice undoes the restore
+    fn restore_slot_backup(&self, slot: u8) -> Result<(), String> {
+        let path = slot_path(slot);
+        let backup_path = slot_backup_path(&path);
+        let previous = self.read_file(&backup_path).ok_or_else(|| format!("Slot {} has no previous save", slot))?;
+        let current = self.read_file(&path);
+        // Counts as this session's backup, so the next write keeps the swapped-out save
+        self.first_write(&path);
+        if let Some(reason) = self.keep_files(vec![(path.clone(), Some(previous.clone())), (backup_path.clone(), current.clone())]) {
+            return Err(reason);
+        }
+        if !owns_save_lock() {
+            return Err("another instance is using the saves folder".to_string());
+        }
+        write_atomic(&path, &previous).map_err(|e| e.to_string())?;
+        match current {
+            Some(current) => write_atomic(&backup_path, &current).map_err(|e| e.to_string()),
+            None => fs::remove_file(&backup_path).map_err(|e| e.to_string()),
+        }
+    }
+
+    // A deleted slot becomes its backup, so a delete can be restored too
+    fn delete_slot_file(&self, slot: u8) -> bool {
+        let path = slot_path(slot);
+        if self.session_only_reason().is_some() {
+            let old = self.read_file(&path);
+            let existed = old.is_some();
+            let mut files = vec![(path.clone(), None)];
+            if existed {
+                files.push((slot_backup_path(&path), old));
+            }
+            self.keep_files(files);
+            return existed;
+        }
+        Path::new(&path).exists() && fs::rename(&path, slot_backup_path(&path)).is_ok()
+    }
+}
+
+fn slot_path(slot: u8) -> String {
+    format!("{}/slot_{}.json", SAVES_DIR, slot)
+}
+
+// slot_N.json -> slot_N.prev.json
+fn slot_backup_path(path: &str) -> String {
+    path.replace(".json", ".prev.json")
+}
+
+// Writes beside the file and renames over it, so a crash or a second instance never sees
+// half a file
+fn write_atomic(path: &str, contents: &str) -> std::io::Result<()> {
+    let temp = format!("{}.{}.tmp", path, std::process::id());
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+// First line of the lock file: the owning process
+fn save_lock_owner() -> String {
+    format!("pid {}", std::process::id())
+}
+
+fn owns_save_lock() -> bool {
+    fs::read_to_string(SAVE_LOCK_FILE).is_ok_and(|lock| lock.lines().next() == Some(&save_lock_owner()))
+}
+
+fn save_lock_contents() -> String {
+    format!("{}\nstarted {}\n", save_lock_owner(), utc_timestamp())
+}
+
+// Taking over from the lock screen replaces whatever lock is there
+fn take_save_lock() -> std::io::Result<()> {
+    write_atomic(SAVE_LOCK_FILE, &save_lock_contents())
+}
+
+// Only succeeds if no lock exists, so two instances starting together can't both get it
+fn create_save_lock() -> std::io::Result<()> {
+    use std::io::Write;
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(SAVE_LOCK_FILE)?
+        .write_all(save_lock_contents().as_bytes())
+}
+
+// Takes the lock, or returns the other instance's lock file. A lock whose process is
+// gone (only checkable where /proc exists) was left by a crash and is taken over
+fn acquire_save_lock(saves: &SaveWriter) -> Option<String> {
+    if saves.store().session_only_reason().is_some() {
+        return None;
+    }
+    match create_save_lock() {
+        Ok(()) => return None,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => {
+            eprintln!("Failed to create {}: {}", SAVE_LOCK_FILE, e);
+            return None;
+        }
+    }
+    let existing = fs::read_to_string(SAVE_LOCK_FILE).unwrap_or_default();
+    let holder = existing.lines().next().and_then(|line| line.strip_prefix("pid ")).unwrap_or_default();
+    let stale = holder == std::process::id().to_string()
+        || (Path::new("/proc").is_dir() && !Path::new("/proc").join(holder).is_dir());
+    if !stale {
+        return Some(existing);
+    }
+    eprintln!("Clearing stale save lock ({})", existing.lines().next().unwrap_or_default());
+    let _ = fs::remove_file(SAVE_LOCK_FILE);
+    // Another instance clearing the same lock may have got there first
+    match create_save_lock() {
+        Ok(()) => None,
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Some(fs::read_to_string(SAVE_LOCK_FILE).unwrap_or_default()),
+        Err(e) => {
+            eprintln!("Failed to create {}: {}", SAVE_LOCK_FILE, e);
+            None
+        }
+    }
+}
+
+// Clean exit gives the lock back; a crash leaves it for the next start to clear
+fn release_save_lock(mut app_exit: MessageReader<AppExit>) {
+    if app_exit.read().last().is_some() && owns_save_lock() {
+        let _ = fs::remove_file(SAVE_LOCK_FILE);
+    }
+}
+
+fn cleanup_menu<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn cleanup_game(
+    mut commands: Commands,
+    run_query: Query<Entity, With<RunScoped>>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    // Tear down everything that belongs to a run before returning to menus
+    for entity in &run_query {
+        commands.entity(entity).despawn();
+    }
+
+    commands.remove_resource::<TimeAttackState>();
+    commands.remove_resource::<CheckpointsState>();
+    commands.remove_resource::<LevelRun>();
+    commands.remove_resource::<GapProgress>();
+    commands.remove_resource::<ModifierState>();
+    commands.remove_resource::<TimeAttackFinish>();
+    for (mut camera, mut projection) in camera_query.iter_mut() {
+        camera.scale.x = 1.0;
+        camera.translation.x = 0.0;
+        camera.translation.y = 0.0;
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scaling_mode = ScalingMode::WindowSize;
+            ortho.scale = 1.0;
+        }
+    }
+}
+
+// Main Menu UI
+fn setup_main_menu_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    audio: Res<AudioSettings>,
+    banner: Res<SaveBanner>,
+    bindings: Res<KeyBindings>,
+    saves: Res<SaveWriter>,
+    clock: Res<LocalClock>,
+) {
+    // Neutral background for menus so theme colors from gameplay don't stick
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+                Sprite {
+                    image: asset_server.load("Background2.png"),
+                    custom_size: Some(Vec2::new(window_width, window_height)),
+                    ..default()
+                },
+                Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+                Background,
+                MainMenuMarker,
+            ));
+
+    // Loop menu music
+    commands.spawn((
+    AudioPlayer::new(asset_server.load("35-Lost-Woods.ogg")),
+    PlaybackSettings {
+        volume: audio.music(),
+        ..PlaybackSettings::LOOP
+    },
+    MusicTrack,
+    MainMenuMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        MainMenuMarker,
+    ))
+    .with_children(|parent| {
+
+        parent.spawn((
+            Text::new("FLAPPY BIRD"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(80.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            Node {
+                margin: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+        ));
+
+        if audio.greeting {
+            let name = settings
+                .current_slot
+                .or(audio.last_slot)
+                .and_then(|slot| saves.load(slot))
+                .map(|save| save.profile.name);
+            parent.spawn((
+                Text::new(greeting_text(clock.hour(), name.as_deref())),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(24.0),
+                    ..default()
+                },
+                TextShadow::default(),
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(16.0)),
+                    ..default()
+                },
+            ));
+        }
+        
+        parent.spawn((
+            // A W flap key stays on Weekly here, see main_menu_system
+            Text::new(if bindings.flap_key == FlapKey::ArrowUp { "Start Game [Space/Up]" } else { "Start Game [Space]" }),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(AQUAMARINE.into()),
+        ));
+
+        parent.spawn((
+            Text::new("Options [O]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(AQUAMARINE.into()),
+        ));
+
+        parent.spawn((
+            Text::new("Weekly Challenge [W]   Quick Start [Y]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(AQUAMARINE.into()),
+        ));
+
+        parent.spawn((
+            Text::new(current_weekly_challenge().describe()),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(18.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(Color::srgb(1.0, 0.84, 0.0)),
+        ));
+
+        parent.spawn((
+            Text::new("Leaderboard [L]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(AQUAMARINE.into()),
+        ));
+
+        parent.spawn((
+            Text::new("Scoreboard [S]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(AQUAMARINE.into()),
+        ));
+
+        parent.spawn((
+            Text::new("Quit [Q]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextShadow::default(),
+            TextColor(AQUAMARINE.into()),
+        ));
+
+    });
+
+    // Display current save slot in top right
+    let slot_text = if let Some(slot_num) = settings.current_slot {
+        if let Some(save_data) = saves.load(slot_num) {
+            format!("Slot {}: {}", slot_num, save_data.profile.name)
+        } else {
+            format!("Slot {}: New", slot_num)
+        }
+    } else {
+        "No slot selected".to_string()
+    };
+
+    commands.spawn((
+        Text::new(slot_text),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(18.0),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.992, 0.816)),
+        TextShadow::default(),
+        TextBackgroundColor(Color::BLACK.with_alpha(0.3)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            ..default()
+        },
+        MainMenuMarker,
+    ));
+
+    if let Some(reason) = saves.store().session_only_reason().filter(|_| !banner.dismissed) {
+        commands.spawn((
+            Text::new(format!("Progress can't be saved: {}   Dismiss [X]", reason)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(18.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                right: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.6, 0.15, 0.1).with_alpha(0.85)),
+            SaveBannerMarker,
+            MainMenuMarker,
+        ));
+    }
+}
+
+fn main_menu_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut fade: ResMut<ScreenFade>,
+    mut settings: ResMut<GameSettings>,
+    mut commands: Commands,  // Add Commands parameter
+    saves: Res<SaveWriter>,
+    mut banner: ResMut<SaveBanner>,
+    banner_query: Query<Entity, With<SaveBannerMarker>>,
+    prewarm: Res<AudioPrewarm>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyX) && !banner.dismissed {
+        banner.dismissed = true;
+        for entity in &banner_query {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    // Runs wait for the silent pre-warm, so the first flap doesn't stall on decoding
+    // W opens the Weekly screen here, so a W flap key doesn't also start a run
+    let start = if bindings.flap_key == FlapKey::W { InputAction::MenuConfirm } else { InputAction::Advance };
+    if prewarm.is_done() && bindings.consume(&mut keyboard, start) {
+        start_run(&settings, &saves, &mut fade);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyO) {
+        fade.request_transition(GameState::Options);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyL) {
+        fade.request_transition(GameState::Leaderboard);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyS) {
+        fade.request_transition(GameState::Scoreboard);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyW) {
+        fade.request_transition(GameState::Weekly);
+    }
+
+    // One key straight into this week's challenge
+    if prewarm.is_done() && keyboard.just_pressed(KeyCode::KeyY) {
+        start_weekly(&mut commands, &mut settings, &mut fade, &fonts, &toast_query);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyQ) {
+        fade.request_transition(GameState::QuitConfirm);
+    }
+}
+
+// A run needs a slot to save into, and asks first if its settings differ from the slot's
+fn start_run(settings: &GameSettings, saves: &SaveWriter, fade: &mut ScreenFade) {
+    if settings.current_slot.is_none() {
+        fade.request_transition(GameState::SaveSelect);
+    } else if settings
+        .current_slot
+        .and_then(|slot| saves.load(slot))
+        .is_some_and(|save| !slot_differences(settings, &save).is_empty())
+    {
+        fade.request_transition(GameState::OverwriteConfirm);
+    } else {
+        fade.request_transition(GameState::Playing);
+    }
+}
+
+fn options_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut status: ResMut<RunCodeStatus>,
+    mut flag: ResMut<OptionsChanged>,
+    mut audio: ResMut<AudioSettings>,
+    mut fonts: ResMut<UiFonts>,
+    mut bindings: ResMut<KeyBindings>,
+    nav: Res<NavStack>,
+    mut saves: ResMut<SaveWriter>,
+    config: Res<GameConfig>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::MainMenu));
+        return;
+    }
+
+    // Text size: the Options screen is rebuilt at once as a preview
+    let text_step = if keyboard.just_pressed(KeyCode::Equal) {
+        UI_SCALE_STEP
+    } else if keyboard.just_pressed(KeyCode::Minus) {
+        -UI_SCALE_STEP
+    } else {
+        0.0
+    };
+    if text_step != 0.0 {
+        audio.ui_scale = (audio.ui_scale + text_step).clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        fonts.scale = audio.ui_scale;
+        save_audio_settings(&mut saves, &audio);
+        status.message = Some(format!("Text size: {:.0}%", audio.ui_scale * 100.0));
+        flag.changed = true;
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyB) {
+        settings.record_marker = !settings.record_marker;
+        status.message = Some(format!(
+            "Record marker (Endless): {}",
+            if settings.record_marker { "On" } else { "Off" },
+        ));
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        settings.fill_window = !settings.fill_window;
+        status.message = Some(format!(
+            "Playfield: {}",
+            if settings.fill_window { "Fill window (flagged on the leaderboard)" } else { "Letterboxed" },
+        ));
+        return;
+    }
+
+    let zoom_step = if keyboard.just_pressed(KeyCode::BracketRight) {
+        CAMERA_ZOOM_STEP
+    } else if keyboard.just_pressed(KeyCode::BracketLeft) {
+        -CAMERA_ZOOM_STEP
+    } else {
+        0.0
+    };
+    if zoom_step != 0.0 {
+        settings.camera_zoom = ((settings.camera_zoom + zoom_step) / CAMERA_ZOOM_STEP).round() * CAMERA_ZOOM_STEP;
+        settings.camera_zoom = settings.camera_zoom.clamp(CAMERA_ZOOM_MIN, CAMERA_ZOOM_MAX);
+        status.message = Some(format!(
+            "Camera zoom: {:.0}%{}",
+            settings.camera_zoom * 100.0,
+            if settings.zoomed_out() { " (runs are not ranked)" } else { "" },
+        ));
+        return;
+    }
+
+    // Greeting cycles On -> On with the evening dark theme -> Off
+    if keyboard.just_pressed(KeyCode::KeyG) {
+        (audio.greeting, audio.evening_theme) = match (audio.greeting, audio.evening_theme) {
+            (true, false) => (true, true),
+            (true, true) => (false, false),
+            _ => (true, false),
+        };
+        save_audio_settings(&mut saves, &audio);
+        status.message = Some(
+            match (audio.greeting, audio.evening_theme) {
+                (true, false) => "Main menu greeting: On",
+                (true, true) => "Main menu greeting: On, dark theme in the evening",
+                _ => "Main menu greeting: Off",
+            }
+            .to_string(),
+        );
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyK) {
+        audio.auto_score_card = !audio.auto_score_card;
+        save_audio_settings(&mut saves, &audio);
+        status.message = Some(format!("Score card after every run: {}", if audio.auto_score_card { "On" } else { "Off" }));
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyT) {
+        audio.flap_key = audio.flap_key.next();
+        *bindings = KeyBindings::new(audio.flap_key);
+        save_audio_settings(&mut saves, &audio);
+        status.message = Some(format!("Flap key: {}", audio.flap_key.label()));
+        flag.changed = true;
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyP) {
+        audio.low_power_menus = !audio.low_power_menus;
+        save_audio_settings(&mut saves, &audio);
+        status.message = Some(format!("Low-power menus: {}", if audio.low_power_menus { "On" } else { "Off" }));
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyN) {
+        audio.name_prompt = !audio.name_prompt;
+        save_audio_settings(&mut saves, &audio);
+        status.message = Some(format!("Leaderboard name prompt: {}", if audio.name_prompt { "On" } else { "Off" }));
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyI) {
+        audio.idle_in_menus = !audio.idle_in_menus;
+        save_audio_settings(&mut saves, &audio);
+        status.message = Some(format!(
+            "Idle return in menus: {} ({:.0}s)",
+            if audio.idle_in_menus { "On" } else { "Off" },
+            audio.idle_timeout,
+        ));
+        return;
+    }
+
+    // Run code sharing: copy needs a fixed seed, paste loads mode/difficulty/seed
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        status.message = Some(match settings.seed {
+            Some(seed) => copy_run_code(&encode_run_code(settings.selected_mode, settings.selected_difficulty, seed)),
+            None => "No seed set - paste a code or copy one from Game Over".to_string(),
+        });
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyV) {
+        match read_clipboard().and_then(|text| parse_run_code(&text)) {
+            Ok((mode, difficulty, seed)) => match difficulty_lock(&settings, &saves, &config, difficulty) {
+                Some(lock) => refuse_locked(&mut commands, &fonts, &toast_query, difficulty, lock),
+                None => {
+                    settings.selected_mode = mode;
+                    settings.selected_difficulty = difficulty;
+                    settings.seed = Some(seed);
+                    status.message = Some("Run code loaded".to_string());
+                    flag.changed = true;
+                }
+            },
+            Err(e) => status.message = Some(format!("Invalid run code: {}", e)),
+        }
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyW) {
+        next_state.set(GameState::Replay);
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyX) && settings.seed.is_some() {
+        settings.seed = None;
+        status.message = Some("Seed cleared - runs are random again".to_string());
+        flag.changed = true;
+        return;
+    }
+    
+    for (digit, state) in [
+        (1, GameState::SaveSelect),
+        (2, GameState::ModeSelect),
+        (3, GameState::DifficultySelect),
+        (4, GameState::ThemeSelect),
+        (5, GameState::SkinSelect),
+        (6, GameState::AccessibilitySelect),
+        (7, GameState::Challenge),
+        (8, GameState::Cosmetics),
+    ] {
+        if digit_just_pressed(&keyboard, digit) {
+            next_state.set(state);
+            return;
+        }
+    }
+}
+
+fn setup_options_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    status: Res<RunCodeStatus>,
+    trainer: Res<TrainerTuning>,
+    bindings: Res<KeyBindings>,
+    saves: Res<SaveWriter>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        OptionsMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        OptionsMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("OPTIONS"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+        ));
+        
+        // Display current settings
+        let slot_text = if let Some(slot_num) = settings.current_slot {
+            if let Some(save_data) = saves.load(slot_num) {
+                format!("Slot {}: {}", slot_num, save_data.profile.name)
+            } else {
+                format!("Slot {}: New", slot_num)
+            }
+        } else {
+            "No slot selected".to_string()
+        };
+
+        let entries = [
+            format!("Saves:  {}", slot_text),
+            match (settings.selected_mode, &settings.selected_level) {
+                (GameMode::Level, Some(level)) => format!("Game Mode:  Level ({})", level),
+                (GameMode::Trainer, _) => format!("Game Mode:  Trainer (gap {:.2})", trainer.gap_size),
+                (mode, _) => format!("Game Mode:  {:?}", mode),
+            },
+            format!("Difficulty:  {:?}{}", settings.selected_difficulty, if settings.is_custom() { " (Custom)" } else { "" }),
+            if settings.modifiers.any() {
+                format!("Theme:  {:?}  + {}", settings.selected_theme, settings.modifiers.names().join(", "))
+            } else {
+                format!("Theme:  {:?}", settings.selected_theme)
+            },
+            format!("Skin:  {:?}", settings.selected_skin),
+            "Accessibility".to_string(),
+            if settings.is_challenge() {
+                format!("Challenge:  {}", settings.challenge_label())
+            } else {
+                "Challenge:  Off".to_string()
+            },
+            match settings.current_slot.and_then(|slot| saves.load(slot)) {
+                Some(save) => format!(
+                    "Cosmetics:  {:?} trail, {:?} flap  ({} coins)",
+                    save.profile.trail, save.profile.flap_sound, save.profile.coins
+                ),
+                None => "Cosmetics".to_string(),
+            },
+        ];
+
+        for entry in entries {
+            parent.spawn((
+                Text::new(entry),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(8.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        let run_code = match settings.seed {
+            Some(seed) => encode_run_code(settings.selected_mode, settings.selected_difficulty, seed),
+            None => "Random".to_string(),
+        };
+
+        parent.spawn((
+            Text::new(format!("Run Code:  {}", run_code)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(8.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(status.message.clone().unwrap_or_default()),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
+                ..default()
+            },
+            TextColor(AQUAMARINE.into()),
+            TextShadow::default(),
+            RunCodeStatusDisplay,
+        ));
+
+        parent.spawn((
+            Text::new(format!(
+                "Select Option [1-8]   Run Code Copy/Paste/Clear [C/V/X]\nWatch Replay [W]   Record Marker [B]   Fill Window [F]   Idle Return in Menus [I]   Low-Power Menus [P]   Flap Key [T]: {}\nText Size [-/+]   Camera Zoom [[/]]   Greeting [G]   Name Prompt [N]   Auto Score Card [K]   Return to Main Menu [ESC]",
+                bindings.flap_key.label(),
+            )),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(20.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn refresh_options_ui(
+    mut commands: Commands,
+    query: Query<Entity, With<OptionsMarker>>,
+    mut flag: ResMut<OptionsChanged>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    status: Res<RunCodeStatus>,
+    trainer: Res<TrainerTuning>,
+    bindings: Res<KeyBindings>,
+    saves: Res<SaveWriter>,
+) {
+    if flag.changed {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+
+        setup_options_ui(commands, asset_server, fonts, window_query, settings, status, trainer, bindings, saves);
+
+        flag.changed = false;
+    }
+}
+
+// ---------------------------- NAVIGATION ----------------------------
+// Menu screens that remember where they were entered from; everything else clears the stack
+const NAV_SCREENS: [GameState; 15] = [
+    GameState::Options,
+    GameState::SaveSelect,
+    GameState::ModeSelect,
+    GameState::SeedSelect,
+    GameState::LevelSelect,
+    GameState::TrainerSelect,
+    GameState::DifficultySelect,
+    GameState::DifficultyTune,
+    GameState::ThemeSelect,
+    GameState::Modifiers,
+    GameState::SkinSelect,
+    GameState::AccessibilitySelect,
+    GameState::Challenge,
+    GameState::Cosmetics,
+    GameState::Leaderboard,
+];
+
+// Entering a screen already on the stack (by ESC or by confirming back to it) pops down to it
+fn track_navigation(mut transitions: MessageReader<StateTransitionEvent<GameState>>, mut nav: ResMut<NavStack>) {
+    for transition in transitions.read() {
+        let (Some(from), Some(to)) = (transition.exited, transition.entered) else {
+            continue;
+        };
+        if from == to {
+            continue;
+        }
+        if !NAV_SCREENS.contains(&to) {
+            nav.screens.clear();
+        } else if let Some(index) = nav.screens.iter().position(|state| *state == to) {
+            nav.screens.truncate(index);
+        } else {
+            nav.screens.push(from);
+        }
+    }
+}
+
+#[derive(Component)]
+struct Breadcrumb;
+
+// How far into the pre-run flow each screen sits; sub-screens share the step after their parent
+fn breadcrumb_step(state: GameState) -> Option<usize> {
+    match state {
+        GameState::ModeSelect => Some(1),
+        GameState::SeedSelect | GameState::LevelSelect | GameState::TrainerSelect | GameState::DifficultySelect => Some(2),
+        GameState::DifficultyTune | GameState::ThemeSelect => Some(3),
+        GameState::Modifiers | GameState::SkinSelect => Some(4),
+        GameState::AccessibilitySelect => Some(5),
+        _ => None,
+    }
+}
+
+// "Slot 2 > TimeAttack > Hard > ..." - the choices made on the steps before this screen
+fn breadcrumb_text(settings: &GameSettings, state: GameState) -> Option<String> {
+    let step = breadcrumb_step(state)?;
+    let path = [
+        settings.current_slot.map_or("No Slot".to_string(), |slot| format!("Slot {}", slot)),
+        match (settings.selected_mode, &settings.selected_level) {
+            (GameMode::Level, Some(level)) => format!("Level ({})", level),
+            (mode, _) => format!("{:?}", mode),
+        },
+        format!("{:?}{}", settings.selected_difficulty, if settings.is_custom() { " (Custom)" } else { "" }),
+        format!("{:?}", settings.selected_theme),
+        format!("{:?}", settings.selected_skin),
+    ];
+    let mut parts: Vec<String> = path.into_iter().take(step).collect();
+    parts.push("...".to_string());
+    Some(parts.join("  >  "))
+}
+
+fn update_breadcrumb(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    settings: Res<GameSettings>,
+    fonts: Res<UiFonts>,
+    query: Query<Entity, With<Breadcrumb>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+    let Some(text) = breadcrumb_text(&settings, *state.get()) else {
+        return;
+    };
+    commands.spawn((
+        Text::new(text),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(18.0),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.992, 0.816)),
+        TextBackgroundColor(Color::BLACK.with_alpha(0.35)),
+        TextShadow::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        Breadcrumb,
+    ));
+}
+
+// ---------------------------- OVERWRITE CONFIRM ----------------------------
+// The slot's settings are replaced when the next ranked run ends, so a change is confirmed first
+fn slot_differences(settings: &GameSettings, save: &SaveSlot) -> Vec<String> {
+    let mut lines = Vec::new();
+    if settings.selected_mode != save.mode {
+        lines.push(format!("Mode:  {:?} -> {:?}", save.mode, settings.selected_mode));
+    }
+    if settings.selected_difficulty != save.difficulty {
+        lines.push(format!("Difficulty:  {:?} -> {:?}", save.difficulty, settings.selected_difficulty));
+    }
+    if settings.selected_theme != save.theme {
+        lines.push(format!("Theme:  {:?} -> {:?}", save.theme, settings.selected_theme));
+    }
+    if settings.selected_skin != save.skin {
+        lines.push(format!("Skin:  {:?} -> {:?}", save.skin, settings.selected_skin));
+    }
+    lines
+}
+
+fn setup_overwrite_confirm_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        OverwriteConfirmMarker,
+    ));
+
+    let save = settings.current_slot.and_then(|slot| saves.load(slot));
+    let lines = save.as_ref().map_or_else(Vec::new, |save| slot_differences(&settings, save));
+    let name = save.map_or_else(String::new, |save| save.profile.name);
+
+    commands.spawn((
+        menu_root_node(),
+        OverwriteConfirmMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("CHANGE SLOT SETTINGS?"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(56.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(format!("{} will be saved with these settings after the run:", name)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+
+        for line in lines {
+            parent.spawn((
+                Text::new(line),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(32.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new("\nPlay Anyway [SPACE]\nPlay with the Slot's Settings [R]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn overwrite_confirm_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut fade: ResMut<ScreenFade>,
+    mut settings: ResMut<GameSettings>,
+    saves: Res<SaveWriter>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        fade.request_transition(GameState::MainMenu);
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        if let Some(save) = settings.current_slot.and_then(|slot| saves.load(slot)) {
+            settings.selected_mode = save.mode;
+            settings.selected_difficulty = save.difficulty;
+            settings.selected_theme = save.theme;
+            settings.selected_skin = save.skin;
+        }
+        fade.request_transition(GameState::Playing);
+        return;
+    }
+
+    if bindings.consume(&mut keyboard, InputAction::MenuConfirm) {
+        fade.request_transition(GameState::Playing);
+    }
+}
+
+// ---------------------------- SEED HISTORY ----------------------------
+// Starts a run on a remembered layout; a pinned seed is flagged "seeded" on the leaderboard.
+// Goes through the same slot checks as starting from the Main Menu
+fn launch_seed(settings: &mut GameSettings, saves: &SaveWriter, prewarm: &AudioPrewarm, record: SeedRecord, fade: &mut ScreenFade) {
+    // Left alone while another screen switch is fading out
+    if fade.target.is_some() || !prewarm.is_done() {
+        return;
+    }
+    settings.selected_mode = record.mode;
+    settings.selected_difficulty = record.difficulty;
+    settings.seed = Some(record.seed);
+    start_run(settings, saves, fade);
+}
+
+fn setup_seed_select_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    audio: Res<AudioSettings>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        SeedSelectMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        SeedSelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("RECENT SEEDS"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        if audio.seed_history.is_empty() {
+            parent.spawn((
+                Text::new("No finished runs yet"),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(24.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+            ));
+        }
+
+        for (i, record) in audio.seed_history.iter().enumerate() {
+            parent.spawn((
+                Text::new(format!(
+                    "[{}] {}  {:?}/{:?} - {} pts",
+                    (i + 1) % 10,
+                    encode_run_code(record.mode, record.difficulty, record.seed),
+                    record.mode,
+                    record.difficulty,
+                    record.score,
+                )),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(22.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new("Play a Layout Again [1-9, 0]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn seed_select_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut fade: ResMut<ScreenFade>,
+    mut settings: ResMut<GameSettings>,
+    audio: Res<AudioSettings>,
+    nav: Res<NavStack>,
+    saves: Res<SaveWriter>,
+    config: Res<GameConfig>,
+    prewarm: Res<AudioPrewarm>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::ModeSelect));
+        return;
+    }
+
+    let picked = (0..audio.seed_history.len()).find(|i| digit_just_pressed(&keyboard, (i + 1) % 10));
+    if let Some(record) = picked.map(|i| audio.seed_history[i]) {
+        match difficulty_lock(&settings, &saves, &config, record.difficulty) {
+            Some(lock) => refuse_locked(&mut commands, &fonts, &toast_query, record.difficulty, lock),
+            None => launch_seed(&mut settings, &saves, &prewarm, record, &mut fade),
+        }
+    }
+}
+
+// L in the menus relaunches the most recent layout (Main Menu keeps L for the leaderboard)
+fn relaunch_last_seed(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut fade: ResMut<ScreenFade>,
+    mut settings: ResMut<GameSettings>,
+    audio: Res<AudioSettings>,
+    saves: Res<SaveWriter>,
+    config: Res<GameConfig>,
+    prewarm: Res<AudioPrewarm>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+    if let Some(&record) = audio.seed_history.first() {
+        match difficulty_lock(&settings, &saves, &config, record.difficulty) {
+            Some(lock) => refuse_locked(&mut commands, &fonts, &toast_query, record.difficulty, lock),
+            None => launch_seed(&mut settings, &saves, &prewarm, record, &mut fade),
+        }
+    }
+}
+
+// ---------------------------- RUN CODES ----------------------------
+// Shareable "FLAPPY-<mode><difficulty>-<seed>" codes, e.g. FLAPPY-EN-00000000DEADBEEF
+fn encode_run_code(mode: GameMode, difficulty: Difficulty, seed: u64) -> String {
+    let mode_char = match mode {
+        GameMode::Endless => 'E',
+        GameMode::TimeAttack => 'T',
+        GameMode::Checkpoints => 'C',
+        GameMode::Tournament => 'R',
+        GameMode::Level => 'L',
+        GameMode::Trainer => 'G',
+    };
+    let difficulty_char = match difficulty {
+        Difficulty::Easy => 'E',
+        Difficulty::Normal => 'N',
+        Difficulty::Hard => 'H',
+        Difficulty::Insane => 'I',
+    };
+    format!("{}-{}{}-{:016X}", RUN_CODE_PREFIX, mode_char, difficulty_char, seed)
+}
+
+fn parse_run_code(code: &str) -> Result<(GameMode, Difficulty, u64), String> {
+    let parts: Vec<&str> = code.trim().split('-').collect();
+    let [prefix, kind, seed] = parts.as_slice() else {
+        return Err("expected FLAPPY-XX-SEED".to_string());
+    };
+    if !prefix.eq_ignore_ascii_case(RUN_CODE_PREFIX) {
+        return Err("missing FLAPPY prefix".to_string());
+    }
+
+    let mut kind_chars = kind.chars().map(|c| c.to_ascii_uppercase());
+    let mode = match kind_chars.next() {
+        Some('E') => GameMode::Endless,
+        Some('T') => GameMode::TimeAttack,
+        Some('C') => GameMode::Checkpoints,
+        Some('R') => GameMode::Tournament,
+        // The course comes from the level file, not the seed
+        Some('L') => return Err("level runs are shared by their level file".to_string()),
+        Some('G') => return Err("trainer runs can't be shared".to_string()),
+        _ => return Err("unknown mode".to_string()),
+    };
+    let difficulty = match kind_chars.next() {
+        Some('E') => Difficulty::Easy,
+        Some('N') => Difficulty::Normal,
+        Some('H') => Difficulty::Hard,
+        Some('I') => Difficulty::Insane,
+        _ => return Err("unknown difficulty".to_string()),
+    };
+    if kind_chars.next().is_some() {
+        return Err("unknown mode".to_string());
+    }
+
+    let seed = u64::from_str_radix(seed, 16).map_err(|_| "seed is not hexadecimal".to_string())?;
+    Ok((mode, difficulty, seed))
+}
+
+// Returns the status line to show after trying to copy a code
+fn copy_run_code(code: &str) -> String {
+    match write_clipboard(code) {
+        Ok(()) => format!("Copied {}", code),
+        Err(e) => {
+            eprintln!("Failed to copy run code: {}", e);
+            println!("Run code: {}", code);
+            "Clipboard unavailable - code printed to console".to_string()
+        }
+    }
+}
+
+#[cfg(feature = "clipboard")]
+fn write_clipboard(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.to_string()))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "clipboard")]
+fn read_clipboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn write_clipboard(_text: &str) -> Result<(), String> {
+    Err("built without clipboard support".to_string())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn read_clipboard() -> Result<String, String> {
+    Err("built without clipboard support".to_string())
+}
+
+fn update_run_code_status(status: Res<RunCodeStatus>, mut query: Query<&mut Text, With<RunCodeStatusDisplay>>) {
+    if !status.is_changed() {
+        return;
+    }
+    for mut text in query.iter_mut() {
+        text.0 = status.message.clone().unwrap_or_default();
+    }
+}
+
+fn clear_run_code_status(mut status: ResMut<RunCodeStatus>) {
+    status.message = None;
+}
+
+// Save Select UI - UPDATED with background and font
+fn setup_save_select_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    cursor: Res<MenuCursor>,
+    saves: Res<SaveWriter>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        SaveSelectMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        SaveSelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("SAVES"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            SlotList,
+        ))
+        .with_children(|list| spawn_slot_rows(list, &asset_server, &fonts, &cursor, &saves));
+
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(22.0),
+                ..default()
+            },
+            TextColor(AQUAMARINE.into()),
+            TextShadow::default(),
+            SlotActionPrompt,
+        ));
+
+        parent.spawn((
+            Text::new("\nSelect a slot [1/2/3]\nChange avatar [UP/DOWN + LEFT/RIGHT]\nDelete a slot [CTRL + 1/2/3]\nCopy a slot [C, then from and to 1/2/3]\nRestore a slot's previous save [U, then 1/2/3]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+// Slot rows live in their own container so refreshes rebuild only them
+fn spawn_slot_rows(parent: &mut ChildSpawnerCommands, asset_server: &AssetServer, fonts: &UiFonts, cursor: &MenuCursor, saves: &SaveWriter) {
+    for slot_num in 1..=3u8 {
+        let save_data = saves.load(slot_num);
+        let highlighted = cursor.index + 1 == slot_num as usize;
+        let marker = if highlighted { ">" } else { " " };
+        let text = if let Some(save) = &save_data {
+            format!("{} [{}] Slot {}: {} - High Score: {}", 
+                marker, slot_num, slot_num, save.profile.name, save.profile.high_score)
+        } else {
+            format!("{} [{}] Slot {}: Empty (New Player)", marker, slot_num, slot_num)
+        };
+        
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Column,
+            margin: UiRect::all(fonts.margin(15.0)),
+            ..default()
+        })
+        .with_children(|slot| {
+            slot.spawn(Node {
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|row| {
+                // Empty slots keep the same indent as saved ones
+                match &save_data {
+                    Some(save) => spawn_avatar(row, asset_server, save.profile.avatar, 40.0),
+                    None => {
+                        row.spawn(Node {
+                            width: Val::Px(40.0),
+                            margin: UiRect::right(Val::Px(12.0)),
+                            ..default()
+                        });
+                    }
+                }
+                row.spawn((
+                    Text::new(text),
+                    TextFont {
+                        font: fonts.body.clone(),
+                        font_size: fonts.scaled(32.0),
+                        ..default()
+                    },
+                    TextColor(if highlighted { AQUAMARINE.into() } else { Color::srgb(1.0, 0.992, 0.816) }),
+                    TextShadow::default(),
+                    TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                ));
+            });
+
+            // Recent scores line up under the name, past the avatar column
+            if let Some(save) = save_data.as_ref().filter(|save| !save.profile.history.is_empty()) {
+                spawn_sparkline(slot, &save.profile.history, &save.profile.history_interrupted, SPARKLINE_HEIGHT);
+                let (trend, color) = trend_label(&save.profile.history);
+                slot.spawn((
+                    Text::new(trend),
+                    TextFont {
+                        font: fonts.body.clone(),
+                        font_size: fonts.scaled(16.0),
+                        ..default()
+                    },
+                    TextColor(color),
+                    TextShadow::default(),
+                    Node {
+                        margin: UiRect::left(Val::Px(52.0)),
+                        ..default()
+                    },
+                ));
+            }
+        });
+    }
+}
+
+// Change delete_save_slot to return a boolean indicating if a slot was deleted
+fn delete_save_slot(saves: &SaveWriter, slot: u32) -> bool {
+    saves.store().delete_slot_file(slot as u8)
+}
+
+fn reset_slot_action(mut action: ResMut<SlotAction>) {
+    *action = SlotAction::Idle;
+}
+
+// Duplicates a profile into another slot under a " (copy)" name; the write goes through
+// SaveWriter, whose completion refreshes the slot rows
+fn copy_save_slot(saves: &mut SaveWriter, from: u8, to: u8) -> Result<String, String> {
+    let mut save = saves.load(from).ok_or_else(|| format!("Slot {} is empty", from))?;
+    save.slot_number = to;
+    save.profile.name = format!("{} (copy)", save.profile.name);
+    let name = save.profile.name.clone();
+    saves.write(save);
+    Ok(name)
+}
+
+// Keys while an action is in progress; Some once it is done, refused or cancelled
+fn slot_action_input(
+    keyboard: &ButtonInput<KeyCode>,
+    action: &mut SlotAction,
+    saves: &mut SaveWriter,
+    settings: &mut GameSettings,
+) -> Option<Result<String, String>> {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        *action = SlotAction::Idle;
+        return Some(Err("Cancelled".to_string()));
+    }
+    let digit = (1..=3u8).find(|slot| digit_just_pressed(keyboard, *slot as usize));
+    let (from, to) = match (*action, digit) {
+        (SlotAction::CopyFrom, Some(from)) if saves.load(from).is_none() => {
+            *action = SlotAction::Idle;
+            return Some(Err(format!("Slot {} is empty - nothing to copy", from)));
+        }
+        (SlotAction::CopyFrom, Some(from)) => {
+            *action = SlotAction::CopyTo(from);
+            return None;
+        }
+        (SlotAction::CopyTo(from), Some(to)) if from == to => {
+            *action = SlotAction::Idle;
+            return Some(Err(format!("Slot {} is already itself - nothing copied", from)));
+        }
+        (SlotAction::CopyTo(from), Some(to)) if saves.load(to).is_some() => {
+            *action = SlotAction::CopyConfirm(from, to);
+            return None;
+        }
+        (SlotAction::CopyTo(from), Some(to)) => (from, to),
+        (SlotAction::CopyConfirm(from, to), _) if keyboard.just_pressed(KeyCode::KeyY) => (from, to),
+        (SlotAction::RestorePick, Some(slot)) if saves.store().load_slot_backup(slot).is_none() => {
+            *action = SlotAction::Idle;
+            return Some(Err(format!("Slot {} has no previous save", slot)));
+        }
+        (SlotAction::RestorePick, Some(slot)) => {
+            *action = SlotAction::RestoreConfirm(slot);
+            return None;
+        }
+        (SlotAction::RestoreConfirm(slot), _) if keyboard.just_pressed(KeyCode::KeyY) => {
+            *action = SlotAction::Idle;
+            if saves.slots.contains_key(&slot) {
+                return Some(Err(format!("Slot {} is still being saved - try again", slot)));
+            }
+            if settings.current_slot == Some(slot) {
+                settings.current_slot = None;
+            }
+            return Some(saves.store().restore_slot_backup(slot).map(|_| format!("Slot {} restored - press U again to undo", slot)));
+        }
+        _ => return None,
+    };
+
+    *action = SlotAction::Idle;
+    // Like a delete, replacing the active slot's profile deselects it
+    if settings.current_slot == Some(to) {
+        settings.current_slot = None;
+    }
+    Some(copy_save_slot(saves, from, to).map(|name| format!("Copied slot {} to slot {} as {}", from, to, name)))
+}
+
+fn slot_action_prompt(action: SlotAction, saves: &SaveWriter) -> String {
+    let describe = |save: Option<SaveSlot>| {
+        save.map_or_else(|| "an empty slot".to_string(), |save| format!("{} (high score {})", save.profile.name, save.profile.high_score))
+    };
+    match action {
+        SlotAction::Idle => String::new(),
+        SlotAction::CopyFrom => "Copy: pick the slot to copy [1/2/3]   Cancel [ESC]".to_string(),
+        SlotAction::CopyTo(from) => format!("Copy slot {} to [1/2/3]   Cancel [ESC]", from),
+        SlotAction::CopyConfirm(from, to) => format!(
+            "Slot {} holds {} - replace it with a copy of slot {}? [Y]   Cancel [ESC]",
+            to,
+            saves.load(to).map_or_else(|| "a profile".to_string(), |save| save.profile.name),
+            from,
+        ),
+        SlotAction::RestorePick => "Restore: pick the slot to roll back [1/2/3]   Cancel [ESC]".to_string(),
+        SlotAction::RestoreConfirm(slot) => format!(
+            "Swap slot {}: {}\nfor its previous save: {}? [Y]   Cancel [ESC]",
+            slot,
+            describe(saves.load(slot)),
+            describe(saves.store().load_slot_backup(slot)),
+        ),
+    }
+}
+
+// Update save_select_system to handle this differently
+fn save_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut flag: ResMut<SaveSlotChanged>,
+    nav: Res<NavStack>,  // Track where we came from
+    mut cursor: ResMut<MenuCursor>,
+    mut commands: Commands,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+    mut saves: ResMut<SaveWriter>,
+    mut action: ResMut<SlotAction>,
+    mut prompt_query: Query<&mut Text, With<SlotActionPrompt>>,
+    mut audio: ResMut<AudioSettings>,
+    clock: Res<LocalClock>,
+) {
+    if *action != SlotAction::Idle {
+        if let Some(result) = slot_action_input(&keyboard, &mut action, &mut saves, &mut settings) {
+            let (message, color) = match result {
+                Ok(message) => (message, Color::srgb(1.0, 0.84, 0.0)),
+                Err(message) => (message, Color::srgb(0.9, 0.35, 0.3)),
+            };
+            spawn_toast(&mut commands, &fonts, &toast_query, message, color);
+            flag.changed = true;
+        }
+        for mut text in prompt_query.iter_mut() {
+            text.0 = slot_action_prompt(*action, &saves);
+        }
+        return;
+    }
+
+    let started = if keyboard.just_pressed(KeyCode::KeyC) {
+        SlotAction::CopyFrom
+    } else if keyboard.just_pressed(KeyCode::KeyU) {
+        SlotAction::RestorePick
+    } else {
+        SlotAction::Idle
+    };
+    if started != SlotAction::Idle {
+        *action = started;
+        for mut text in prompt_query.iter_mut() {
+            text.0 = slot_action_prompt(*action, &saves);
+        }
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        // Return to where we came from
+        next_state.set(nav.back(GameState::MainMenu));
+        return;
+    }
+
+    // Up/Down picks a slot row, Left/Right cycles its avatar with a live preview
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        cursor.index = (cursor.index + 2) % 3;
+        flag.changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        cursor.index = (cursor.index + 1) % 3;
+        flag.changed = true;
+    }
+    let step = if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -1
+    } else if keyboard.just_pressed(KeyCode::ArrowRight) {
+        1
+    } else {
+        0
+    };
+    if step != 0 && cycle_avatar(&mut saves, cursor.index as u8 + 1, step) {
+        flag.changed = true;
+    }
+    
+    let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
+
+    let mut slot_deleted = false;
+    
+    for slot in 1..=3u8 {
+        if digit_just_pressed(&keyboard, slot as usize) {
+            if ctrl {
+                if delete_save_slot(&saves, slot as u32) {
+                    settings.current_slot = None;
+                    slot_deleted = true;
+                }
+                continue;
+            }
+
+            settings.current_slot = Some(slot);
+            if audio.last_slot != Some(slot) {
+                audio.last_slot = Some(slot);
+                save_audio_settings(&mut saves, &audio);
+            }
+            
+            // Load existing save or use defaults
+            if let Some(save_data) = saves.load(slot) {
+                settings.selected_mode = save_data.mode;
+                settings.selected_difficulty = save_data.difficulty;
+                settings.selected_theme = save_data.theme;
+                settings.selected_skin = save_data.skin;
+                settings.photosensitive_safe = save_data.photosensitive_safe;
+                settings.floor_warning = save_data.floor_warning;
+                settings.floor_warning_sound = save_data.floor_warning_sound;
+                settings.gap_tone = save_data.gap_tone;
+                settings.blind_run = save_data.blind_run;
+                settings.spawn_strategy = save_data.spawn_strategy;
+                settings.selected_level = save_data.level;
+                settings.reduce_motion = save_data.reduce_motion;
+                settings.overtime = save_data.overtime;
+                settings.record_marker = save_data.record_marker;
+                settings.gap_telegraph = save_data.gap_telegraph;
+                settings.fill_window = save_data.fill_window;
+                settings.camera_zoom = save_data.camera_zoom.clamp(CAMERA_ZOOM_MIN, CAMERA_ZOOM_MAX);
+                settings.custom_tuning = save_data.custom_tuning.clamped();
+            } else {
+                // New slot - reset to defaults
+                settings.selected_mode = GameMode::Endless;
+                settings.selected_difficulty = Difficulty::Normal;
+                settings.selected_theme = audio.default_theme(&clock);
+                settings.selected_skin = Skin::Classic;
+                settings.photosensitive_safe = false;
+                settings.floor_warning = None;
+                settings.floor_warning_sound = true;
+                settings.gap_tone = false;
+                settings.blind_run = false;
+                settings.spawn_strategy = SpawnStrategy::Recycle;
+                settings.selected_level = None;
+                settings.reduce_motion = false;
+                settings.overtime = false;
+                settings.record_marker = true;
+                settings.gap_telegraph = false;
+                settings.fill_window = false;
+                settings.camera_zoom = 1.0;
+                settings.custom_tuning = CustomTuning::default();
+            }
+            
+            // Different transition based on origin
+            match nav.back(GameState::MainMenu) {
+                GameState::MainMenu => {
+                    // Came from MainMenu (starting game without slot) → go to Playing
+                    next_state.set(GameState::Playing);
+                }
+                _ => {
+                    // Came from Options or elsewhere → go back to MainMenu
+                    next_state.set(GameState::MainMenu);
+                }
+            }
+            return;
+        }
+    }
+    
+    // Only set the flag if a slot was actually deleted
+    if slot_deleted {
+        flag.changed = true;
+    }
+}
+
+// Old rows are despawned and new ones spawned in the same command flush, so they never overlap on screen
+fn refresh_save_select_ui(
+    mut commands: Commands,
+    list_query: Query<Entity, With<SlotList>>,
+    mut flag: ResMut<SaveSlotChanged>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    cursor: Res<MenuCursor>,
+    saves: Res<SaveWriter>,
+) {
+    if !flag.changed {
+        return;
+    }
+    for list in &list_query {
+        commands
+            .entity(list)
+            .despawn_related::<Children>()
+            .with_children(|parent| spawn_slot_rows(parent, &asset_server, &fonts, &cursor, &saves));
+    }
+    flag.changed = false;
+}
+
+// Mode Select UI - UPDATED with background and font
+fn setup_mode_select_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    // Add background image
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        ModeSelectMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        ModeSelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("GAME MODE"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new(format!("Endless{}", mastered_badge(&settings, &saves, GameMode::Endless, settings.selected_difficulty))),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new(format!("Time Attack{}", mastered_badge(&settings, &saves, GameMode::TimeAttack, settings.selected_difficulty))),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new(format!("Checkpoints{}", mastered_badge(&settings, &saves, GameMode::Checkpoints, settings.selected_difficulty))),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(format!("Tournament [{} x {:.0}s rounds]", TOURNAMENT_ROUNDS, TOURNAMENT_ROUND_DURATION)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new("Level..."),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new("Gap Trainer..."),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Select [1-6]   Recent Seeds [S]   Last Seed [L]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn mode_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::Options));
+        return;
+    }
+
+    // Level mode needs a level file picked first
+    if digit_just_pressed(&keyboard, 5) {
+        next_state.set(GameState::LevelSelect);
+        return;
+    }
+
+    if digit_just_pressed(&keyboard, 6) {
+        next_state.set(GameState::TrainerSelect);
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyS) {
+        next_state.set(GameState::SeedSelect);
+        return;
+    }
+    
+    for (digit, mode) in [
+        (1, GameMode::Endless),
+        (2, GameMode::TimeAttack),
+        (3, GameMode::Checkpoints),
+        (4, GameMode::Tournament),
+    ] {
+        if digit_just_pressed(&keyboard, digit) {
+            settings.selected_mode = mode;
+            next_state.set(GameState::Options);
+            return;
+        }
+    }
+}
+
+// Difficulty Select UI - UPDATED with background and font
+fn setup_difficulty_select_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+    config: Res<GameConfig>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    // Add background image
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        DifficultySelectMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        DifficultySelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("DIFFICULTY"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new(format!("Easy [Large Gaps, Slow, Low Gravity]{}", mastered_badge(&settings, &saves, settings.selected_mode, Difficulty::Easy))),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(0.5, 1.0, 0.5)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new(format!("Normal [Standard difficulty]{}", mastered_badge(&settings, &saves, settings.selected_mode, Difficulty::Normal))),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 0.5)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+        
+        // Locked rows are greyed out and show what unlocks them instead
+        for (difficulty, description, color) in [
+            (Difficulty::Hard, "Smaller Gaps, Fast, High Gravity", Color::srgb(1.0, 0.5, 0.5)),
+            (Difficulty::Insane, "Tiny Gaps, Very Fast, Heavy Gravity", Color::srgb(0.75, 0.1, 0.1)),
+        ] {
+            let lock = difficulty_lock(&settings, &saves, &config, difficulty);
+            parent.spawn((
+                Text::new(match &lock {
+                    Some(lock) => format!("{:?} [Locked - {}]", difficulty, lock),
+                    None => format!("{:?} [{}]{}", difficulty, description, mastered_badge(&settings, &saves, settings.selected_mode, difficulty)),
+                }),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(32.0),
+                    ..default()
+                },
+                TextColor(if lock.is_some() { Color::srgb(0.5, 0.5, 0.5) } else { color }),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new(format!(
+                "Pipe Spawning:  {:?}   Gap Shrink (Endless, config.ron):  {}",
+                settings.spawn_strategy,
+                if config.gap_shrink.enabled { format!("to {:.0}%", config.gap_shrink.floor * 100.0) } else { "Off".to_string() },
+            )),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(AQUAMARINE.into()),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(format!(
+                "Bird Size (next run):  {:?}   Overtime (Time Attack):  {}",
+                settings.bird_size,
+                if settings.overtime { "On" } else { "Off" },
+            )),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(AQUAMARINE.into()),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Select [1-4]   Fine-Tune Selected [TAB]\nTiny / Giant Bird [T/G]   Pipe Spawning [S]\nOvertime [O]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn difficulty_select_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut flag: ResMut<OptionsChanged>,
+    saves: Res<SaveWriter>,
+    config: Res<GameConfig>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::Options));
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyS) {
+        settings.spawn_strategy = match settings.spawn_strategy {
+            SpawnStrategy::Recycle => SpawnStrategy::Stream,
+            SpawnStrategy::Stream => SpawnStrategy::Recycle,
+        };
+        flag.changed = true;
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyO) {
+        settings.overtime = !settings.overtime;
+        flag.changed = true;
+        return;
+    }
+
+    for (key, size) in [(KeyCode::KeyT, BirdSize::Tiny), (KeyCode::KeyG, BirdSize::Giant)] {
+        if keyboard.just_pressed(key) {
+            settings.bird_size = settings.bird_size.toggled(size);
+            flag.changed = true;
+            return;
+        }
+    }
+    
+    for (digit, difficulty) in [
+        (1, Difficulty::Easy),
+        (2, Difficulty::Normal),
+        (3, Difficulty::Hard),
+        (4, Difficulty::Insane),
+    ] {
+        if digit_just_pressed(&keyboard, digit) {
+            if let Some(lock) = difficulty_lock(&settings, &saves, &config, difficulty) {
+                refuse_locked(&mut commands, &fonts, &toast_query, difficulty, lock);
+                return;
+            }
+            settings.selected_difficulty = difficulty;
+            settings.custom_tuning = CustomTuning::default();
+            next_state.set(GameState::Options);
+            return;
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Tab) {
+        next_state.set(GameState::DifficultyTune);
+    }
+}
+
+fn refresh_difficulty_select_ui(
+    mut commands: Commands,
+    query: Query<Entity, With<DifficultySelectMarker>>,
+    mut flag: ResMut<OptionsChanged>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+    config: Res<GameConfig>,
+) {
+    if flag.changed {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+
+        setup_difficulty_select_ui(commands, asset_server, fonts, window_query, settings, saves, config);
+
+        flag.changed = false;
+    }
+}
+
+// ---------------------------- DIFFICULTY TUNE ----------------------------
+// Second step of the difficulty select: Up/Down picks a row, Left/Right moves it by TUNE_STEP
+fn tune_row_text(row: usize, custom: &CustomTuning, tuning: &DifficultyTuning) -> String {
+    let (name, value, info) = match row {
+        0 => ("Gap Size", custom.gap, format!("estimated gap: {:.0}px", 2. * tuning.gap_size * PIXEL_RATIO)),
+        1 => ("Scroll Speed", custom.speed, format!("{:.0} px/s", tuning.scroll_speed)),
+        2 => ("Gravity", custom.gravity, format!("{:.0} px/s each second", GRAVITY * tuning.gravity_mult)),
+        // Height of one flap from a standstill: v^2 / 2g
+        3 => {
+            let velocity = FLAP_FORCE * tuning.flap_mult;
+            ("Flap Strength", custom.flap, format!("flap height: {:.0}px", velocity * velocity / (2. * GRAVITY * tuning.gravity_mult)))
+        }
+        _ => ("Gap Height Spread", custom.offset, format!("gaps within {:.0}px of center", tuning.vertical_offset * PIXEL_RATIO)),
+    };
+    format!("{}:  < {:.0}% >   {}", name, value * 100.0, info)
+}
+
+fn tune_summary_text(settings: &GameSettings, tuning: &DifficultyTuning) -> String {
+    if let Some(problem) = course_problem(tuning) {
+        format!("Not passable: {}", problem)
+    } else if settings.custom_tuning.is_custom() {
+        format!("Custom {:?} - runs are flagged and keep a separate best", settings.selected_difficulty)
+    } else {
+        format!("{:?} preset", settings.selected_difficulty)
+    }
+}
+
+fn tune_summary_color(tuning: &DifficultyTuning) -> Color {
+    if is_course_passable(tuning) { AQUAMARINE.into() } else { Color::srgb(0.9, 0.35, 0.3) }
+}
+
+fn setup_difficulty_tune_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    config: Res<GameConfig>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        DifficultyTuneMarker,
+    ));
+
+    let tuning = settings.custom_tuning.apply(difficulty_tuning(settings.selected_difficulty, &config));
+    commands.spawn((
+        menu_root_node(),
+        DifficultyTuneMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("FINE-TUNE"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(tune_summary_text(&settings, &tuning)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(tune_summary_color(&tuning)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+            TuneSummary,
+        ));
+
+        for row in 0..TUNE_ROWS {
+            parent.spawn((
+                Text::new(tune_row_text(row, &settings.custom_tuning, &tuning)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+                TuneRow(row),
+            ));
+        }
+
+        parent.spawn((
+            Text::new("Navigate [UP/DOWN]  Adjust [LEFT/RIGHT]\nBack to Preset [R]   Return [ENTER/ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn difficulty_tune_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut cursor: ResMut<MenuCursor>,
+    config: Res<GameConfig>,
+    mut rows: Query<(&TuneRow, &mut Text, &mut TextColor), Without<TuneSummary>>,
+    mut summary: Query<(&mut Text, &mut TextColor), With<TuneSummary>>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) || bindings.consume(&mut keyboard, InputAction::MenuConfirm) {
+        next_state.set(nav.back(GameState::DifficultySelect));
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        cursor.index = (cursor.index + TUNE_ROWS - 1) % TUNE_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        cursor.index = (cursor.index + 1) % TUNE_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        settings.custom_tuning.adjust(cursor.index, TUNE_STEP);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        settings.custom_tuning.adjust(cursor.index, -TUNE_STEP);
+    }
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        settings.custom_tuning = CustomTuning::default();
+    }
+
+    let tuning = settings.custom_tuning.apply(difficulty_tuning(settings.selected_difficulty, &config));
+    for (row, mut text, mut color) in rows.iter_mut() {
+        text.0 = tune_row_text(row.0, &settings.custom_tuning, &tuning);
+        color.0 = if row.0 == cursor.index {
+            AQUAMARINE.into()
+        } else {
+            Color::srgb(1.0, 0.992, 0.816)
+        };
+    }
+    for (mut text, mut color) in summary.iter_mut() {
+        text.0 = tune_summary_text(&settings, &tuning);
+        color.0 = tune_summary_color(&tuning);
+    }
+}
+
+// Theme Select UI - UPDATED with background and font
+fn setup_theme_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, fonts: Res<UiFonts>, window_query: Query<&Window, With<PrimaryWindow>>) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    // Add background image
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        ThemeSelectMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        ThemeSelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("THEME"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Classic [Original Look]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("High Contrast [Enhanced Visibility]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Minimal [Basic]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Select [1/2/3]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn theme_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::Options));
+        return;
+    }
+    
+    for (digit, theme) in [
+        (1, Theme::Classic),
+        (2, Theme::HighContrast),
+        (3, Theme::Minimal),
+    ] {
+        if digit_just_pressed(&keyboard, digit) {
+            settings.selected_theme = theme;
+            next_state.set(GameState::Modifiers);
+            return;
+        }
+    }
+}
+
+fn setup_skin_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, fonts: Res<UiFonts>, window_query: Query<&Window, With<PrimaryWindow>>) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    // Add background image
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        SkinSelectMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        SkinSelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("BIRD SKIN"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Classic [Yellow Bird]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Red [Red Bird]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Blue [Blue Bird]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Green [Green Bird]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+        
+        parent.spawn((
+            Text::new("Select [1/2/3/4]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn skin_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::Options));
+        return;
+    }
+    
+    for (digit, skin) in [
+        (1, Skin::Classic),
+        (2, Skin::Red),
+        (3, Skin::Blue),
+        (4, Skin::Green),
+    ] {
+        if digit_just_pressed(&keyboard, digit) {
+            settings.selected_skin = skin;
+            next_state.set(GameState::Options);
+            return;
+        }
+    }
+}
+
+// Accessibility UI - toggles apply in place and the screen is rebuilt
+fn setup_accessibility_ui(mut commands: Commands, asset_server: Res<AssetServer>, fonts: Res<UiFonts>, window_query: Query<&Window, With<PrimaryWindow>>, settings: Res<GameSettings>) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        AccessibilityMarker,
+    ));
+
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    let floor_warning = match settings.floor_warning {
+        None => format!("Auto ({})", on_off(settings.floor_warning_enabled())),
+        Some(enabled) => on_off(enabled).to_string(),
+    };
+    let entries = [
+        format!("[1] Photosensitive Safe:  {}", on_off(settings.photosensitive_safe)),
+        format!("[2] Floor Warning:  {}", floor_warning),
+        format!("[3] Floor Warning Sound:  {}", on_off(settings.floor_warning_sound)),
+        format!("[4] Gap Tone (pitch follows next gap):  {}", on_off(settings.gap_tone)),
+        format!("[5] Reduce Motion:  {}", on_off(settings.reduce_motion)),
+        format!("[6] Blind Run (audio cues, pitch = gap height, pan = distance):  {}", on_off(settings.blind_run)),
+        format!("[7] Gap Telegraph (marks the next gap early, runs are not ranked):  {}", on_off(settings.gap_telegraph)),
+    ];
+
+    commands.spawn((
+        menu_root_node(),
+        AccessibilityMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("ACCESSIBILITY"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        for entry in entries {
+            parent.spawn((
+                Text::new(entry),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new("Toggle [1-6]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn accessibility_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut flag: ResMut<OptionsChanged>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::Options));
+        return;
+    }
+
+    if digit_just_pressed(&keyboard, 1) {
+        settings.photosensitive_safe = !settings.photosensitive_safe;
+        flag.changed = true;
+    }
+
+    // Floor warning cycles Auto -> On -> Off
+    if digit_just_pressed(&keyboard, 2) {
+        settings.floor_warning = match settings.floor_warning {
+            None => Some(true),
+            Some(true) => Some(false),
+            Some(false) => None,
+        };
+        flag.changed = true;
+    }
+
+    if digit_just_pressed(&keyboard, 3) {
+        settings.floor_warning_sound = !settings.floor_warning_sound;
+        flag.changed = true;
+    }
+
+    if digit_just_pressed(&keyboard, 4) {
+        settings.gap_tone = !settings.gap_tone;
+        flag.changed = true;
+    }
+
+    if digit_just_pressed(&keyboard, 5) {
+        settings.reduce_motion = !settings.reduce_motion;
+        flag.changed = true;
+    }
+
+    if digit_just_pressed(&keyboard, 6) {
+        settings.blind_run = !settings.blind_run;
+        flag.changed = true;
+    }
+
+    if digit_just_pressed(&keyboard, 7) {
+        settings.gap_telegraph = !settings.gap_telegraph;
+        flag.changed = true;
+    }
+}
+
+fn refresh_accessibility_ui(
+    mut commands: Commands,
+    query: Query<Entity, With<AccessibilityMarker>>,
+    mut flag: ResMut<OptionsChanged>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+) {
+    if flag.changed {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+
+        setup_accessibility_ui(commands, asset_server, fonts, window_query, settings);
+
+        flag.changed = false;
+    }
+}
+
+// The trainer keeps Normal's feel and only overrides the gap
+fn run_tuning(settings: &GameSettings, trainer: &TrainerTuning, config: &GameConfig) -> DifficultyTuning {
+    if settings.selected_mode == GameMode::Trainer {
+        DifficultyTuning {
+            gap_size: trainer.gap_size,
+            ..difficulty_tuning(Difficulty::Normal, config)
+        }
+    } else {
+        settings.custom_tuning.apply(difficulty_tuning(settings.selected_difficulty, config))
+    }
+}
+
+fn difficulty_tuning(difficulty: Difficulty, config: &GameConfig) -> DifficultyTuning {
+    match difficulty {
+        Difficulty::Easy => DifficultyTuning {
+            gap_size: config.gap_size * 1.3,
+            scroll_speed: config.scroll_speed * 0.85,
+            gravity_mult: config.gravity_mult * 0.75,
+            flap_mult: config.flap_mult * 1.2,
+            vertical_offset: config.vertical_offset * 0.7,
+            spacing: OBSTACLE_SPACING * 1.2,
+            flap: config.flap.style(difficulty),
+        },
+        Difficulty::Normal => DifficultyTuning {
+            gap_size: config.gap_size,
+            scroll_speed: config.scroll_speed,
+            gravity_mult: config.gravity_mult,
+            flap_mult: config.flap_mult,
+            vertical_offset: config.vertical_offset,
+            spacing: OBSTACLE_SPACING,
+            flap: config.flap.style(difficulty),
+        },
+        Difficulty::Hard => DifficultyTuning {
+            gap_size: config.gap_size * 0.75,
+            scroll_speed: config.scroll_speed * 1.25,
+            gravity_mult: config.gravity_mult * 1.3,
+            flap_mult: config.flap_mult * 1.05,
+            vertical_offset: config.vertical_offset * 1.2,
+            spacing: OBSTACLE_SPACING * 0.85,
+            flap: config.flap.style(difficulty),
+        },
+        // The stronger flap keeps the widest consecutive-gap climb within reach
+        Difficulty::Insane => DifficultyTuning {
+            gap_size: config.gap_size * 0.6,
+            scroll_speed: config.scroll_speed * 1.45,
+            gravity_mult: config.gravity_mult * 1.4,
+            flap_mult: config.flap_mult * 1.1,
+            vertical_offset: config.vertical_offset * 1.35,
+            spacing: OBSTACLE_SPACING * 0.85,
+            flap: config.flap.style(difficulty),
+        },
+    }
+}
+
+// Balance guard for modded constants and config values. The bird's center is safe while it
+// stays within gap_size of the gap center, so a gap is a band of 2 * gap_size px. Both checks
+// are lower bounds for a normal-sized bird; passing them doesn't promise a comfortable course
+fn course_problem(tuning: &DifficultyTuning) -> Option<String> {
+    let gravity = GRAVITY * tuning.gravity_mult;
+    let flap = FLAP_FORCE * tuning.flap_mult;
+    let band = 2. * tuning.gap_size * PIXEL_RATIO;
+
+    // Crossing a pipe's width takes one arc centered on its apex when that is shorter than a
+    // full flap arc; longer crossings need full arcs (flap, rise, fall back), F^2 / 2g tall
+    let crossing = OBSTACLE_WIDTH * PIXEL_RATIO / tuning.scroll_speed;
+    let span = if gravity * crossing / 2. <= flap {
+        gravity * crossing * crossing / 8.
+    } else {
+        flap * flap / (2. * gravity)
+    };
+    if span >= band {
+        return Some(format!("crossing a pipe takes {:.0}px of height but the gap is {:.0}px", span, band));
+    }
+
+    let shift = 2. * tuning.vertical_offset * PIXEL_RATIO;
+    let reach = max_gap_shift(tuning, tuning.gap_size);
+    if shift > reach {
+        return Some(format!("gaps can rise {:.0}px between pipes but the bird climbs at most {:.0}px", shift - band, reach - band));
+    }
+    None
+}
+
+// Velocity never exceeds the flap's, so that bounds the climb between neighbouring gaps;
+// the bird may leave one band at its top and enter the next at its bottom
+fn max_gap_shift(tuning: &DifficultyTuning, gap_size: f32) -> f32 {
+    let between = tuning.spacing * PIXEL_RATIO / tuning.scroll_speed;
+    FLAP_FORCE * tuning.flap_mult * between + 2. * gap_size * PIXEL_RATIO
+}
+
+fn is_course_passable(tuning: &DifficultyTuning) -> bool {
+    course_problem(tuning).is_none()
+}
+
+// One line per difficulty the config makes impassable, at full gaps and once Endless has
+// shrunk them to the floor
+fn config_balance_warnings(config: &GameConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for difficulty in DIFFICULTIES {
+        let tuning = difficulty_tuning(difficulty, config);
+        if let Some(problem) = course_problem(&tuning) {
+            warnings.push(format!("{:?} is not passable: {}", difficulty, problem));
+        } else if config.gap_shrink.enabled
+            && let Some(problem) = course_problem(&DifficultyTuning { gap_size: tuning.gap_size * config.gap_shrink.floor, ..tuning })
+        {
+            warnings.push(format!("{:?} is not passable once gaps shrink to {:.0}%: {}", difficulty, config.gap_shrink.floor * 100.0, problem));
+        }
+    }
+    warnings
+}
+
+fn setup_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    tournament: Option<Res<TournamentState>>,
+    trainer: Res<TrainerTuning>,
+    mut pitches: ResMut<Assets<Pitch>>,
+    audio: Res<AudioSettings>,
+    config: Res<GameConfig>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+    saves: Res<SaveWriter>,
+) {
+    // Load core assets and cache the playfield size used by obstacle wrap logic;
+    // fill_window keeps the old behavior where the world is as big as the window
+    let window = window_query.single().expect("Missing primary window");
+    let pipe_image = sprite_image(&asset_server, window.scale_factor(), "pipe.png");
+    let (window_width, window_height) = if settings.fill_window {
+        (window.width(), window.height())
+    } else {
+        (PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT)
+    };
+    commands.insert_resource(GameManager {
+        pipe_image: pipe_image.clone(),
+        window_dimensions: Vec2::new(window_width, window_height),
+    });
+    
+    // Cosmetics come from the slot's profile
+    let profile = settings.current_slot.and_then(|slot| saves.load(slot)).map(|save| save.profile);
+    let (trail, flap_sound) = profile.map_or((TrailColor::Off, FlapSound::Classic), |profile| (profile.trail, profile.flap_sound));
+    commands.insert_resource(BirdTrail {
+        color: trail.color(),
+        since_dot: 0.0,
+    });
+
+    // Load sound effects, preferring the theme's own variants
+    let theme = settings.selected_theme;
+    commands.insert_resource(SoundEffects {
+        flap: theme.sound(&asset_server, flap_sound.file()),
+        point: theme.sound(&asset_server, "point.ogg"),
+        die: theme.sound(&asset_server, "die.ogg"),
+        swoosh: theme.sound(&asset_server, "swoosh.ogg"),
+        jingle: JINGLE_NOTES
+            .iter()
+            .map(|&(_, frequency)| pitches.add(Pitch::new(frequency, Duration::from_millis(JINGLE_NOTE_MS))))
+            .collect(),
+    });
+    
+    let tuning = run_tuning(&settings, &trainer, &config);
+    commands.insert_resource(tuning);
+
+    // Modifiers: fresh clocks each run, and the mirror flips the world camera (UI stays readable)
+    commands.insert_resource(ModifierState { elapsed: 0.0, since_flap: FLAP_COOLDOWN, flip_pickup_score: 0 });
+    if settings.modifiers.mirror {
+        for (mut camera, _) in camera_query.iter_mut() {
+            camera.scale.x = -1.0;
+        }
+    }
+
+    // HUD colors are fixed for the whole run
+    let palette = settings.selected_theme.palette();
+    commands.insert_resource(palette);
+    commands.insert_resource(ClearColor(palette.background));
+
+    // Zoom only scales the view; walls, floor and pipe wrap stay in playfield coordinates
+    for (_, mut projection) in camera_query.iter_mut() {
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = 1.0 / settings.camera_zoom;
+        }
+    }
+
+    // Letterbox: the camera always shows at least the playfield, and Background bars hide the rest.
+    // Zoomed out, the right bar moves to the view's edge so the extra room shows incoming pipes
+    if !settings.fill_window {
+        for (_, mut projection) in camera_query.iter_mut() {
+            if let Projection::Orthographic(ortho) = projection.as_mut() {
+                ortho.scaling_mode = ScalingMode::AutoMin { min_width: PLAYFIELD_WIDTH, min_height: PLAYFIELD_HEIGHT };
+            }
+        }
+        let bar = Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT) * 4.;
+        let reveal = PLAYFIELD_WIDTH / 2. * (1. / settings.camera_zoom.min(1.) - 1.);
+        for offset in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+            let mut center = offset * (Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT) + bar) / 2.;
+            if offset == Vec2::X {
+                center.x += reveal;
+            }
+            commands.spawn((
+                Sprite::from_color(palette.background, bar),
+                Transform::from_translation(center.extend(LETTERBOX_Z)),
+                Background,
+                RunScoped,
+            ));
+        }
+    }
+
+    commands.insert_resource(Score { custom_config: !config.is_stock(), ..default() });
+
+    // Tournament rounds are short timed runs; the state survives between rounds
+    let round = tournament.as_ref().map_or(0, |t| t.scores.len());
+    if settings.selected_mode == GameMode::Tournament && tournament.is_none() {
+        commands.insert_resource(TournamentState::default());
+    }
+
+    // Time Attack / Tournament setup: start the countdown and show UI
+    let time_limit = match settings.selected_mode {
+        GameMode::TimeAttack => Some(TIME_ATTACK_DURATION),
+        GameMode::Tournament => Some(TOURNAMENT_ROUND_DURATION),
+        _ => None,
+    };
+    if let Some(time_limit) = time_limit {
+        commands.insert_resource(TimeAttackState { remaining: time_limit, duration: time_limit, credited_score: 0 });
+
+        commands.spawn((
+            Text::new(format!("Time: {:.0}", time_limit)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(22.0),
+                ..default()
+            },
+            TextColor(time_color(1.0)),
+            palette.text_shadow(),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(15.0),
+                left: Val::Percent(45.0),
+                ..default()
+            },
+            TimeDisplay,
+            RunScoped,
+        ));
+
+        if settings.selected_mode == GameMode::TimeAttack && settings.overtime {
+            commands.spawn((
+                Text::new(""),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(18.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                palette.text_shadow(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(42.0),
+                    left: Val::Percent(47.0),
+                    ..default()
+                },
+                OvertimeToast { remaining: 0.0 },
+                RunScoped,
+            ));
+        }
+
+        // Confetti for surviving the whole clock, kept hidden under reduce motion
+        if settings.selected_mode == GameMode::TimeAttack {
+            let colors = [palette.accent, palette.hud_text, Color::srgb(1.0, 0.4, 0.5), Color::srgb(0.4, 0.7, 1.0)];
+            for i in 0..CONFETTI_COUNT {
+                commands.spawn((
+                    Sprite::from_color(colors[i % colors.len()], Vec2::new(8., 4.)),
+                    Transform::from_xyz(0., 0., 30.),
+                    Visibility::Hidden,
+                    Confetti { velocity: Vec2::ZERO, spin: 0.0 },
+                    RunScoped,
+                ));
+            }
+        }
+    }
+
+    // Level setup: load the chosen file; a broken level falls back to a random course
+    let level = match (settings.selected_mode, &settings.selected_level) {
+        (GameMode::Level, Some(name)) => load_level(name)
+            .map_err(|e| eprintln!("Failed to load level {}: {}", name, e))
+            .ok(),
+        _ => None,
+    };
+    if let Some(def) = level.as_ref() {
+        commands.spawn((
+            Text::new(format!("{}: 0/{}", def.name, def.pipes.len())),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(22.0),
+                ..default()
+            },
+            TextColor(palette.hud_text),
+            palette.text_shadow(),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(15.0),
+                left: Val::Percent(40.0),
+                ..default()
+            },
+            LevelDisplay,
+            RunScoped,
+        ));
+    }
+
+    // Endless runs mark the pair that would tie the profile's Endless best at this difficulty
+    // (custom runs keep their own best)
+    let record_key = if settings.is_custom() {
+        custom_key(GameMode::Endless, settings.selected_difficulty)
+    } else {
+        mastery_key(GameMode::Endless, settings.selected_difficulty)
+    };
+    let record = settings
+        .current_slot
+        .filter(|_| settings.record_marker && settings.selected_mode == GameMode::Endless)
+        .and_then(|slot| saves.load(slot))
+        .and_then(|save| save.profile.mode_bests.get(&record_key).copied())
+        .unwrap_or(0);
+    if record > 0 {
+        commands.spawn((
+            Sprite::from_color(palette.accent.with_alpha(0.5), Vec2::new(3.0, 1.0)),
+            Transform::from_xyz(0.0, 0.0, 0.5),
+            Visibility::Hidden,
+            RecordMarker { target: record },
+            RunScoped,
+        ));
+    }
+
+    // A diamond half past the view's edge reads as an arrow pointing at the incoming gap
+    if settings.gap_telegraph {
+        commands.spawn((
+            Sprite::from_color(palette.accent, Vec2::splat(GAP_TELEGRAPH_SIZE)),
+            Transform::from_xyz(0.0, 0.0, 1.5).with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            Visibility::Hidden,
+            GapTelegraph,
+            RunScoped,
+        ));
+    }
+
+    if settings.modifiers.gravity_flip {
+        commands.spawn((
+            Text::new(""),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(22.0),
+                ..default()
+            },
+            TextColor(palette.accent),
+            palette.text_shadow(),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(90.0),
+                left: Val::Percent(40.0),
+                ..default()
+            },
+            FlipIndicator,
+            RunScoped,
+        ));
+    }
+
+    let shrink_floor = Some(config.gap_shrink.floor).filter(|_| config.gap_shrink.enabled && settings.selected_mode == GameMode::Endless);
+    let mut progress = GapProgress::new(shrink_floor);
+    if shrink_floor.is_some() {
+        commands.spawn((
+            Text::new(""),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(22.0),
+                ..default()
+            },
+            TextColor(palette.accent),
+            palette.text_shadow(),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.0),
+                left: Val::Percent(40.0),
+                ..default()
+            },
+            GapToast { remaining: 0.0 },
+            RunScoped,
+        ));
+    }
+
+    if settings.selected_mode == GameMode::Trainer {
+        commands.spawn((
+            Text::new(format!("Trainer - Gap: {:.2}", trainer.gap_size)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(22.0),
+                ..default()
+            },
+            TextColor(palette.hud_text),
+            palette.text_shadow(),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(15.0),
+                left: Val::Percent(40.0),
+                ..default()
+            },
+            TrainerDisplay,
+            RunScoped,
+        ));
+    }
+
+    // Checkpoints setup: initialize checkpoint state and show UI
+    if settings.selected_mode == GameMode::Checkpoints {
+        let checkpoints_state = CheckpointsState::new(settings.selected_difficulty);
+        let target = checkpoints_state.target_score();
+        commands.insert_resource(checkpoints_state);
+
+        commands.spawn((
+            Text::new(format!("Checkpoint: 0/{}", target)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(22.0),
+                ..default()
+            },
+            TextColor(palette.hud_text),
+            palette.text_shadow(),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(15.0),
+                left: Val::Percent(45.0),
+                ..default()
+            },
+            CheckpointDisplay,
+            RunScoped,
+        ));
+    }
+    
+    // Apply theme background; Classic uses a full-screen texture instead of a flat color
+    match settings.selected_theme {
+        Theme::Classic => {
+            commands.spawn((
+                Sprite {
+                    image: asset_server.load("Background2.png"),
+                    custom_size: Some(Vec2::new(window_width, window_height)),
+                    ..Default::default()
+                },
+                Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+                Background,
+                RunScoped,
+            ));
+        }
+        Theme::HighContrast => {
+            commands.spawn((
+                Sprite {
+                    image: asset_server.load("Background1.png"),
+                    custom_size: Some(Vec2::new(window_width, window_height)),
+                    ..Default::default()
+                },
+                Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+                Background,
+                RunScoped,
+            ));
+        }
+        Theme::Minimal => {}
+    }
+
+    // Spawn bird with selected skin
+    let bird_sprite = match settings.selected_skin {
+        Skin::Classic => "bird.png",
+        Skin::Red => "red_bird.png",
+        Skin::Blue => "blue_bird.png",
+        Skin::Green => "green_bird.png",
+    };
+
+    commands.spawn((
+        art_sprite(sprite_image(&asset_server, window.scale_factor(), bird_sprite), BIRD_ART_SIZE),
+        Transform::from_xyz(config.bird_x(window_width), 0., 0.).with_scale(Vec3::splat(PIXEL_RATIO * settings.bird_size.scale())),
+        Bird { velocity: 0., tilt: 0., flap_boost: 0., flap_ticks: 0 },
+        RunScoped,
+    ));
+
+    // Best Score UI - Top Right
+    commands.spawn((
+        Text::new("Best: 0"),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(20.0),
+            ..default()
+        },
+        TextColor(palette.hud_text),
+        palette.text_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(15.0),
+            right: Val::Px(15.0),
+            ..default()
+        },
+        BestScoreDisplay,
+        RunScoped,
+    ));
+
+    // Current Score UI - Top Left
+    commands.spawn((
+        Text::new("Score: 0"),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(20.0),
+            ..default()
+        },
+        TextColor(palette.hud_text),
+        palette.text_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(15.0),
+            left: Val::Px(15.0),
+            ..default()
+        },
+        ScoreDisplay,
+        RunScoped,
+    ));
+
+    // Gap tone - Quiet looping sine, pitch is adjusted while playing
+    if settings.blind_run {
+        commands.spawn((
+            AudioPlayer(pitches.add(Pitch::new(GAP_TONE_FREQUENCY, std::time::Duration::from_millis(250)))),
+            PlaybackSettings {
+                volume: audio.sfx(BLIND_TONE_VOLUME),
+                ..PlaybackSettings::LOOP
+            }
+            .with_spatial(true)
+            .with_spatial_scale(bevy::audio::SpatialScale::new(BLIND_SPATIAL_SCALE)),
+            Transform::from_xyz(BLIND_PAN_WIDTH, 0.0, 0.0),
+            GapTone {
+                update_timer: Timer::from_seconds(GAP_TONE_UPDATE_INTERVAL, TimerMode::Repeating),
+            },
+            BlindTone,
+            RunScoped,
+        ));
+    } else if settings.gap_tone {
+        commands.spawn((
+            AudioPlayer(pitches.add(Pitch::new(GAP_TONE_FREQUENCY, std::time::Duration::from_millis(250)))),
+            PlaybackSettings {
+                volume: audio.sfx(GAP_TONE_VOLUME),
+                ..PlaybackSettings::LOOP
+            },
+            GapTone {
+                update_timer: Timer::from_seconds(GAP_TONE_UPDATE_INTERVAL, TimerMode::Repeating),
+            },
+            RunScoped,
+        ));
+    }
+
+    // Floor warning - Bottom edge, transparent until the bird gets close
+    if settings.floor_warning_enabled() {
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Px(FLOOR_WARNING_DISTANCE),
+                ..default()
+            },
+            BackgroundGradient::default(),
+            FloorWarning { tick_cooldown: 0.0 },
+            RunScoped,
+        ));
+    }
+
+    // Combo meter - Below score, refills on each pipe and drains over time
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(45.0),
+            left: Val::Px(15.0),
+            width: Val::Px(COMBO_BAR_WIDTH),
+            height: Val::Px(6.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.4)),
+        ComboBarFrame,
+        RunScoped,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Node {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(palette.accent),
+            ComboBar,
+        ));
+        parent.spawn((
+            Text::new(""),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(16.0),
+                ..default()
+            },
+            TextColor(palette.hud_text),
+            palette.text_shadow(),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(COMBO_BAR_WIDTH + 8.0),
+                top: Val::Px(-6.0),
+                ..default()
+            },
+            ComboDisplay,
+        ));
+    });
+
+    // Pasted run codes pin the seed; otherwise every run gets a fresh one
+    let seed = settings.seed.map_or_else(rand::random, |seed| seed.wrapping_add(round as u64));
+    let mut course = CourseRng::new(seed);
+    match level {
+        // Level pipes are streamed in by update_obstacles as the world scrolls
+        Some(def) => commands.insert_resource(LevelRun { def, next_pipe: 0, coins: 0, elapsed: 0.0 }),
+        None => {
+            progress.last_offset =
+                spawn_obstacles(&mut commands, &mut course.rng, window_width, &pipe_image, tuning, settings.gauntlet_start);
+        }
+    }
+    commands.insert_resource(progress);
+    commands.insert_resource(course);
+}
+
+// Frame delta clamped so a long stall can't tunnel the bird through pipes or the floor
+fn physics_delta(time: &Time) -> f32 {
+    time.delta_secs().min(MAX_PHYSICS_DELTA)
+}
+
+fn get_centered_pipe_position(gap_size: f32) -> f32 {
+    (OBSTACLE_HEIGHT / 2. + gap_size) * PIXEL_RATIO
+}
+
+fn spawn_obstacles(
+    commands: &mut Commands,
+    rand: &mut StdRng,
+    window_width: f32,
+    pipe_image: &Handle<Image>,
+    tuning: DifficultyTuning,
+    gauntlet: bool,
+) -> f32 {
+    // Spawn paired top/bottom pipes spaced across the screen; the rightmost pair's offset
+    // is returned so the first recycled gap leans toward it
+    let mut y_offset = 0.0;
+    for i in 0..OBSTACLE_AMOUNT {
+        y_offset = generate_offset(rand, tuning.vertical_offset);
+        let x_pos = window_width / 2. + (tuning.spacing * PIXEL_RATIO * start_pair_offset(i as usize, gauntlet));
+        spawn_obstacle_pair(x_pos, y_offset, tuning.gap_size, i as u32 + 1, commands, pipe_image);
+    }
+    y_offset
+}
+
+// Distance of the i-th starting pair from the first, in spacings
+fn start_pair_offset(i: usize, gauntlet: bool) -> f32 {
+    if gauntlet {
+        GAUNTLET_SPACING[..i].iter().sum()
+    } else {
+        i as f32
+    }
+}
+
+fn spawn_obstacle_pair(
+    x_pos: f32,
+    y_offset: f32,
+    gap_size: f32,
+    index: u32,
+    commands: &mut Commands,
+    pipe_image: &Handle<Image>,
+) {
+    spawn_obstacle(
+        Vec3::X * x_pos + Vec3::Y * (get_centered_pipe_position(gap_size) + y_offset),
+        1.,
+        gap_size,
+        index,
+        commands,
+        pipe_image,
+    );
+
+    spawn_obstacle(
+        Vec3::X * x_pos + Vec3::Y * (-get_centered_pipe_position(gap_size) + y_offset),
+        -1.,
+        gap_size,
+        index,
+        commands,
+        pipe_image,
+    );
+}
+
+fn spawn_obstacle(
+    translation: Vec3,
+    pipe_direction: f32,
+    gap_size: f32,
+    index: u32,
+    commands: &mut Commands,
+    pipe_image: &Handle<Image>,
+) {
+    commands.spawn((
+        art_sprite(pipe_image.clone(), PIPE_ART_SIZE),
+        Transform::from_translation(translation).with_scale(Vec3::new(
+            PIXEL_RATIO,
+            PIXEL_RATIO * -pipe_direction,
+            PIXEL_RATIO,
+        )),
+        Obstacle { 
+            pipe_direction,
+            scored: false,
+            gap_size,
+            index,
+            whooshed: false,
+            parked: false,
+        },
+        RunScoped,
+    ));
+}
+
+// Mean of two uniform samples: a triangular distribution that favors the middle,
+// so extreme-to-extreme jumps are rare but still possible
+fn generate_offset(rand: &mut StdRng, vertical_offset: f32) -> f32 {
+    let a = rand.gen_range(-vertical_offset..vertical_offset);
+    let b = rand.gen_range(-vertical_offset..vertical_offset);
+    (a + b) / 2. * PIXEL_RATIO
+}
+
+fn update_obstacles(
+    mut commands: Commands,
+    step: Res<SimStep>,
+    game_manager: Res<GameManager>,
+    tuning: Res<DifficultyTuning>,
+    settings: Res<GameSettings>,
+    mut course: ResMut<CourseRng>,
+    level: Option<ResMut<LevelRun>>,
+    mut progress: ResMut<GapProgress>,
+    mut obstacle_query: Query<(Entity, &mut Obstacle, &mut Transform)>,
+    protected_bird_query: Query<&Transform, (With<Bird>, With<Invulnerable>, Without<Obstacle>)>,
+    config: Res<GameConfig>,
+    fonts: Res<UiFonts>,
+) {
+    // While the bird is protected after a respawn, keep recycled pipes out of its way
+    let protected_x = protected_bird_query.single().ok().map(|bird| bird.translation.x);
+
+    // Levels always stream, handing out their pipes in order until none are left
+    if let Some(mut level) = level {
+        let anchor = scroll_stream(&mut commands, step.dt, &game_manager, *tuning, &mut obstacle_query);
+        let next = level.def.pipes.get(level.next_pipe).copied();
+        if let (Some(anchor), Some(pipe)) = (anchor, next) {
+            let x_pos = anchor + pipe.spacing * PIXEL_RATIO;
+            let y_offset = pipe.y_offset * PIXEL_RATIO;
+            spawn_obstacle_pair(x_pos, y_offset, pipe.gap_size, level.next_pipe as u32 + 1, &mut commands, &game_manager.pipe_image);
+            if pipe.coin {
+                spawn_coin(Vec3::new(x_pos, y_offset, 1.), &mut commands);
+            }
+            level.next_pipe += 1;
+        }
+        return;
+    }
+
+    if settings.spawn_strategy == SpawnStrategy::Stream {
+        stream_obstacles(
+            &mut commands,
+            step.dt,
+            &game_manager,
+            *tuning,
+            &mut course.rng,
+            &mut progress,
+            &mut obstacle_query,
+            protected_x,
+        );
+        return;
+    }
+
+    // Rest stops: a pair reaching the stop is parked for a cycle instead of taking the next
+    // index; at most one is parked at a time, so two stops can never run together
+    let rest_interval = match (settings.selected_mode, settings.selected_difficulty) {
+        (GameMode::Endless, difficulty) if difficulty != Difficulty::Insane => config.rest_stop_interval,
+        _ => 0,
+    };
+    let mut next_index = obstacle_query.iter().map(|(_, obstacle, _)| obstacle.index).max().unwrap_or(0) + 1;
+    let mut resting = obstacle_query.iter().any(|(_, obstacle, _)| obstacle.parked);
+    // Old index -> new one (None = parked), its x and its gap offset, so both pipes of a
+    // pair get the same decision
+    let mut placed: Vec<(u32, Option<u32>, f32, f32)> = Vec::new();
+
+    let scroll = step.dt * tuning.scroll_speed;
+    let spacing = tuning.spacing * PIXEL_RATIO;
+    let exits = |x: f32| x + OBSTACLE_WIDTH * PIXEL_RATIO / 2. < -game_manager.window_dimensions.x / 2.;
+    // Rightmost pair that stays on the course this step, moved along as pairs are placed
+    let mut rightmost = obstacle_query
+        .iter()
+        .map(|(_, _, transform)| transform.translation.x - scroll)
+        .filter(|x| !exits(*x))
+        .fold(f32::MIN, f32::max);
+
+    // Scroll pipes and recycle them when they exit left
+    for (_, mut obstacle, mut transform) in obstacle_query.iter_mut() {
+        transform.translation.x -= scroll;
+
+        if exits(transform.translation.x) {
+            // Shrunk gaps only reach pipes as they're recycled, never ones already on screen
+            obstacle.gap_size = progress.gap_size(&tuning);
+            let (new_index, y_offset) = match placed.iter().find(|(old, _, _, _)| *old == obstacle.index) {
+                Some(&(_, new_index, x_pos, y_offset)) => {
+                    transform.translation.x = x_pos;
+                    (new_index, y_offset)
+                }
+                None => {
+                    // A gauntlet start packs the pairs tighter than OBSTACLE_AMOUNT spacings and a
+                    // respawn shift pushes part of the ring on, so a recycled pair never lands
+                    // closer than one spacing past the rightmost one
+                    let wrapped = transform.translation.x + OBSTACLE_AMOUNT as f32 * spacing;
+                    let mut x_pos = if settings.gauntlet_start || wrapped < rightmost + spacing - 1. {
+                        rightmost + spacing
+                    } else {
+                        wrapped
+                    };
+                    if let Some(bird_x) = protected_x
+                        && (x_pos - bird_x).abs() < RESPAWN_PROTECTION_RADIUS
+                    {
+                        x_pos += spacing;
+                    }
+                    rightmost = rightmost.max(x_pos);
+                    transform.translation.x = x_pos;
+                    let stop = rest_interval > 0
+                        && !resting
+                        && (settings.start_score + next_index - 1).is_multiple_of(rest_interval);
+                    let new_index = (!stop).then_some(next_index);
+                    if stop {
+                        resting = true;
+                        commands.spawn((
+                            Text2d::new(""),
+                            TextFont {
+                                font: fonts.body.clone(),
+                                font_size: fonts.scaled(24.0),
+                                ..default()
+                            },
+                            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                            Transform::from_xyz(transform.translation.x, 0., REST_STOP_BANNER_Z),
+                            RestStopBanner,
+                            RunScoped,
+                        ));
+                    } else {
+                        next_index += 1;
+                    }
+                    // One draw per pair; a parked pair has no gap to place
+                    let y_offset = if stop { 0.0 } else { progress.next_offset(&mut course.rng, &tuning) };
+                    placed.push((obstacle.index, new_index, x_pos, y_offset));
+                    (new_index, y_offset)
+                }
+            };
+            obstacle.parked = new_index.is_none();
+            obstacle.index = new_index.unwrap_or(0);
+            if obstacle.parked {
+                // Out of view and already counted, so nothing collides with it or scores it
+                obstacle.whooshed = true;
+                obstacle.scored = true;
+                transform.translation.y = obstacle.pipe_direction * game_manager.window_dimensions.y * 2.;
+                continue;
+            }
+            obstacle.whooshed = false;
+            transform.translation.y =
+                get_centered_pipe_position(obstacle.gap_size) * obstacle.pipe_direction + y_offset;
+            obstacle.scored = false;
+        }
+    }
+}
+
+// Keeps the banner over the empty slot and shows the score reached so far
+fn update_rest_stop_banner(
+    mut commands: Commands,
+    score: Res<Score>,
+    game_manager: Res<GameManager>,
+    mut banner_query: Query<(Entity, &mut Text2d, &mut Transform, Ref<RestStopBanner>)>,
+    obstacle_query: Query<(&Obstacle, &Transform), Without<RestStopBanner>>,
+    mut stats: ResMut<HudStats>,
+    mut shown: Local<Option<u32>>,
+) {
+    let parked_x = obstacle_query.iter().find(|(obstacle, _)| obstacle.parked).map(|(_, transform)| transform.translation.x);
+    for (entity, mut text, mut transform, marker) in banner_query.iter_mut() {
+        match parked_x {
+            Some(x) if x > -game_manager.window_dimensions.x / 2. => {
+                transform.translation.x = x;
+                if marker.is_added() || *shown != Some(score.current) {
+                    text.0 = format!("REST STOP\nScore {}", score.current);
+                    *shown = Some(score.current);
+                    stats.updates += 1;
+                }
+            }
+            _ => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+// Stream strategy: pipes that leave the screen are despawned and new pairs are
+// spawned one spacing past the rightmost pipe as it nears the right edge
+fn stream_obstacles(
+    commands: &mut Commands,
+    dt: f32,
+    game_manager: &GameManager,
+    tuning: DifficultyTuning,
+    rand: &mut StdRng,
+    progress: &mut GapProgress,
+    obstacle_query: &mut Query<(Entity, &mut Obstacle, &mut Transform)>,
+    protected_x: Option<f32>,
+) {
+    let Some(anchor) = scroll_stream(commands, dt, game_manager, tuning, obstacle_query) else {
+        return;
+    };
+
+    let mut x_pos = anchor + tuning.spacing * PIXEL_RATIO;
+    if let Some(bird_x) = protected_x
+        && (x_pos - bird_x).abs() < RESPAWN_PROTECTION_RADIUS
+    {
+        x_pos += tuning.spacing * PIXEL_RATIO;
+    }
+    let y_offset = progress.next_offset(rand, &tuning);
+    let index = obstacle_query.iter().map(|(_, obstacle, _)| obstacle.index).max().unwrap_or(0) + 1;
+    spawn_obstacle_pair(x_pos, y_offset, progress.gap_size(&tuning), index, commands, &game_manager.pipe_image);
+}
+
+// Scrolls streamed pipes and despawns the ones that left the screen. Returns the x
+// to measure the next pair's spacing from once there is room for one
+fn scroll_stream(
+    commands: &mut Commands,
+    dt: f32,
+    game_manager: &GameManager,
+    tuning: DifficultyTuning,
+    obstacle_query: &mut Query<(Entity, &mut Obstacle, &mut Transform)>,
+) -> Option<f32> {
+    let left_edge = -game_manager.window_dimensions.x / 2.;
+    let right_edge = game_manager.window_dimensions.x / 2.;
+    let mut rightmost = f32::MIN;
+    let mut live = 0;
+
+    for (entity, _, mut transform) in obstacle_query.iter_mut() {
+        transform.translation.x -= dt * tuning.scroll_speed;
+
+        if transform.translation.x + OBSTACLE_WIDTH * PIXEL_RATIO / 2. < left_edge {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        live += 1;
+        rightmost = rightmost.max(transform.translation.x);
+    }
+
+    if live + 2 > STREAM_MAX_OBSTACLES || rightmost > right_edge + OBSTACLE_WIDTH * PIXEL_RATIO {
+        return None;
+    }
+    Some(if live == 0 { right_edge } else { rightmost })
+}
+
+fn spawn_coin(translation: Vec3, commands: &mut Commands) {
+    commands.spawn((
+        Sprite::from_color(Color::srgb(1.0, 0.84, 0.0), Vec2::splat(COIN_SIZE * PIXEL_RATIO)),
+        Transform::from_translation(translation),
+        Coin,
+        RunScoped,
+    ));
+}
+
+fn update_bird(
+    mut commands: Commands,
+    mut bird_query: Query<(Entity, &mut Bird, &mut Transform, Has<Invulnerable>, Has<GravityFlip>), Without<Obstacle>>,
+    mut obstacle_query: Query<(&mut Obstacle, &mut Transform, Entity)>,
+    step: Res<SimStep>,
+    game_manager: Res<GameManager>,
+    sound_effects: Res<SoundEffects>,
+    mut score: ResMut<Score>,
+    mut state: ResMut<NextState<GameState>>,
+    settings: Res<GameSettings>,
+    tuning: Res<DifficultyTuning>,
+    checkpoints_state: Option<ResMut<CheckpointsState>>,
+    audio: Res<AudioSettings>,
+    replay: Option<Res<ReplayPlayback>>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut rumble_requests: MessageWriter<GamepadRumbleRequest>,
+    mut saves: ResMut<SaveWriter>,
+) {
+    if let Ok((bird_entity, mut bird, mut transform, invulnerable, flipped)) = bird_query.single_mut() {
+        // Input + physics; gravity_sign is -1 while a gravity flip is active
+        let gravity_sign = if flipped { -1. } else { 1. };
+        score.log.elapsed += step.dt;
+        if step.flap {
+            score.log.flap();
+            audio.rumble(&mut rumble_requests, &gamepads, 0.0, 0.3, FLAP_RUMBLE_MS);
+            match tuning.flap {
+                FlapStyle::Classic => bird.velocity = FLAP_FORCE * tuning.flap_mult * gravity_sign,
+                FlapStyle::Impulse { strength, ticks } => {
+                    bird.flap_boost = FLAP_FORCE * tuning.flap_mult * strength / ticks as f32;
+                    bird.flap_ticks = ticks;
+                }
+            }
+            if !settings.reduce_motion {
+                commands.entity(bird_entity).insert(ScaleAnimation::new(STRETCH_DURATION, STRETCH_AMOUNT));
+            }
+            commands.spawn((
+            AudioPlayer::new(sound_effects.flap.clone()),
+            PlaybackSettings {
+                volume: audio.sfx(0.1),
+                ..PlaybackSettings::DESPAWN
+        },
+            SfxKind::Flap,
+        ));
+        }
+
+        // Impulse flaps never push the bird past the speed a classic flap would give it
+        if bird.flap_ticks > 0 {
+            bird.flap_ticks -= 1;
+            let rising = bird.velocity * gravity_sign;
+            let cap = (FLAP_FORCE * tuning.flap_mult).max(rising);
+            bird.velocity = (rising + bird.flap_boost).min(cap) * gravity_sign;
+        }
+
+        let dt = step.dt;
+        bird.velocity -= dt * GRAVITY * tuning.gravity_mult * gravity_sign;
+        transform.translation.y += bird.velocity * dt;
+
+
+        // Collision and scoring
+        let bird_scale = settings.bird_size.scale();
+        let floor = -game_manager.window_dimensions.y / 2.;
+        let extent = bird_extra_extents(bird_scale).y;
+        // Boundaries are checked along the fall direction, so a gravity flip makes the top lethal
+        let mut fall_y = transform.translation.y * gravity_sign;
+        // Screen wrap trades the floor death for re-entering from the top (and back down)
+        if settings.modifiers.screen_wrap {
+            if fall_y - extent <= floor {
+                fall_y = -floor;
+            } else if fall_y - extent > -floor {
+                fall_y = floor + extent + 1.;
+            }
+            transform.translation.y = fall_y * gravity_sign;
+        }
+        // Collisions resolve before scoring, so a pass on the frame the bird clips a pipe
+        // never counts (whatever the query order); passes from earlier frames are kept
+        let bird_position = transform.translation.truncate();
+        let hit_pipe = obstacle_query
+            .iter()
+            .filter(|_| !invulnerable)
+            .find(|(_, pipe_transform, _)| pipe_collides(bird_position, bird_scale, pipe_transform.translation.truncate()))
+            .map(|(obstacle, pipe_transform, _)| (obstacle.index, pipe_transform.translation.truncate()));
+        let dead = fall_y - extent <= floor || hit_pipe.is_some();
+        if dead {
+            score.log.push(RunEvent::Death { bird: bird_position, velocity: bird.velocity, pipe: hit_pipe });
+        } else {
+            let mut overlap: Option<(u32, f32)> = None;
+            for (mut obstacle, pipe_transform, _) in obstacle_query.iter_mut() {
+                if let Some(clearance) = pipe_clearance(bird_position, bird_scale, pipe_transform.translation.truncate()) {
+                    overlap = Some((obstacle.index, overlap.map_or(clearance, |(_, closest)| closest.min(clearance))));
+                }
+
+                // Soft swoosh once per pair as the bird lines up with a gap it is about to enter
+                let lead = pipe_transform.translation.x - transform.translation.x;
+                if obstacle.pipe_direction == 1.0 && !obstacle.whooshed && lead > 0.0 && lead < WHOOSH_LEAD {
+                    let gap_center = pipe_transform.translation.y - get_centered_pipe_position(obstacle.gap_size);
+                    if (transform.translation.y - gap_center).abs() < obstacle.gap_size * PIXEL_RATIO {
+                        obstacle.whooshed = true;
+                        commands.spawn((
+                            AudioPlayer::new(sound_effects.swoosh.clone()),
+                            PlaybackSettings {
+                                volume: audio.sfx(0.04),
+                                ..PlaybackSettings::DESPAWN
+                            },
+                        ));
+                    }
+                }
+
+                if !obstacle.scored
+                    && transform.translation.x > pipe_transform.translation.x
+                    && obstacle.pipe_direction == 1.0
+                {
+                    score.current += 1;
+                    if score.current > score.best {
+                        score.best = score.current;
+                    }
+                    score.combo += 1;
+                    score.combo_timer = COMBO_WINDOW;
+                    obstacle.scored = true;
+                    let gap_center = pipe_transform.translation.y - get_centered_pipe_position(obstacle.gap_size);
+                    score.precision.record(
+                        (transform.translation.y - gap_center).abs() / (obstacle.gap_size * PIXEL_RATIO),
+                    );
+                    commands.spawn((
+                        AudioPlayer::new(sound_effects.point.clone()),
+                        PlaybackSettings {
+                            volume: audio.sfx(0.1),
+                            ..PlaybackSettings::DESPAWN // Fix for overlapping sounds and volume adjusted
+                        },
+                        SfxKind::Point,
+                    ));
+                }
+            }
+            score.log.thread(overlap.filter(|_| !invulnerable));
+        }
+        
+        if dead {
+            audio.rumble(&mut rumble_requests, &gamepads, 0.8, 0.5, DEATH_RUMBLE_MS);
+            commands.spawn((
+                AudioPlayer::new(sound_effects.die.clone()),
+                PlaybackSettings {
+                    volume: audio.sfx(0.1),
+                    ..PlaybackSettings::DESPAWN
+            },
+                SfxKind::Die,
+            ));
+
+            // Handle Checkpoints mode respawn
+            if settings.selected_mode == GameMode::Checkpoints
+                && let Some(cp_state) = checkpoints_state
+            {
+                // Reset score to last checkpoint and respawn
+                score.current = cp_state.last_checkpoint_score;
+                score.precision.streak = 0;
+                bird.velocity = 0.0;
+                bird.tilt = 0.0;
+                bird.flap_ticks = 0;
+                transform.translation.y = 0.0;
+                clear_respawn_area(transform.translation.x, tuning.spacing, &mut obstacle_query);
+                commands.entity(bird_entity).insert(Invulnerable::after_respawn());
+                if !settings.reduce_motion {
+                    commands.entity(bird_entity).insert(ScaleAnimation::new(SQUASH_DURATION, SQUASH_AMOUNT));
+                }
+                return; // Don't go to game over, just respawn
+            }
+
+            // Tournament rounds are saved once, when the whole tournament ends
+            if settings.selected_mode == GameMode::Tournament {
+                state.set(GameState::RoundSummary);
+                return;
+            }
+
+            save_run_result(&mut saves, &settings, &mut score, replay.is_some());
+            state.set(GameState::GameOver);
+        }
+    }
+}
+
+// Adds a finished run to the slot's profile (replays, trainer, modified, zoomed-out, weekly and
+// custom-config runs are never ranked)
+fn is_ranked(settings: &GameSettings, score: &Score, replay: bool) -> bool {
+    !replay
+        && !score.custom_config
+        && settings.weekly.is_none()
+        && settings.selected_mode != GameMode::Trainer
+        && !settings.modifiers.any()
+        && !settings.is_challenge()
+        && !settings.zoomed_out()
+        && !settings.gap_telegraph
+}
+
+fn save_run_result(saves: &mut SaveWriter, settings: &GameSettings, score: &mut Score, replay: bool) {
+    if let Some(slot_num) = settings.current_slot.filter(|_| is_ranked(settings, score, replay)) {
+        let save_data = saves.load(slot_num);
+        score.grade = Some(grade_run(score, save_data.as_ref().map(|save| &save.profile)));
+        let mut profile = save_data.map(|s| s.profile).unwrap_or_else(|| PlayerProfile {
+            name: format!("Player {}", slot_num),
+            high_score: 0,
+            total_games: 0,
+            average_score: 0.0,
+            longest_survival: 0.0,
+            sessions: 0,
+            best_session: 0,
+            tournament_best: 0,
+            avatar: 0,
+            history: Vec::new(),
+            history_interrupted: Vec::new(),
+            monthly: std::collections::BTreeMap::new(),
+            best_precision: 0.0,
+            mode_bests: std::collections::HashMap::new(),
+            mastered: Vec::new(),
+            unlocked: Vec::new(),
+            best_perfect_streak: 0,
+            death_pipes: Vec::new(),
+            weekly_bests: std::collections::HashMap::new(),
+            coins: 0,
+            owned_cosmetics: Vec::new(),
+            trail: TrailColor::Off,
+            flap_sound: FlapSound::Classic,
+        });
+        
+        profile.total_games += 1;
+        // Custom runs keep their own best and never touch the preset buckets
+        let custom = settings.is_custom();
+        profile.push_history(score.current, false, custom);
+        if score.current > profile.high_score && !custom {
+            profile.high_score = score.current;
+        }
+        if let Some(precision) = score.precision.average() {
+            profile.best_precision = profile.best_precision.max(precision);
+        }
+        profile.best_perfect_streak = profile.best_perfect_streak.max(score.precision.best_streak);
+        profile.longest_survival = profile.longest_survival.max(score.log.elapsed);
+        if let Some((_, RunEvent::Death { .. })) = score.log.events.back() {
+            profile.record_death(score.current);
+        }
+        if custom {
+            let best = profile.mode_bests.entry(custom_key(settings.selected_mode, settings.selected_difficulty)).or_insert(0);
+            *best = (*best).max(score.current);
+        } else {
+            record_mastery(&mut profile, settings.selected_mode, settings.selected_difficulty, score.current);
+            record_unlocks(&mut profile, settings.selected_difficulty, score.current);
+        }
+        profile.average_score = ((profile.average_score * (profile.total_games - 1) as f32) 
+            + score.current as f32) / profile.total_games as f32;
+        
+        let save_slot = SaveSlot {
+            slot_number: slot_num,
+            profile,
+            mode: settings.selected_mode,
+            difficulty: settings.selected_difficulty,
+            theme: settings.selected_theme,
+            skin: settings.selected_skin,
+            score: score.current,
+            survival_time: 0.0,
+            photosensitive_safe: settings.photosensitive_safe,
+            bird_size: settings.bird_size,
+            floor_warning: settings.floor_warning,
+            floor_warning_sound: settings.floor_warning_sound,
+            gap_tone: settings.gap_tone,
+            blind_run: settings.blind_run,
+            spawn_strategy: settings.spawn_strategy,
+            level: settings.selected_level.clone(),
+            reduce_motion: settings.reduce_motion,
+            overtime: settings.overtime,
+            record_marker: settings.record_marker,
+            gap_telegraph: settings.gap_telegraph,
+            fill_window: settings.fill_window,
+            camera_zoom: settings.camera_zoom,
+            custom_tuning: settings.custom_tuning,
+            flags: run_flags(settings),
+            run_name: None,
+        };
+        
+        saves.write(save_slot);
+    }
+}
+
+// Announces an unlock as soon as the score crosses its threshold. A slot that already has
+// a save gets it written at once; a new one receives it with the run result
+fn check_difficulty_unlocks(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    settings: Res<GameSettings>,
+    replay: Option<Res<ReplayPlayback>>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+    mut saves: ResMut<SaveWriter>,
+) {
+    if !score.is_changed() || !is_ranked(&settings, &score, replay.is_some()) || settings.is_custom() {
+        return;
+    }
+    let Some(slot) = settings.current_slot else { return; };
+    let earned: Vec<Difficulty> = earned_unlocks(settings.selected_difficulty, score.current)
+        .filter(|unlock| !score.unlocks.contains(unlock))
+        .collect();
+    if earned.is_empty() {
+        return;
+    }
+    score.unlocks.extend(&earned);
+
+    let mut save = saves.load(slot);
+    let new: Vec<Difficulty> = earned
+        .into_iter()
+        .filter(|unlock| !save.as_ref().is_some_and(|save| save.profile.unlocked.contains(unlock)))
+        .collect();
+    if let Some(mut save) = save.take().filter(|_| !new.is_empty()) {
+        save.profile.unlocked.extend(&new);
+        saves.write(save);
+    }
+    for unlock in new {
+        spawn_toast(&mut commands, &fonts, &toast_query, format!("{:?} difficulty unlocked!", unlock), Color::srgb(1.0, 0.84, 0.0));
+    }
+}
+
+// Toasts each PERFECT_STREAK_MILESTONES streak once, as the pass that reaches it is scored
+fn announce_perfect_streak(
+    mut commands: Commands,
+    score: Res<Score>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+    mut last_streak: Local<u32>,
+) {
+    let streak = score.precision.streak;
+    if streak != *last_streak && PERFECT_STREAK_MILESTONES.contains(&streak) {
+        spawn_toast(&mut commands, &fonts, &toast_query, format!("Perfect streak x{}!", streak), Color::srgb(1.0, 0.84, 0.0));
+    }
+    *last_streak = streak;
+}
+
+// The pipe hitbox already accounts for a normal-sized bird, so size mutators
+// only add (or remove) the difference in the bird's half extents
+fn bird_extra_extents(bird_scale: f32) -> Vec2 {
+    (bird_scale - 1.0) * Vec2::new(BIRD_WIDTH, BIRD_HEIGHT) * PIXEL_RATIO / 2.
+}
+
+// Largest center-to-center distance at which the bird touches a pipe, per axis
+fn pipe_extents(bird_scale: f32) -> Vec2 {
+    Vec2::new(OBSTACLE_WIDTH, OBSTACLE_HEIGHT) * PIXEL_RATIO / 2. + bird_extra_extents(bird_scale)
+}
+
+fn pipe_collides(bird_position: Vec2, bird_scale: f32, pipe_position: Vec2) -> bool {
+    let extents = pipe_extents(bird_scale);
+    let distance = (pipe_position - bird_position).abs();
+    distance.y < extents.y && distance.x < extents.x
+}
+
+// Vertical room (px) between the bird and a pipe it overlaps horizontally; None when it doesn't
+fn pipe_clearance(bird_position: Vec2, bird_scale: f32, pipe_position: Vec2) -> Option<f32> {
+    let extents = pipe_extents(bird_scale);
+    let distance = (pipe_position - bird_position).abs();
+    (distance.x < extents.x).then_some(distance.y - extents.y)
+}
+
+// Push pipes that would overlap a freshly respawned bird one spacing further right.
+// Everything ahead of them moves too so the course keeps its even spacing; the pairs
+// left behind close the ring again as update_obstacles recycles them past the rightmost.
+fn clear_respawn_area(bird_x: f32, spacing: f32, obstacle_query: &mut Query<(&mut Obstacle, &mut Transform, Entity)>) {
+    let blocked = obstacle_query
+        .iter()
+        .any(|(_, pipe_transform, _)| (pipe_transform.translation.x - bird_x).abs() < RESPAWN_PROTECTION_RADIUS);
+    if !blocked {
+        return;
+    }
+
+    for (_, mut pipe_transform, _) in obstacle_query.iter_mut() {
+        if pipe_transform.translation.x > bird_x - RESPAWN_PROTECTION_RADIUS {
+            pipe_transform.translation.x += spacing * PIXEL_RATIO;
+        }
+    }
+}
+
+// Rebuilds the scale from the size mutator every step so the pulse never drifts
+fn animate_bird_scale(
+    mut commands: Commands,
+    step: Res<SimStep>,
+    settings: Res<GameSettings>,
+    mut bird_query: Query<(Entity, &mut Transform, &mut ScaleAnimation), With<Bird>>,
+) {
+    let base = Vec3::splat(PIXEL_RATIO * settings.bird_size.scale());
+    for (entity, mut transform, mut animation) in bird_query.iter_mut() {
+        animation.elapsed += step.dt;
+        if animation.elapsed >= animation.duration || settings.reduce_motion {
+            transform.scale = base;
+            commands.entity(entity).remove::<ScaleAnimation>();
+        } else {
+            transform.scale = base * animation.factor();
+        }
+    }
+}
+
+// Follows the target pair through its gap and goes away once the record is tied
+fn update_record_marker(
+    mut commands: Commands,
+    score: Res<Score>,
+    mut marker_query: Query<(Entity, &RecordMarker, &mut Transform, &mut Visibility), Without<Obstacle>>,
+    obstacle_query: Query<(&Obstacle, &Transform)>,
+) {
+    for (entity, marker, mut transform, mut visibility) in marker_query.iter_mut() {
+        if score.current >= marker.target {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let pair = obstacle_query
+            .iter()
+            .find(|(obstacle, _)| obstacle.pipe_direction == 1.0 && obstacle.index == marker.target);
+        let Some((obstacle, pipe_transform)) = pair else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        transform.translation.x = pipe_transform.translation.x;
+        transform.translation.y = pipe_transform.translation.y - get_centered_pipe_position(obstacle.gap_size);
+        transform.scale.y = 2. * obstacle.gap_size * PIXEL_RATIO;
+        *visibility = Visibility::Inherited;
+    }
+}
+
+// Follows the next pair that hasn't scrolled into view yet, once it's within GAP_TELEGRAPH_LEAD of the edge.
+// World space, so the mirror modifier moves it to the side pipes come from
+fn update_gap_telegraph(
+    settings: Res<GameSettings>,
+    game_manager: Res<GameManager>,
+    tuning: Res<DifficultyTuning>,
+    mut telegraph_query: Query<(&GapTelegraph, &mut Transform, &mut Visibility), Without<Obstacle>>,
+    obstacle_query: Query<(&Obstacle, &Transform)>,
+) {
+    let Ok((_, mut transform, mut visibility)) = telegraph_query.single_mut() else { return; };
+    let view_edge = game_manager.window_dimensions.x / 2. / settings.camera_zoom;
+    let half_pipe = OBSTACLE_WIDTH * PIXEL_RATIO / 2.;
+    let incoming = obstacle_query
+        .iter()
+        .filter(|(obstacle, pipe)| obstacle.pipe_direction == 1.0 && !obstacle.parked && pipe.translation.x - half_pipe > view_edge)
+        .min_by(|(_, a), (_, b)| a.translation.x.total_cmp(&b.translation.x));
+    let Some((obstacle, pipe)) = incoming.filter(|(_, pipe)| pipe.translation.x - half_pipe - view_edge <= tuning.scroll_speed * GAP_TELEGRAPH_LEAD) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    transform.translation.x = view_edge;
+    transform.translation.y = pipe.translation.y - get_centered_pipe_position(obstacle.gap_size);
+    *visibility = Visibility::Inherited;
+}
+
+fn update_invulnerability(
+    mut commands: Commands,
+    step: Res<SimStep>,
+    mut bird_query: Query<(Entity, &mut Invulnerable, &mut Visibility), With<Bird>>,
+) {
+    // Blink the bird while respawn protection lasts, then restore it
+    for (entity, mut invulnerable, mut visibility) in bird_query.iter_mut() {
+        let delta = std::time::Duration::from_secs_f32(step.dt);
+        invulnerable.timer.tick(delta);
+        if invulnerable.timer.is_finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Invulnerable>();
+            continue;
+        }
+
+        if invulnerable.blink.tick(delta).just_finished() {
+            visibility.toggle_inherited_hidden();
+        }
+    }
+}
+
+// HUD texts are only reformatted when the value they show changes. A freshly spawned
+// element forgets the cached value, so the first frame after setup always fills it
+fn refresh_hud_text<T: PartialEq + Copy>(
+    text: &mut Mut<Text>,
+    added: bool,
+    shown: &mut Option<T>,
+    value: T,
+    stats: &mut HudStats,
+    render: impl FnOnce(T) -> String,
+) {
+    if added {
+        *shown = None;
+    }
+    if *shown == Some(value) {
+        return;
+    }
+    text.0 = render(value);
+    *shown = Some(value);
+    stats.updates += 1;
+}
+
+fn update_ui(
+    mut score_query: Query<(&mut Text, Ref<ScoreDisplay>), (Without<BestScoreDisplay>, Without<ComboDisplay>)>,
+    mut best_score_query: Query<(&mut Text, Ref<BestScoreDisplay>), Without<ComboDisplay>>,
+    mut combo_text_query: Query<(&mut Text, Ref<ComboDisplay>)>,
+    mut combo_bar_query: Query<(&mut Node, &mut BackgroundColor), With<ComboBar>>,
+    score: Res<Score>,
+    palette: Res<ThemePalette>,
+    mut stats: ResMut<HudStats>,
+    mut shown: Local<HudShown>,
+) {
+    for (mut text, marker) in score_query.iter_mut() {
+        refresh_hud_text(&mut text, marker.is_added(), &mut shown.score, score.current, &mut stats, |score| format!("Score: {}", score));
+    }
+
+    for (mut text, marker) in best_score_query.iter_mut() {
+        refresh_hud_text(&mut text, marker.is_added(), &mut shown.best, score.best, &mut stats, |best| format!("Best: {}", best));
+    }
+
+    // Combo bar: drained width while active, full red flash right after it breaks
+    for (mut node, mut color) in combo_bar_query.iter_mut() {
+        if score.combo_flash > 0.0 {
+            node.width = Val::Percent(100.0);
+            color.0 = Color::srgb(1.0, 0.2, 0.2);
+        } else {
+            node.width = Val::Percent(score.combo_timer / COMBO_WINDOW * 100.0);
+            color.0 = palette.accent;
+        }
+    }
+
+    // 0 while there's no combo to show
+    let combo = if score.combo > 1 { score.combo } else { 0 };
+    for (mut text, marker) in combo_text_query.iter_mut() {
+        refresh_hud_text(&mut text, marker.is_added(), &mut shown.combo, combo, &mut stats, |combo| {
+            if combo > 0 { format!("x{}", combo) } else { String::new() }
+        });
+    }
+}
+
+// Rolls the rewrite count into a per-second rate and toggles the overlay with F3
+fn update_hud_stats(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut stats: ResMut<HudStats>,
+    mut overlay_query: Query<(&mut Text, &mut Visibility), With<HudStatsOverlay>>,
+    run_query: Query<(), With<RunScoped>>,
+) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        stats.visible = !stats.visible;
+    }
+    stats.elapsed += time.delta_secs();
+    stats.worst_frame = stats.worst_frame.max(time.delta_secs());
+    let rolled = stats.elapsed >= 1.0;
+    if rolled {
+        stats.per_second = stats.updates;
+        stats.updates = 0;
+        stats.elapsed = 0.0;
+        stats.last_worst_frame = stats.worst_frame;
+        stats.worst_frame = 0.0;
+        stats.run_entities = run_query.iter().count();
+        // A steady climb past the cap means some spawn path never despawns
+        let over_cap = stats.run_entities > RUN_ENTITY_CAP;
+        if over_cap && !stats.over_cap {
+            eprintln!("{} run entities alive (cap {}); something isn't despawning", stats.run_entities, RUN_ENTITY_CAP);
+        }
+        stats.over_cap = over_cap;
+    }
+
+    for (mut text, mut visibility) in overlay_query.iter_mut() {
+        visibility.set_if_neq(if stats.visible { Visibility::Inherited } else { Visibility::Hidden });
+        if rolled && stats.visible {
+            text.0 = format!(
+                "HUD updates/s: {}   Worst frame: {:.1}ms   Run entities: {}",
+                stats.per_second,
+                stats.last_worst_frame * 1000.0,
+                stats.run_entities
+            );
+        }
+    }
+}
+
+fn update_floor_warning(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    game_manager: Res<GameManager>,
+    sound_effects: Res<SoundEffects>,
+    bird_query: Query<&Transform, With<Bird>>,
+    mut warning_query: Query<(&mut BackgroundGradient, &mut FloorWarning)>,
+    audio: Res<AudioSettings>,
+) {
+    let Ok(bird) = bird_query.single() else { return; };
+    let Ok((mut gradient, mut warning)) = warning_query.single_mut() else { return; };
+
+    // Only the floor is lethal; flying above the screen is allowed
+    let bird_bottom = bird.translation.y - BIRD_HEIGHT * PIXEL_RATIO * settings.bird_size.scale() / 2.;
+    let distance = bird_bottom + game_manager.window_dimensions.y / 2.;
+    let proximity = (1.0 - distance / FLOOR_WARNING_DISTANCE).clamp(0.0, 1.0);
+
+    *gradient = BackgroundGradient::from(LinearGradient::to_top(vec![
+        ColorStop::auto(Color::srgb(1.0, 0.1, 0.1).with_alpha(proximity * FLOOR_WARNING_MAX_ALPHA)),
+        ColorStop::auto(Color::NONE),
+    ]));
+
+    warning.tick_cooldown = (warning.tick_cooldown - time.delta_secs()).max(0.0);
+    if proximity > 0.0 && warning.tick_cooldown == 0.0 && settings.floor_warning_sound {
+        commands.spawn((
+            AudioPlayer::new(sound_effects.flap.clone()),
+            PlaybackSettings {
+                volume: audio.sfx(0.04),
+                speed: 1.8,
+                ..PlaybackSettings::DESPAWN
+            },
+        ));
+        warning.tick_cooldown = FLOOR_WARNING_TICK_INTERVAL;
+    }
+}
+
+fn update_gap_tone(
+    time: Res<Time>,
+    bird_query: Query<&Transform, With<Bird>>,
+    obstacle_query: Query<(&Obstacle, &Transform), Without<Bird>>,
+    mut tone_query: Query<(&AudioSink, &mut GapTone)>,
+) {
+    let Ok((sink, mut tone)) = tone_query.single_mut() else { return; };
+    if !tone.update_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Ok(bird) = bird_query.single() else { return; };
+    let Some((_, gap_center)) = next_gap(bird.translation, obstacle_query.iter()) else { return; };
+    sink.set_speed(gap_tone_speed(gap_center - bird.translation.y));
+}
+
+// Horizontal distance to and height of the next gap, from the nearest unscored top pipe
+// still ahead of the bird. Uses the pipe's own gap so shrinking gaps are followed too
+fn next_gap<'a>(bird: Vec3, pipes: impl Iterator<Item = (&'a Obstacle, &'a Transform)>) -> Option<(f32, f32)> {
+    let (obstacle, top_pipe) = pipes
+        .filter(|(obstacle, pipe)| {
+            obstacle.pipe_direction == 1.0
+                && !obstacle.scored
+                && pipe.translation.x + OBSTACLE_WIDTH * PIXEL_RATIO / 2. > bird.x
+        })
+        .min_by(|(_, a), (_, b)| a.translation.x.total_cmp(&b.translation.x))?;
+    let gap_center = top_pipe.translation.y - get_centered_pipe_position(obstacle.gap_size);
+    Some(((top_pipe.translation.x - bird.x).max(0.0), gap_center))
+}
+
+// Higher pitch = gap is above the bird: one octave per GAP_TONE_OCTAVE_DISTANCE, at most one either way
+fn gap_tone_speed(delta: f32) -> f32 {
+    2f32.powf(delta / GAP_TONE_OCTAVE_DISTANCE).clamp(0.5, 2.0)
+}
+
+// Same pitch mapping as the gap tone, but every frame; the emitter slides from the right ear
+// toward the center as the pipe closes in, so timing can be heard as well as height
+fn update_blind_tone(
+    bird_query: Query<&Transform, With<Bird>>,
+    obstacle_query: Query<(&Obstacle, &Transform), Without<Bird>>,
+    mut tone_query: Query<(&SpatialAudioSink, &mut Transform), (With<BlindTone>, Without<Bird>, Without<Obstacle>)>,
+) {
+    let Ok((sink, mut emitter)) = tone_query.single_mut() else { return; };
+    let Ok(bird) = bird_query.single() else { return; };
+    let Some((distance, gap_center)) = next_gap(bird.translation, obstacle_query.iter()) else { return; };
+    sink.set_speed(gap_tone_speed(gap_center - bird.translation.y));
+    emitter.translation.x = BLIND_PAN_WIDTH * (distance / BLIND_PAN_RANGE).min(1.0);
+}
+
+fn update_combo(step: Res<SimStep>, settings: Res<GameSettings>, mut score: ResMut<Score>) {
+    // The meter drains every frame without flagging Score as changed; only a broken
+    // combo does, so systems watching the score don't rerun each frame
+    let dt = step.dt;
+    let meter = score.bypass_change_detection();
+    meter.combo_flash = (meter.combo_flash - dt).max(0.0);
+    if meter.combo == 0 {
+        return;
+    }
+
+    // Combo breaks when the meter runs dry before the next pipe
+    meter.combo_timer -= dt;
+    if meter.combo_timer <= 0.0 {
+        score.combo_timer = 0.0;
+        score.combo = 0;
+        if !settings.photosensitive_safe {
+            score.combo_flash = COMBO_FLASH_DURATION;
+        }
+    }
+}
+
+fn update_time_attack(
+    mut commands: Commands,
+    step: Res<SimStep>,
+    settings: Res<GameSettings>,
+    score: Res<Score>,
+    timer: Option<ResMut<TimeAttackState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut time_ui: Query<(&mut Text, &mut TextColor, Ref<TimeDisplay>), Without<OvertimeToast>>,
+    mut toast_query: Query<(&mut Text, &mut OvertimeToast)>,
+    mut stats: ResMut<HudStats>,
+    mut shown: Local<Option<u32>>,
+) {
+    if settings.selected_mode != GameMode::TimeAttack && settings.selected_mode != GameMode::Tournament {
+        return;
+    }
+
+    // Count down and end the run (or tournament round) at zero
+    let Some(mut timer) = timer else { return; };
+    timer.remaining -= step.dt;
+
+    // Overtime: pipes scored since last frame add time, capped at OVERTIME_MAX
+    let earned = score.current.saturating_sub(timer.credited_score);
+    timer.credited_score = score.current;
+    if settings.selected_mode == GameMode::TimeAttack && settings.overtime && earned > 0 {
+        timer.remaining = (timer.remaining + earned as f32 * OVERTIME_BONUS).min(OVERTIME_MAX);
+        for (mut text, mut toast) in toast_query.iter_mut() {
+            text.0 = format!("+{:.0}s", earned as f32 * OVERTIME_BONUS);
+            toast.remaining = OVERTIME_TOAST_DURATION;
+        }
+    }
+    for (mut text, mut toast) in toast_query.iter_mut() {
+        toast.remaining = (toast.remaining - step.dt).max(0.0);
+        if toast.remaining == 0.0 {
+            text.0.clear();
+        }
+    }
+
+    let remaining = timer.remaining.max(0.0);
+    if let Some((mut txt, mut color, marker)) = time_ui.iter_mut().next() {
+        refresh_hud_text(&mut txt, marker.is_added(), &mut shown, remaining.round() as u32, &mut stats, |seconds| format!("Time: {}", seconds));
+        color.set_if_neq(TextColor(time_color(remaining / timer.duration)));
+    }
+
+    if timer.remaining <= 0.0 {
+        if settings.selected_mode == GameMode::Tournament {
+            next_state.set(GameState::RoundSummary);
+        } else {
+            commands.insert_resource(TimeAttackFinish {
+                timer: Timer::from_seconds(FINISH_DURATION, TimerMode::Once),
+                notes_played: 0,
+            });
+        }
+    }
+}
+
+// Confetti burst from the top edge and the banner, once per finish
+fn start_time_attack_finish(
+    mut commands: Commands,
+    fonts: Res<UiFonts>,
+    palette: Res<ThemePalette>,
+    game_manager: Res<GameManager>,
+    settings: Res<GameSettings>,
+    mut confetti_query: Query<(&mut Transform, &mut Visibility, &mut Confetti)>,
+) {
+    let mut rng = rand::thread_rng();
+    let half = game_manager.window_dimensions / 2.;
+    // Read here rather than at run start, since Reduce Motion can change from the pause menu
+    for (mut transform, mut visibility, mut confetti) in confetti_query.iter_mut().filter(|_| !settings.reduce_motion) {
+        transform.translation.x = rng.gen_range(-half.x..half.x);
+        transform.translation.y = half.y;
+        confetti.velocity = Vec2::new(rng.gen_range(-120.0..120.0), rng.gen_range(-300.0..50.0));
+        confetti.spin = rng.gen_range(-10.0..10.0);
+        *visibility = Visibility::Visible;
+    }
+
+    commands.spawn((
+        Text::new("TIME UP - YOU SURVIVED!"),
+        TextFont {
+            font: fonts.title.clone(),
+            font_size: fonts.heading(48.0),
+            ..default()
+        },
+        TextColor(palette.accent),
+        palette.text_shadow(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(25.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        FinishBanner,
+        RunScoped,
+    ));
+}
+
+// Runs instead of the gameplay systems; the slot is saved once, when the timer ends
+fn update_time_attack_finish(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut finish: ResMut<TimeAttackFinish>,
+    settings: Res<GameSettings>,
+    audio: Res<AudioSettings>,
+    sound_effects: Res<SoundEffects>,
+    mut bird_query: Query<&mut Transform, (With<Bird>, Without<Confetti>)>,
+    mut confetti_query: Query<(Entity, &mut Transform, &mut Confetti)>,
+    mut score: ResMut<Score>,
+    replay: Option<Res<ReplayPlayback>>,
+    mut saves: ResMut<SaveWriter>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let dt = time.delta_secs();
+    finish.timer.tick(time.delta());
+
+    let elapsed = finish.timer.elapsed_secs();
+    while finish.notes_played < JINGLE_NOTES.len() && JINGLE_NOTES[finish.notes_played].0 <= elapsed {
+        commands.spawn((
+            AudioPlayer(sound_effects.jingle[finish.notes_played].clone()),
+            PlaybackSettings {
+                volume: audio.sfx(0.1),
+                ..PlaybackSettings::DESPAWN
+            },
+            SfxKind::Milestone,
+        ));
+        finish.notes_played += 1;
+    }
+
+    // The bird levels out and glides to the middle of the screen
+    if !settings.reduce_motion {
+        let glide = (dt * FINISH_GLIDE_RATE).min(1.0);
+        for mut transform in bird_query.iter_mut() {
+            transform.translation.y += (0.0 - transform.translation.y) * glide;
+            transform.rotation = transform.rotation.slerp(Quat::IDENTITY, glide);
+        }
+    }
+
+    for (_, mut transform, mut confetti) in confetti_query.iter_mut() {
+        confetti.velocity.y -= CONFETTI_GRAVITY * dt;
+        transform.translation += (confetti.velocity * dt).extend(0.);
+        transform.rotate_z(confetti.spin * dt);
+    }
+
+    if finish.timer.just_finished() {
+        for (entity, _, _) in &confetti_query {
+            commands.entity(entity).despawn();
+        }
+        save_run_result(&mut saves, &settings, &mut score, replay.is_some());
+        next_state.set(GameState::GameOver);
+    }
+}
+
+// Green with the full clock, through yellow at half time, to red at zero
+fn time_color(fraction: f32) -> Color {
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    let fraction = fraction.clamp(0.0, 1.0);
+    if fraction > 0.5 {
+        let t = (fraction - 0.5) * 2.0;
+        Color::srgb(lerp(1.0, 0.3, t), 0.9, lerp(0.2, 0.3, t))
+    } else {
+        let t = fraction * 2.0;
+        Color::srgb(1.0, lerp(0.25, 0.9, t), 0.2)
+    }
+}
+
+fn update_checkpoints(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    checkpoints: Option<ResMut<CheckpointsState>>,
+    score: Res<Score>,
+    sound_effects: Res<SoundEffects>,
+    mut checkpoint_ui: Query<(&mut Text, Ref<CheckpointDisplay>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    audio: Res<AudioSettings>,
+    mut stats: ResMut<HudStats>,
+    mut shown: Local<Option<(u32, u32)>>,
+) {
+    if settings.selected_mode != GameMode::Checkpoints {
+        return;
+    }
+
+    let Some(mut checkpoints_state) = checkpoints else { return; };
+    
+    // Update UI
+    if let Some((mut txt, marker)) = checkpoint_ui.iter_mut().next() {
+        let progress = (score.current, checkpoints_state.target_score());
+        refresh_hud_text(&mut txt, marker.is_added(), &mut shown, progress, &mut stats, |(current, target)| format!("Checkpoint: {}/{}", current, target));
+    }
+
+    // Check if player reached current checkpoint
+    if !checkpoints_state.completed && score.current >= checkpoints_state.target_score() {
+        // Play checkpoint sound
+        commands.spawn((
+            AudioPlayer::new(sound_effects.swoosh.clone()),
+            PlaybackSettings {
+                volume: audio.sfx(0.2),
+                ..PlaybackSettings::DESPAWN
+            },
+            SfxKind::Milestone,
+        ));
+
+        // Save this checkpoint as the respawn point
+        checkpoints_state.last_checkpoint_score = checkpoints_state.target_score();
+
+        if checkpoints_state.is_final_checkpoint() {
+            // Final checkpoint reached - Victory!
+            checkpoints_state.completed = true;
+            
+            // Transition to victory screen
+            next_state.set(GameState::Victory);
+        } else {
+            // Move to next checkpoint
+            checkpoints_state.current_checkpoint_index += 1;
+            
+            // Update UI with new target
+            if let Some((mut txt, _)) = checkpoint_ui.iter_mut().next() {
+                let progress = (score.current, checkpoints_state.target_score());
+                refresh_hud_text(&mut txt, false, &mut shown, progress, &mut stats, |(current, target)| format!("Checkpoint: {}/{}", current, target));
+            }
+        }
+    }
+}
+
+fn update_level(
+    mut commands: Commands,
+    step: Res<SimStep>,
+    level: Option<ResMut<LevelRun>>,
+    mut score: ResMut<Score>,
+    tuning: Res<DifficultyTuning>,
+    game_manager: Res<GameManager>,
+    sound_effects: Res<SoundEffects>,
+    audio: Res<AudioSettings>,
+    bird_query: Query<&Transform, With<Bird>>,
+    mut coin_query: Query<(Entity, &mut Transform), (With<Coin>, Without<Bird>)>,
+    obstacle_query: Query<&Obstacle>,
+    mut level_ui: Query<(&mut Text, Ref<LevelDisplay>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut stats: ResMut<HudStats>,
+    mut shown: Local<Option<usize>>,
+) {
+    let Some(mut level) = level else { return; };
+    let dt = step.dt;
+    level.elapsed += dt;
+
+    // Coins ride along with the pipes and are picked up on contact
+    let bird = bird_query.single().ok().map(|transform| transform.translation.truncate());
+    for (entity, mut transform) in coin_query.iter_mut() {
+        transform.translation.x -= dt * tuning.scroll_speed;
+
+        let touched = bird.is_some_and(|bird| {
+            let reach = (Vec2::new(BIRD_WIDTH, BIRD_HEIGHT) + COIN_SIZE) * PIXEL_RATIO / 2.;
+            let distance = (transform.translation.truncate() - bird).abs();
+            distance.x < reach.x && distance.y < reach.y
+        });
+        if touched {
+            level.coins += 1;
+            score.log.push(RunEvent::Pickup("coin"));
+            commands.entity(entity).despawn();
+            commands.spawn((
+                AudioPlayer::new(sound_effects.point.clone()),
+                PlaybackSettings {
+                    volume: audio.sfx(0.1),
+                    ..PlaybackSettings::DESPAWN
+                },
+                SfxKind::Coin,
+            ));
+        } else if transform.translation.x < -game_manager.window_dimensions.x / 2. {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let passed = level.next_pipe.saturating_sub(obstacle_query.iter().filter(|o| o.pipe_direction == 1.0 && !o.scored).count());
+    if let Some((mut txt, marker)) = level_ui.iter_mut().next() {
+        refresh_hud_text(&mut txt, marker.is_added(), &mut shown, passed, &mut stats, |passed| format!("{}: {}/{}", level.def.name, passed, level.def.pipes.len()));
+    }
+
+    // Complete once every pipe has been handed out and flown through
+    if passed == level.def.pipes.len() {
+        commands.insert_resource(LevelResult {
+            name: level.def.name.clone(),
+            score: score.current,
+            coins: level.coins,
+            total_coins: level.def.total_coins(),
+            time: level.elapsed,
+        });
+        next_state.set(GameState::LevelComplete);
+    }
+}
+
+// ---------------------------- LEVELS ----------------------------
+// Levels are JSON files in assets/levels; the file stem identifies the level
+fn load_level(name: &str) -> Result<LevelDef, String> {
+    let path = format!("{}/{}.json", LEVELS_DIR, name);
+    let contents = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let level: LevelDef = serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+    level.validate()?;
+    Ok(level)
+}
+
+// Every level file found, sorted by file name, with malformed ones kept so the list can explain them
+fn list_levels() -> Vec<(String, Result<LevelDef, String>)> {
+    let Ok(entries) = fs::read_dir(LEVELS_DIR) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    names.truncate(MAX_LISTED_LEVELS);
+
+    names
+        .into_iter()
+        .map(|name| {
+            let level = load_level(&name);
+            (name, level)
+        })
+        .collect()
+}
+
+fn setup_level_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, fonts: Res<UiFonts>, window_query: Query<&Window, With<PrimaryWindow>>) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        LevelSelectMarker,
+    ));
+
+    let levels = list_levels();
+
+    commands.spawn((
+        menu_root_node(),
+        LevelSelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("SELECT LEVEL"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        if levels.is_empty() {
+            parent.spawn((
+                Text::new(format!("No levels found in {}", LEVELS_DIR)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(24.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+            ));
+        }
+
+        for (i, (file, level)) in levels.iter().enumerate() {
+            let (text, color) = match level {
+                Ok(level) => (
+                    format!("[{}] {}  ({} pipes, {} coins)", i + 1, level.name, level.pipes.len(), level.total_coins()),
+                    Color::srgb(1.0, 0.992, 0.816),
+                ),
+                Err(e) => (format!("{}.json - invalid: {}", file, e), Color::srgb(1.0, 0.4, 0.4)),
+            };
+            parent.spawn((
+                Text::new(text),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(24.0),
+                    ..default()
+                },
+                TextColor(color),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new("Select [1-9]\nReturn [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn level_select_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::ModeSelect));
+        return;
+    }
+
+    let Some(index) = (1..=MAX_LISTED_LEVELS).position(|digit| digit_just_pressed(&keyboard, digit)) else {
+        return;
+    };
+
+    // Invalid levels stay listed but can't be picked
+    if let Some((file, Ok(_))) = list_levels().into_iter().nth(index) {
+        settings.selected_mode = GameMode::Level;
+        settings.selected_level = Some(file);
+        next_state.set(GameState::Options);
+    }
+}
+
+fn setup_level_complete_ui(mut commands: Commands, asset_server: Res<AssetServer>, fonts: Res<UiFonts>, window_query: Query<&Window, With<PrimaryWindow>>, result: Res<LevelResult>, bindings: Res<KeyBindings>) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        LevelCompleteMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        LevelCompleteMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("LEVEL COMPLETE"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.84, 0.0)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        for line in [
+            result.name.clone(),
+            format!("SCORE: {}", result.score),
+            format!("COINS: {}/{}", result.coins, result.total_coins),
+            format!("TIME: {}", format_play_time(result.time)),
+        ] {
+            parent.spawn((
+                Text::new(line),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(32.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(0.85, 0.95, 1.0)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new(format!("RETRY [R]\nRETURN TO MAIN MENU [{}]", bindings.advance_hint())),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn handle_level_complete(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut fade: ResMut<ScreenFade>,
+) {
+    if bindings.consume(&mut keyboard, InputAction::Advance) {
+        fade.request_transition(GameState::MainMenu);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        fade.request_transition(GameState::Restarting);
+    }
+}
+
+fn update_gap_progress(
+    step: Res<SimStep>,
+    score: Res<Score>,
+    mut progress: ResMut<GapProgress>,
+    mut toast_query: Query<(&mut Text, &mut GapToast)>,
+) {
+    for (mut text, mut toast) in toast_query.iter_mut() {
+        toast.remaining = (toast.remaining - step.dt).max(0.0);
+        if toast.remaining == 0.0 {
+            text.0.clear();
+        }
+    }
+
+    if !progress.sync(score.current) {
+        return;
+    }
+    for (mut text, mut toast) in toast_query.iter_mut() {
+        text.0 = "Gaps tightening...".to_string();
+        toast.remaining = GAP_TOAST_DURATION;
+    }
+}
+
+// ---------------------------- GAP TRAINER ----------------------------
+// Pick a gap on a slider and practice it with Normal physics; runs are never ranked
+fn trainer_gap_label(gap_size: f32, config: &GameConfig) -> String {
+    let nearest = DIFFICULTIES
+        .into_iter()
+        .find(|difficulty| (difficulty_tuning(*difficulty, config).gap_size - gap_size).abs() < 0.01);
+    match nearest {
+        Some(difficulty) => format!("Gap:  < {:.2} >  ({:?})", gap_size, difficulty),
+        None => format!("Gap:  < {:.2} >", gap_size),
+    }
+}
+
+fn trainer_slider_percent(gap_size: f32) -> f32 {
+    (gap_size - TRAINER_GAP_MIN) / (TRAINER_GAP_MAX - TRAINER_GAP_MIN) * 100.0
+}
+
+fn setup_trainer_select_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    trainer: Res<TrainerTuning>,
+    config: Res<GameConfig>,
+    bindings: Res<KeyBindings>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        TrainerSelectMarker,
+    ));
+
+    commands.spawn((
+        menu_root_node(),
+        TrainerSelectMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("GAP TRAINER"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(trainer_gap_label(trainer.gap_size, &config)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+            TrainerGapText,
+        ));
+
+        // Slider - Track with a fill proportional to the gap
+        parent.spawn((
+            Node {
+                width: Val::Px(300.0),
+                height: Val::Px(12.0),
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.4)),
+        ))
+        .with_children(|track| {
+            track.spawn((
+                Node {
+                    width: Val::Percent(trainer_slider_percent(trainer.gap_size)),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(AQUAMARINE.into()),
+                TrainerSlider,
+            ));
+        });
+
+        parent.spawn((
+            Text::new("Every pipe uses this gap with Normal speed and gravity.\nTrainer runs are never ranked."),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(format!("Adjust [LEFT/RIGHT]   Start [{}]\nReturn [ESC]", bindings.advance_hint())),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn trainer_select_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut trainer: ResMut<TrainerTuning>,
+    config: Res<GameConfig>,
+    mut text_query: Query<&mut Text, With<TrainerGapText>>,
+    mut slider_query: Query<&mut Node, With<TrainerSlider>>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::ModeSelect));
+        return;
+    }
+
+    if bindings.consume(&mut keyboard, InputAction::Advance) {
+        settings.selected_mode = GameMode::Trainer;
+        next_state.set(GameState::Playing);
+        return;
+    }
+
+    let step = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        TRAINER_GAP_STEP
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -TRAINER_GAP_STEP
+    } else {
+        return;
+    };
+    trainer.gap_size = (trainer.gap_size + step).clamp(TRAINER_GAP_MIN, TRAINER_GAP_MAX);
+
+    for mut text in text_query.iter_mut() {
+        text.0 = trainer_gap_label(trainer.gap_size, &config);
+    }
+    for mut node in slider_query.iter_mut() {
+        node.width = Val::Percent(trainer_slider_percent(trainer.gap_size));
+    }
+}
+
+// ---------------------------- REPLAYS ----------------------------
+// Runs are simulated from SimStep, so replaying the recorded steps on the same
+// seed reproduces the run exactly
+fn sample_sim_step(
+    mut commands: Commands,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
+    held_flap: Option<Res<HeldFlap>>,
+    replay: Option<ResMut<ReplayPlayback>>,
+    mut step: ResMut<SimStep>,
+    mut recording: ResMut<RunRecording>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if held_flap.is_some() {
+        commands.remove_resource::<HeldFlap>();
+    }
+
+    *step = match replay {
+        Some(mut replay) => {
+            let Some(recorded) = replay.steps.get(replay.cursor).copied() else {
+                // Log ran out before the run ended; stop rather than improvise input
+                *step = SimStep::default();
+                next_state.set(GameState::GameOver);
+                return;
+            };
+            replay.cursor += 1;
+            recorded
+        }
+        None => SimStep {
+            dt: physics_delta(&time),
+            flap: bindings.just_pressed(&keys, InputAction::Flap)
+                || gamepads.iter().any(|gamepad| gamepad.just_pressed(GamepadButton::South))
+                || held_flap.is_some(),
+        },
+    };
+    recording.steps.push(*step);
+}
+
+fn save_replay(
+    settings: &GameSettings,
+    config: &GameConfig,
+    trainer_gap: f32,
+    seed: u64,
+    score: u32,
+    recording: &RunRecording,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let replay = ReplayFile {
+        version: REPLAY_VERSION,
+        seed,
+        mode: settings.selected_mode,
+        difficulty: settings.selected_difficulty,
+        theme: settings.selected_theme,
+        skin: settings.selected_skin,
+        bird_size: recording.bird_size,
+        spawn_strategy: settings.spawn_strategy,
+        level: settings.selected_level.clone(),
+        trainer_gap: (settings.selected_mode == GameMode::Trainer).then_some(trainer_gap),
+        modifiers: settings.modifiers,
+        overtime: settings.overtime,
+        start_score: settings.start_score,
+        score_goal: settings.score_goal,
+        gauntlet_start: settings.gauntlet_start,
+        fill_window: settings.fill_window,
+        custom_tuning: settings.custom_tuning,
+        config: Some(*config),
+        score,
+        steps: recording.steps.clone(),
+    };
+
+    fs::create_dir_all(REPLAY_DIR)?;
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let path = Path::new(REPLAY_DIR).join(format!("{:?}-{}-{}.{}", replay.mode, score, timestamp, REPLAY_EXTENSION));
+    fs::write(&path, serde_json::to_string(&replay)?)?;
+    Ok(path)
+}
+
+fn load_replay(path: &Path) -> Result<ReplayFile, String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let header: ReplayHeader = serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+    if header.version != REPLAY_VERSION {
+        return Err(format!("version {} is not supported (expected {})", header.version, REPLAY_VERSION));
+    }
+    serde_json::from_str(&contents).map_err(|e| e.to_string())
+}
+
+// Shared replays are dropped into saves/replays; the most recently modified one is watched
+fn newest_replay() -> Option<PathBuf> {
+    fs::read_dir(REPLAY_DIR)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == REPLAY_EXTENSION))
+        .max_by_key(|entry| entry.metadata().and_then(|meta| meta.modified()).ok())
+        .map(|entry| entry.path())
+}
+
+fn start_replay(
+    mut commands: Commands,
+    mut settings: ResMut<GameSettings>,
+    mut trainer: ResMut<TrainerTuning>,
+    mut config: ResMut<GameConfig>,
+    mut status: ResMut<RunCodeStatus>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let loaded = newest_replay()
+        .ok_or_else(|| format!("no .{} files in {}", REPLAY_EXTENSION, REPLAY_DIR))
+        .and_then(|path| load_replay(&path));
+    let replay = match loaded {
+        Ok(replay) => replay,
+        Err(e) => {
+            status.message = Some(format!("Replay failed: {}", e));
+            next_state.set(GameState::Options);
+            return;
+        }
+    };
+
+    commands.insert_resource(ReplayPlayback {
+        steps: replay.steps,
+        cursor: 0,
+        recorded_score: replay.score,
+        restore: settings.clone(),
+        restore_config: *config,
+        restore_trainer: *trainer,
+    });
+    if let Some(replay_config) = replay.config {
+        *config = replay_config;
+    }
+    settings.selected_mode = replay.mode;
+    settings.selected_difficulty = replay.difficulty;
+    settings.selected_theme = replay.theme;
+    settings.selected_skin = replay.skin;
+    settings.bird_size = replay.bird_size;
+    settings.spawn_strategy = replay.spawn_strategy;
+    settings.selected_level = replay.level;
+    settings.modifiers = replay.modifiers;
+    settings.overtime = replay.overtime;
+    settings.start_score = replay.start_score;
+    settings.score_goal = replay.score_goal;
+    settings.gauntlet_start = replay.gauntlet_start;
+    settings.fill_window = replay.fill_window;
+    settings.custom_tuning = replay.custom_tuning;
+    if let Some(gap_size) = replay.trainer_gap {
+        trainer.gap_size = gap_size;
+    }
+    settings.seed = Some(replay.seed);
+    next_state.set(GameState::Playing);
+}
+
+fn clear_replay(
+    mut commands: Commands,
+    replay: Option<Res<ReplayPlayback>>,
+    mut settings: ResMut<GameSettings>,
+    mut config: ResMut<GameConfig>,
+    mut trainer: ResMut<TrainerTuning>,
+) {
+    if let Some(replay) = replay {
+        *settings = replay.restore.clone();
+        *config = replay.restore_config;
+        *trainer = replay.restore_trainer;
+        commands.remove_resource::<ReplayPlayback>();
+    }
+}
+
+// ---------------------------- MODIFIERS ----------------------------
+// Toggled after picking a theme; ENTER continues to Options, ESC goes back to the themes
+fn setup_modifiers_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        ModifiersMarker,
+    ));
+
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    let modifiers = settings.modifiers;
+    let entries = [
+        format!("[1] Wind:  {}", on_off(modifiers.wind)),
+        format!("[2] Mirror:  {}", on_off(modifiers.mirror)),
+        format!("[3] Flap Cooldown:  {}", on_off(modifiers.flap_cooldown)),
+        format!("[4] Screen Wrap:  {}", on_off(modifiers.screen_wrap)),
+        format!("[5] Gravity Flip:  {}", on_off(modifiers.gravity_flip)),
+    ];
+
+    commands.spawn((
+        menu_root_node(),
+        ModifiersMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("MODIFIERS"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        for entry in entries {
+            parent.spawn((
+                Text::new(entry),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        let ranking = if modifiers.any() { "Modified runs are not ranked" } else { "Runs are ranked" };
+        parent.spawn((
+            Text::new(ranking),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
+                ..default()
+            },
+            TextColor(AQUAMARINE.into()),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new("Toggle [1-5]\nContinue [ENTER]   Return [ESC]"),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn modifiers_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut flag: ResMut<OptionsChanged>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::ThemeSelect));
+        return;
+    }
+
+    if bindings.consume(&mut keyboard, InputAction::MenuConfirm) {
+        next_state.set(GameState::Options);
+        return;
+    }
+
+    let modifiers = &mut settings.modifiers;
+    for (digit, toggle) in [
+        (1, &mut modifiers.wind),
+        (2, &mut modifiers.mirror),
+        (3, &mut modifiers.flap_cooldown),
+        (4, &mut modifiers.screen_wrap),
+        (5, &mut modifiers.gravity_flip),
+    ] {
+        if digit_just_pressed(&keyboard, digit) {
+            *toggle = !*toggle;
+            flag.changed = true;
+        }
+    }
+}
+
+fn refresh_modifiers_ui(
+    mut commands: Commands,
+    query: Query<Entity, With<ModifiersMarker>>,
+    mut flag: ResMut<OptionsChanged>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+) {
+    if flag.changed {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+
+        setup_modifiers_ui(commands, asset_server, fonts, window_query, settings);
+
+        flag.changed = false;
+    }
+}
+
+// Runs between input sampling and update_bird so replays re-apply the same rules
+fn apply_modifiers(
+    mut step: ResMut<SimStep>,
+    mut modifier_state: ResMut<ModifierState>,
+    settings: Res<GameSettings>,
+    mut bird_query: Query<&mut Bird>,
+) {
+    let modifiers = settings.modifiers;
+    modifier_state.elapsed += step.dt;
+    modifier_state.since_flap += step.dt;
+
+    if modifiers.flap_cooldown && step.flap {
+        if modifier_state.since_flap < FLAP_COOLDOWN {
+            step.flap = false;
+        } else {
+            modifier_state.since_flap = 0.0;
+        }
+    }
+
+    if modifiers.wind {
+        let gust = (modifier_state.elapsed * std::f32::consts::TAU / WIND_PERIOD).sin();
+        for mut bird in bird_query.iter_mut() {
+            bird.velocity += WIND_FORCE * gust * step.dt;
+        }
+    }
+}
+
+// Spawns flip pickups in an upcoming gap, collects them and runs down an active flip
+fn update_gravity_flip(
+    mut commands: Commands,
+    step: Res<SimStep>,
+    settings: Res<GameSettings>,
+    mut score: ResMut<Score>,
+    tuning: Res<DifficultyTuning>,
+    game_manager: Res<GameManager>,
+    mut modifier_state: ResMut<ModifierState>,
+    mut bird_query: Query<(Entity, &Transform, Option<&mut GravityFlip>), With<Bird>>,
+    mut pickup_query: Query<(Entity, &mut Transform), (With<FlipPickup>, Without<Bird>, Without<Obstacle>)>,
+    obstacle_query: Query<(&Obstacle, &Transform), Without<Bird>>,
+    mut indicator_query: Query<&mut Text, With<FlipIndicator>>,
+) {
+    if !settings.modifiers.gravity_flip {
+        return;
+    }
+
+    if score.current > 0
+        && score.current.is_multiple_of(FLIP_PICKUP_EVERY)
+        && modifier_state.flip_pickup_score != score.current
+    {
+        modifier_state.flip_pickup_score = score.current;
+        // The furthest pipe still ahead, so the pickup scrolls in from off screen
+        let ahead = obstacle_query
+            .iter()
+            .filter(|(obstacle, _)| obstacle.pipe_direction == 1.0 && !obstacle.scored)
+            .max_by(|(_, a), (_, b)| a.translation.x.total_cmp(&b.translation.x));
+        if let Some((obstacle, pipe_transform)) = ahead {
+            let gap_center = pipe_transform.translation.y - get_centered_pipe_position(obstacle.gap_size);
+            commands.spawn((
+                Sprite::from_color(Color::srgb(0.6, 0.3, 0.9), Vec2::splat(FLIP_PICKUP_SIZE * PIXEL_RATIO)),
+                Transform::from_xyz(pipe_transform.translation.x, gap_center, 1.)
+                    .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+                FlipPickup,
+                RunScoped,
+            ));
+        }
+    }
+
+    let Ok((bird_entity, bird_transform, flip)) = bird_query.single_mut() else { return; };
+    let bird = bird_transform.translation.truncate();
+    let mut collected = false;
+    for (entity, mut transform) in pickup_query.iter_mut() {
+        transform.translation.x -= step.dt * tuning.scroll_speed;
+
+        let reach = (Vec2::new(BIRD_WIDTH, BIRD_HEIGHT) + FLIP_PICKUP_SIZE) * PIXEL_RATIO / 2.;
+        let distance = (transform.translation.truncate() - bird).abs();
+        if distance.x < reach.x && distance.y < reach.y {
+            collected = true;
+            commands.entity(entity).despawn();
+        } else if transform.translation.x < -game_manager.window_dimensions.x / 2. {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    if collected {
+        score.log.push(RunEvent::Pickup("gravity flip"));
+    }
+
+    let remaining = match flip {
+        Some(mut flip) => {
+            flip.remaining = if collected { FLIP_DURATION } else { flip.remaining - step.dt };
+            if flip.remaining <= 0.0 {
+                commands.entity(bird_entity).remove::<GravityFlip>();
+            }
+            flip.remaining
+        }
+        None if collected => {
+            commands.entity(bird_entity).insert(GravityFlip { remaining: FLIP_DURATION });
+            FLIP_DURATION
+        }
+        None => 0.0,
+    };
+
+    for mut text in indicator_query.iter_mut() {
+        text.0 = if remaining > 0.0 { format!("GRAVITY FLIPPED  {:.1}s", remaining) } else { String::new() };
+    }
+}
+
+// ---------------------------- CHALLENGE ----------------------------
+// Left/Right sets the start handicap, Up/Down the goal (0 turns it off)
+fn setup_challenge_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        ChallengeMarker,
+    ));
+
+    let usual_death = settings
+        .current_slot
+        .and_then(|slot| saves.load(slot))
+        .and_then(|save| save.profile.usual_death());
+    let entries = [
+        format!("Start Score:  {}", settings.start_score),
+        format!("Score Goal:  {}", settings.score_goal.map_or("None".to_string(), |goal| goal.to_string())),
+        format!("Gauntlet Start:  {}", if settings.gauntlet_start { "On" } else { "Off" }),
+        match usual_death {
+            Some(score) => format!("You die most at pipe {} - practice from there [P]", score + 1),
+            None => "No deaths recorded for this slot yet".to_string(),
+        },
+    ];
+
+    commands.spawn((
+        menu_root_node(),
+        ChallengeMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("CHALLENGE"),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+
+        for entry in entries {
+            parent.spawn((
+                Text::new(entry),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        let ranking = if settings.is_challenge() { "Challenge runs are not ranked" } else { "Runs are ranked" };
+        parent.spawn((
+            Text::new(ranking),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
+                ..default()
+            },
+            TextColor(AQUAMARINE.into()),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+
+        parent.spawn((
+            Text::new(format!(
+                "Start Score -/+{0} [LEFT/RIGHT]   Goal -/+{0} [DOWN/UP]\nGauntlet Start [G]   Clear [X]   Return [ENTER/ESC]",
+                CHALLENGE_STEP,
+            )),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
+        ));
+    });
+}
+
+fn challenge_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings: ResMut<GameSettings>,
+    mut flag: ResMut<OptionsChanged>,
+    nav: Res<NavStack>,
+    saves: Res<SaveWriter>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) || bindings.consume(&mut keyboard, InputAction::MenuConfirm) {
+        next_state.set(nav.back(GameState::Options));
+        return;
+    }
+
+    // Practice start: the score (and with it the gap shrink) picks up just before the usual death
+    if keyboard.just_pressed(KeyCode::KeyP)
+        && let Some(score) = settings.current_slot.and_then(|slot| saves.load(slot)).and_then(|save| save.profile.usual_death())
+    {
+        settings.start_score = score.min(CHALLENGE_MAX);
+        flag.changed = true;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyX) {
+        settings.start_score = 0;
+        settings.score_goal = None;
+        settings.gauntlet_start = false;
+        flag.changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::KeyG) {
+        settings.gauntlet_start = !settings.gauntlet_start;
+        flag.changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        settings.start_score = settings.start_score.saturating_sub(CHALLENGE_STEP);
+        flag.changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        settings.start_score = (settings.start_score + CHALLENGE_STEP).min(CHALLENGE_MAX);
+        flag.changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        settings.score_goal = settings.score_goal.map(|goal| goal.saturating_sub(CHALLENGE_STEP)).filter(|goal| *goal > 0);
+        flag.changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        settings.score_goal = Some((settings.score_goal.unwrap_or(0) + CHALLENGE_STEP).min(CHALLENGE_MAX));
+        flag.changed = true;
+    }
+}
+
+fn refresh_challenge_ui(
+    mut commands: Commands,
+    query: Query<Entity, With<ChallengeMarker>>,
+    mut flag: ResMut<OptionsChanged>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+) {
+    if flag.changed {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+
+        setup_challenge_ui(commands, asset_server, fonts, window_query, settings, saves);
+
+        flag.changed = false;
+    }
+}
+
+// A goal above the start score ends the run in Victory once it's reached
+fn check_score_goal(
+    score: Res<Score>,
+    settings: Res<GameSettings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if settings.score_goal.is_some_and(|goal| goal > settings.start_score && score.current >= goal) {
+        next_state.set(GameState::Victory);
+    }
+}
+
+// ---------------------------- COSMETICS ----------------------------
+impl TrailColor {
+    fn color(self) -> Option<Color> {
+        match self {
+            TrailColor::Off => None,
+            TrailColor::Gold => Some(Color::srgb(1.0, 0.84, 0.0)),
+            TrailColor::Sky => Some(Color::srgb(0.45, 0.75, 1.0)),
+            TrailColor::Rose => Some(Color::srgb(1.0, 0.45, 0.6)),
+            TrailColor::Mint => Some(Color::srgb(0.5, 1.0, 0.75)),
+        }
+    }
+
+    fn price(self) -> u32 {
+        match self {
+            TrailColor::Off => 0,
+            TrailColor::Gold | TrailColor::Sky => 3,
+            TrailColor::Rose | TrailColor::Mint => 5,
+        }
+    }
+}
+
+impl FlapSound {
+    // Themes can ship their own copy under assets/sfx like the other sounds
+    fn file(self) -> &'static str {
+        match self {
+            FlapSound::Classic => "flap.ogg",
+            FlapSound::Pop => "flap_pop.wav",
+            FlapSound::Blip => "flap_blip.wav",
+        }
+    }
+
+    fn price(self) -> u32 {
+        match self {
+            FlapSound::Classic => 0,
+            FlapSound::Pop => 4,
+            FlapSound::Blip => 6,
+        }
+    }
+}
+
+// "trail/Gold", "flap/Pop"
+fn cosmetic_key(row: usize, name: &str) -> String {
+    format!("{}/{}", if row == 0 { "trail" } else { "flap" }, name)
+}
+
+impl PlayerProfile {
+    fn owns_cosmetic(&self, row: usize, name: &str, price: u32) -> bool {
+        price == 0 || self.owned_cosmetics.contains(&cosmetic_key(row, name))
+    }
+}
+
+// (name, price, equipped) of the item browsed on a row
+fn browsed_cosmetic(row: usize, browse: &CosmeticsBrowse, profile: &PlayerProfile) -> (String, u32, bool) {
+    if row == 0 {
+        let trail = TRAIL_COLORS[browse.trail];
+        (format!("{:?}", trail), trail.price(), trail == profile.trail)
+    } else {
+        let sound = FLAP_SOUNDS[browse.flap];
+        (format!("{:?}", sound), sound.price(), sound == profile.flap_sound)
+    }
+}
+
+fn cosmetic_row_text(row: usize, browse: &CosmeticsBrowse, profile: &PlayerProfile) -> String {
+    let (name, price, equipped) = browsed_cosmetic(row, browse, profile);
+    let state = if equipped {
+        "Equipped".to_string()
+    } else if profile.owns_cosmetic(row, &name, price) {
+        "Owned - Equip [ENTER]".to_string()
+    } else if profile.coins >= price {
+        format!("{} coins - Buy [ENTER]", price)
+    } else {
+        format!("{} coins (need {} more)", price, price - profile.coins)
+    };
+    format!("{}:  < {} >   {}", if row == 0 { "Trail" } else { "Flap Sound" }, name, state)
+}
+
+// Level coins go to the slot's wallet; replays and abandoned runs are skipped like everywhere else
+fn bank_run_coins(
+    mut run_ended: MessageReader<RunEnded>,
+    settings: Res<GameSettings>,
+    mut saves: ResMut<SaveWriter>,
+) {
+    for run in run_ended.read().filter(|run| !run.abandoned && run.coins > 0) {
+        let Some(slot) = settings.current_slot else { continue; };
+        let mut save = saves.load(slot).unwrap_or_else(|| new_save_slot(slot, &settings));
+        save.profile.coins += run.coins;
+        saves.write(save);
+    }
+}
+
+// Sprite rotation only: physics and collisions never read it. Uses the sim step's dt, so replays tilt the same way
+fn tilt_bird(mut bird_query: Query<(&mut Bird, &mut Transform, Has<GravityFlip>)>, step: Res<SimStep>, config: Res<GameConfig>) {
+    let Ok((mut bird, mut transform, flipped)) = bird_query.single_mut() else {
+        return;
+    };
+    let target = f32::clamp(bird.velocity / VELOCITY_TO_ROTATION_RATIO, -90., 90.);
+    // Exponential easing per second, then capped so a flap out of a dive can't spin the sprite in one frame.
+    // Smoothing 0 (the test harness) shows the raw angle with no cap
+    if config.rotation_smoothing > 0.0 {
+        let eased = target + (bird.tilt - target) * (-config.rotation_smoothing * step.dt).exp();
+        let max_turn = MAX_TILT_SPEED * step.dt;
+        bird.tilt += (eased - bird.tilt).clamp(-max_turn, max_turn);
+    } else {
+        bird.tilt = target;
+    }
+
+    // A flipped bird flies upside down, so it still dives nose-first toward its "floor"
+    let upside_down = if flipped { Quat::from_rotation_x(std::f32::consts::PI) } else { Quat::IDENTITY };
+    transform.rotation = Quat::from_axis_angle(Vec3::Z, bird.tilt.to_radians()) * upside_down;
+}
+
+fn update_bird_trail(
+    mut commands: Commands,
+    step: Res<SimStep>,
+    tuning: Res<DifficultyTuning>,
+    settings: Res<GameSettings>,
+    mut trail: ResMut<BirdTrail>,
+    bird_query: Query<&Transform, With<Bird>>,
+    mut dot_query: Query<(Entity, &mut Transform, &mut Sprite, &mut TrailDot), Without<Bird>>,
+) {
+    // Dots scroll with the pipes, so the trail streams out behind the bird
+    for (entity, mut transform, mut sprite, mut dot) in dot_query.iter_mut() {
+        dot.age += step.dt;
+        if dot.age >= TRAIL_DOT_LIFETIME {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.x -= step.dt * tuning.scroll_speed;
+        sprite.color.set_alpha(0.8 * (1. - dot.age / TRAIL_DOT_LIFETIME));
+    }
+
+    // Reduce Motion (also from the pause menu) stops new dots; the ones out already fade
+    let Some(color) = trail.color.filter(|_| !settings.reduce_motion) else { return; };
+    let Ok(bird) = bird_query.single() else { return; };
+    trail.since_dot += step.dt;
+    if trail.since_dot < TRAIL_DOT_INTERVAL {
+        return;
+    }
+    trail.since_dot = 0.0;
     commands.spawn((
-    AudioPlayer::new(asset_server.load("35-Lost-Woods.ogg")),
-    PlaybackSettings {
-        volume: Volume::Linear(0.1),
-        ..PlaybackSettings::LOOP
-    },
-    MainMenuMarker,
+        Sprite::from_color(color.with_alpha(0.8), Vec2::splat(TRAIL_DOT_SIZE * PIXEL_RATIO)),
+        Transform::from_translation(bird.translation.with_z(-0.5)),
+        TrailDot { age: 0.0 },
+        RunScoped,
     ));
+}
+
+fn setup_cosmetics_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+    mut browse: ResMut<CosmeticsBrowse>,
+) {
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
 
     commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
             ..default()
         },
-        MainMenuMarker,
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        CosmeticsMarker,
+    ));
+
+    // Browsing starts on what the profile has equipped
+    browse.save = settings.current_slot.and_then(|slot| saves.load(slot));
+    let profile = browse.save.as_ref().map(|save| save.profile.clone());
+    if let Some(profile) = &profile {
+        browse.trail = TRAIL_COLORS.iter().position(|&trail| trail == profile.trail).unwrap_or(0);
+        browse.flap = FLAP_SOUNDS.iter().position(|&sound| sound == profile.flap_sound).unwrap_or(0);
+    }
+
+    commands.spawn((
+        menu_root_node(),
+        CosmeticsMarker,
     ))
     .with_children(|parent| {
-
         parent.spawn((
-            Text::new("FLAPPY BIRD"),
+            Text::new("COSMETICS"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 80.0,
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
                 ..default()
             },
-            TextShadow::default(),
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
             Node {
-                margin: UiRect::all(Val::Px(20.0)),
+                margin: UiRect::all(Val::Px(30.0)),
                 ..default()
             },
         ));
-        
+
         parent.spawn((
-            Text::new("Start Game [Space]"),
+            Text::new(match &profile {
+                Some(profile) => format!("Coins:  {}   (picked up on level courses)", profile.coins),
+                None => "Select a save slot to buy cosmetics".to_string(),
+            }),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
+            TextColor(Color::srgb(1.0, 0.84, 0.0)),
             TextShadow::default(),
-            TextColor(AQUAMARINE.into()),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+            CosmeticsStatus,
         ));
 
+        let Some(profile) = &profile else { return; };
+        for row in 0..2 {
+            parent.spawn((
+                Text::new(cosmetic_row_text(row, &browse, profile)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+                CosmeticRow(row),
+            ));
+        }
+
         parent.spawn((
-            Text::new("Options [O]"),
+            Text::new("Navigate [UP/DOWN]  Browse [LEFT/RIGHT]\nBuy / Equip [ENTER]   Return [ESC]"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
             TextShadow::default(),
-            TextColor(AQUAMARINE.into()),
+            Node {
+                margin: UiRect::top(Val::Px(40.0)),
+                ..default()
+            },
         ));
+    });
+}
+
+fn cosmetics_system(
+    mut commands: Commands,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_state: ResMut<NextState<GameState>>,
+    settings: Res<GameSettings>,
+    mut saves: ResMut<SaveWriter>,
+    mut cursor: ResMut<MenuCursor>,
+    mut browse: ResMut<CosmeticsBrowse>,
+    asset_server: Res<AssetServer>,
+    audio: Res<AudioSettings>,
+    mut rows: Query<(&CosmeticRow, &mut Text, &mut TextColor), Without<CosmeticsStatus>>,
+    mut status: Query<&mut Text, With<CosmeticsStatus>>,
+    nav: Res<NavStack>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(nav.back(GameState::Options));
+        return;
+    }
+    let Some(slot) = settings.current_slot else { return; };
+    let mut save = browse.save.take().unwrap_or_else(|| new_save_slot(slot, &settings));
+    let mut changed = false;
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) || keyboard.just_pressed(KeyCode::ArrowDown) {
+        cursor.index = 1 - cursor.index.min(1);
+        changed = true;
+    }
+    let step = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        1
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -1
+    } else {
+        0
+    };
+    if step != 0 {
+        changed = true;
+        if cursor.index == 0 {
+            browse.trail = (browse.trail as i32 + step).rem_euclid(TRAIL_COLORS.len() as i32) as usize;
+        } else {
+            browse.flap = (browse.flap as i32 + step).rem_euclid(FLAP_SOUNDS.len() as i32) as usize;
+            // Preview, routed like an in-game flap
+            commands.spawn((
+                AudioPlayer::new(settings.selected_theme.sound(&asset_server, FLAP_SOUNDS[browse.flap].file())),
+                PlaybackSettings {
+                    volume: audio.sfx(0.1),
+                    ..PlaybackSettings::DESPAWN
+                },
+                SfxKind::Flap,
+            ));
+        }
+    }
+
+    if bindings.consume(&mut keyboard, InputAction::MenuConfirm) {
+        let row = cursor.index.min(1);
+        let (name, price, equipped) = browsed_cosmetic(row, &browse, &save.profile);
+        let owned = save.profile.owns_cosmetic(row, &name, price);
+        if !equipped && (owned || save.profile.coins >= price) {
+            if !owned {
+                save.profile.coins -= price;
+                save.profile.owned_cosmetics.push(cosmetic_key(row, &name));
+            }
+            if row == 0 {
+                save.profile.trail = TRAIL_COLORS[browse.trail];
+            } else {
+                save.profile.flap_sound = FLAP_SOUNDS[browse.flap];
+            }
+            saves.write(save.clone());
+            changed = true;
+        }
+    }
+
+    if changed || cursor.is_changed() {
+        for (row, mut text, mut color) in rows.iter_mut() {
+            text.0 = cosmetic_row_text(row.0, &browse, &save.profile);
+            color.0 = if row.0 == cursor.index {
+                AQUAMARINE.into()
+            } else {
+                Color::srgb(1.0, 0.992, 0.816)
+            };
+        }
+        for mut text in status.iter_mut() {
+            text.0 = format!("Coins:  {}   (picked up on level courses)", save.profile.coins);
+        }
+    }
+    browse.save = Some(save);
+}
+
+// ---------------------------- TEXT ENTRY ----------------------------
+// Applies typed characters and Backspace to `text`, up to max_len characters.
+// Returns whether anything changed
+fn edit_text(text: &mut String, events: &[KeyboardInput], max_len: usize) -> bool {
+    let mut changed = false;
+    for event in events.iter().filter(|event| event.state.is_pressed()) {
+        match &event.logical_key {
+            Key::Backspace => changed |= text.pop().is_some(),
+            Key::Space if text.chars().count() < max_len => {
+                text.push(' ');
+                changed = true;
+            }
+            Key::Character(typed) => {
+                for c in typed.chars().filter(|c| !c.is_control()) {
+                    if text.chars().count() < max_len {
+                        text.push(c);
+                        changed = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    changed
+}
+
+fn name_prompt_line(prompt: &NamePrompt) -> String {
+    format!(
+        "Leaderboard name:  {}_\nSave [ENTER]   {} [SPACE/ESC]",
+        prompt.text,
+        if prompt.edited { "Cancel" } else { "Keep" },
+    )
+}
+
+// Ranked runs that make the top of the leaderboard can be credited to whoever played them;
+// the run itself is already saved, only the shown name waits for the answer
+fn open_name_prompt(
+    mut commands: Commands,
+    fonts: Res<UiFonts>,
+    score: Res<Score>,
+    settings: Res<GameSettings>,
+    audio: Res<AudioSettings>,
+    replay: Option<Res<ReplayPlayback>>,
+    saves: Res<SaveWriter>,
+) {
+    commands.remove_resource::<NamePrompt>();
+    let Some(slot) = settings.current_slot else { return; };
+    if !audio.name_prompt || score.current == 0 || !is_ranked(&settings, &score, replay.is_some()) || settings.selected_mode == GameMode::Tournament {
+        return;
+    }
+    let above = (1..=3u8)
+        .filter(|other| *other != slot)
+        .filter_map(|other| saves.load(other))
+        .filter(|save| save.score > score.current)
+        .count();
+    if above >= NAME_PROMPT_TOP {
+        return;
+    }
+    let Some(save) = saves.load(slot) else { return; };
 
+    let prompt = NamePrompt {
+        slot,
+        default: save.profile.name.clone(),
+        text: save.profile.name,
+        edited: false,
+    };
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(12.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(5),
+        GameOverMarker,
+    ))
+    .with_children(|parent| {
         parent.spawn((
-            Text::new("Leaderboard [L]"),
+            Text::new(name_prompt_line(&prompt)),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
+            TextLayout::new_with_justify(Justify::Center),
+            TextColor(Color::srgb(1.0, 0.84, 0.0)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.7)),
             TextShadow::default(),
-            TextColor(AQUAMARINE.into()),
+            NamePromptText,
         ));
+    });
+    commands.insert_resource(prompt);
+}
+
+fn name_prompt_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut key_events: MessageReader<KeyboardInput>,
+    prompt: Option<ResMut<NamePrompt>>,
+    mut saves: ResMut<SaveWriter>,
+    mut text_query: Query<(Entity, &mut Text, &ChildOf), With<NamePromptText>>,
+) {
+    // Always drained, so keys from before the prompt never show up in it
+    let events: Vec<KeyboardInput> = key_events.read().cloned().collect();
+    let Some(mut prompt) = prompt else { return; };
+
+    let cancel = keyboard.just_pressed(KeyCode::Escape) || (!prompt.edited && keyboard.just_pressed(KeyCode::Space));
+    let save = keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::NumpadEnter);
+    if cancel || save {
+        let name = prompt.text.trim();
+        if save && !name.is_empty() && name != prompt.default
+            && let Some(mut slot) = saves.load(prompt.slot)
+        {
+            slot.run_name = Some(name.to_string());
+            saves.write(slot);
+        }
+        for (_, _, parent) in &text_query {
+            commands.entity(parent.parent()).despawn();
+        }
+        commands.remove_resource::<NamePrompt>();
+        return;
+    }
+
+    if edit_text(&mut prompt.text, &events, NAME_MAX_LEN) {
+        prompt.edited = true;
+        for (_, mut text, _) in text_query.iter_mut() {
+            text.0 = name_prompt_line(&prompt);
+        }
+    }
+}
+
+// ---------------------------- SCORE CARD ----------------------------
+// Offscreen card layout waiting to be drawn and saved to `path`
+#[derive(Resource)]
+struct ScoreCardCapture {
+    image: Handle<Image>,
+    camera: Entity,
+    root: Entity,
+    frames: u32,
+    path: String,
+}
+
+// Lays the card out for a camera that renders into an image instead of the window.
+// Returns the path the card will be saved to
+fn spawn_score_card(
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    asset_server: &AssetServer,
+    fonts: &UiFonts,
+    palette: ThemePalette,
+    settings: &GameSettings,
+    score: u32,
+    save: Option<SaveSlot>,
+) -> Result<String, String> {
+    fs::create_dir_all(CARD_DIR).map_err(|e| e.to_string())?;
+    let path = format!("{}/card_{}.png", CARD_DIR, utc_timestamp());
+    let image = images.add(Image::new_target_texture(CARD_SIZE.x, CARD_SIZE.y, TextureFormat::Rgba8UnormSrgb));
+
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                target: image.clone().into(),
+                order: -1,
+                clear_color: ClearColorConfig::Custom(palette.background),
+                ..default()
+            },
+            RenderLayers::layer(CARD_LAYER),
+        ))
+        .id();
 
+    let (name, avatar) = save.map_or(("Guest".to_string(), None), |save| {
+        (save.run_name.unwrap_or(save.profile.name), Some(save.profile.avatar))
     });
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let mut details = format!("{:?} - {:?}", settings.selected_mode, settings.selected_difficulty);
+    let flags = run_flags(settings);
+    if !flags.is_empty() {
+        details = format!("{}   [{}]", details, flags.join(", ").to_uppercase());
+    }
+
+    // Fixed sizes: the card is always CARD_SIZE, whatever the UI scale
+    let text = |text: String, font: &Handle<Font>, size: f32, color: Color| {
+        (
+            Text::new(text),
+            TextFont { font: font.clone(), font_size: size, ..default() },
+            TextColor(color),
+            palette.text_shadow(),
+        )
+    };
+    let root = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                border: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BorderColor::all(palette.accent),
+            UiTargetCamera(camera),
+        ))
+        .with_children(|card| {
+            card.spawn(text("FLAPPY BIRD".to_string(), &fonts.title, 40.0, palette.hud_text));
+            card.spawn(Node { align_items: AlignItems::Center, ..default() }).with_children(|row| {
+                if let Some(avatar) = avatar {
+                    spawn_avatar(row, asset_server, avatar, 32.0);
+                }
+                row.spawn(text(name, &fonts.body, 28.0, palette.hud_text));
+            });
+            card.spawn(text(score.to_string(), &fonts.title, 72.0, palette.accent));
+            card.spawn(text(details, &fonts.body, 20.0, palette.muted));
+            card.spawn(text(format!("{:04}-{:02}-{:02}", year, month, day), &fonts.body, 18.0, palette.muted));
+        })
+        .id();
+
+    commands.insert_resource(ScoreCardCapture { image, camera, root, frames: CARD_RENDER_FRAMES, path: path.clone() });
+    Ok(path)
+}
+
+// Saves the card once it has been drawn, then removes the offscreen camera and layout
+fn capture_score_card(mut commands: Commands, mut capture: ResMut<ScoreCardCapture>) {
+    if capture.frames > 0 {
+        capture.frames -= 1;
+        return;
+    }
+    let path = capture.path.clone();
+    commands
+        .spawn(Screenshot::image(capture.image.clone()))
+        .observe(move |captured: On<ScreenshotCaptured>, mut status: ResMut<RunCodeStatus>| {
+            let saved = captured
+                .image
+                .clone()
+                .try_into_dynamic()
+                .map_err(|e| e.to_string())
+                .and_then(|image| image.to_rgb8().save(&path).map_err(|e| e.to_string()));
+            status.message = Some(match saved {
+                Ok(()) => format!("Score card saved to {}", path),
+                Err(e) => format!("Failed to save score card: {}", e),
+            });
+        });
+    commands.entity(capture.camera).despawn();
+    commands.entity(capture.root).despawn();
+    commands.remove_resource::<ScoreCardCapture>();
+}
+
+fn score_card_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    settings: Res<GameSettings>,
+    score: Res<Score>,
+    saves: Res<SaveWriter>,
+    palette: Option<Res<ThemePalette>>,
+    prompt: Option<Res<NamePrompt>>,
+    capture: Option<Res<ScoreCardCapture>>,
+    mut status: ResMut<RunCodeStatus>,
+) {
+    if prompt.is_some() || capture.is_some() || !keyboard.just_pressed(KeyCode::KeyK) {
+        return;
+    }
+    let palette = palette.map_or_else(|| settings.selected_theme.palette(), |palette| *palette);
+    let save = settings.current_slot.and_then(|slot| saves.load(slot));
+    match spawn_score_card(&mut commands, &mut images, &asset_server, &fonts, palette, &settings, score.current, save) {
+        Ok(_) => status.message = Some("Saving score card...".to_string()),
+        Err(e) => status.message = Some(format!("Failed to save score card: {}", e)),
+    }
+}
+
+fn mark_game_over_seen(mut commands: Commands) {
+    commands.insert_resource(GameOverSeen(true));
+}
+
+// With the setting on, every finished run (not replays) gets a card without pressing K
+fn auto_score_card(
+    mut commands: Commands,
+    audio: Res<AudioSettings>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    settings: Res<GameSettings>,
+    score: Res<Score>,
+    saves: Res<SaveWriter>,
+    palette: Option<Res<ThemePalette>>,
+    replay: Option<Res<ReplayPlayback>>,
+    mut status: ResMut<RunCodeStatus>,
+) {
+    if !audio.auto_score_card || replay.is_some() {
+        return;
+    }
+    let palette = palette.map_or_else(|| settings.selected_theme.palette(), |palette| *palette);
+    let save = settings.current_slot.and_then(|slot| saves.load(slot));
+    if let Err(e) = spawn_score_card(&mut commands, &mut images, &asset_server, &fonts, palette, &settings, score.current, save) {
+        status.message = Some(format!("Failed to save score card: {}", e));
+    }
+}
+
+// ---------------------------- RUN RECOVERY ----------------------------
+// Snapshot of the run in progress. It only stays on disk if the game never got to end the run
+#[derive(Serialize, Deserialize)]
+struct RunMarker {
+    slot: Option<u8>,
+    score: u32,
+    // Simulated seconds played
+    duration: f32,
+    mode: GameMode,
+    difficulty: Difficulty,
+    flags: Vec<String>,
+    // Whether the run would have updated the profile
+    ranked: bool,
+}
+
+// One write in flight at a time; a slow disk skips a snapshot instead of queueing them
+#[derive(Resource, Default)]
+struct RunMarkerWriter {
+    since_write: f32,
+    task: Option<Task<()>>,
+}
+
+// Score of a run found interrupted at startup, until the main menu mentions it
+#[derive(Resource, Default)]
+struct InterruptedRun {
+    score: Option<u32>,
+}
+
+fn write_run_marker(
+    time: Res<Time>,
+    score: Res<Score>,
+    settings: Res<GameSettings>,
+    replay: Option<Res<ReplayPlayback>>,
+    mut writer: ResMut<RunMarkerWriter>,
+) {
+    if replay.is_some() {
+        return;
+    }
+    writer.since_write += time.delta_secs();
+    if writer.since_write < RUN_MARKER_INTERVAL {
+        return;
+    }
+    if let Some(task) = writer.task.as_mut()
+        && check_ready(task).is_none()
+    {
+        return;
+    }
+    writer.since_write = 0.0;
+
+    let marker = RunMarker {
+        slot: settings.current_slot,
+        score: score.current,
+        duration: score.log.elapsed,
+        mode: settings.selected_mode,
+        difficulty: settings.selected_difficulty,
+        flags: run_flags(&settings),
+        ranked: is_ranked(&settings, &score, false),
+    };
+    let json = match serde_json::to_string(&marker) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("Failed to serialize run marker: {}", e);
+            return;
+        }
+    };
+    writer.task = Some(IoTaskPool::get().spawn(async move {
+        // Another instance owns the saves folder, or it's session-only
+        if !owns_save_lock() {
+            return;
+        }
+        if let Err(e) = write_atomic(RUN_MARKER_PATH, &json) {
+            eprintln!("Failed to write run marker: {}", e);
+        }
+    }));
+}
+
+// The run ended normally; a snapshot still in flight lands first so it can't outlive the delete
+fn clear_run_marker(mut writer: ResMut<RunMarkerWriter>) {
+    if let Some(task) = writer.task.take() {
+        block_on(task);
+    }
+    writer.since_write = 0.0;
+    if Path::new(RUN_MARKER_PATH).exists()
+        && let Err(e) = fs::remove_file(RUN_MARKER_PATH)
+    {
+        eprintln!("Failed to remove run marker: {}", e);
+    }
+}
 
-    // Display current save slot in top right
-    let slot_text = if let Some(slot_num) = settings.current_slot {
-        if let Some(save_data) = load_save_slot(slot_num as u32) {
-            format!("Slot {}: {}", slot_num, save_data.profile.name)
-        } else {
-            format!("Slot {}: New", slot_num)
+// Folds a run left behind by a crash into its slot: it counts as a game and goes into the
+// history flagged as interrupted, but never touches high scores
+fn recover_interrupted_run(saves: &SaveWriter) -> InterruptedRun {
+    if !owns_save_lock() {
+        return InterruptedRun::default();
+    }
+    let Ok(contents) = fs::read_to_string(RUN_MARKER_PATH) else { return InterruptedRun::default(); };
+    if let Err(e) = fs::remove_file(RUN_MARKER_PATH) {
+        eprintln!("Failed to remove run marker: {}", e);
+    }
+    let marker: RunMarker = match serde_json::from_str(&contents) {
+        Ok(marker) => marker,
+        Err(e) => {
+            eprintln!("Ignoring {}: {}", RUN_MARKER_PATH, e);
+            return InterruptedRun::default();
         }
-    } else {
-        "No slot selected".to_string()
     };
 
-    commands.spawn((
-        Text::new(slot_text),
-        TextFont {
-            font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-            font_size: 18.0,
-            ..default()
-        },
-        TextColor(Color::srgb(1.0, 0.992, 0.816)),
-        TextShadow::default(),
-        TextBackgroundColor(Color::BLACK.with_alpha(0.3)),
-        Node {
-            position_type: PositionType::Absolute,
-            top: Val::Px(10.0),
-            right: Val::Px(10.0),
-            ..default()
-        },
-        MainMenuMarker,
-    ));
+    if let Some(slot) = marker.slot.filter(|_| marker.ranked) {
+        let store = saves.store();
+        let mut save = store.load_slot(slot).unwrap_or_else(|| new_save_slot(slot, &GameSettings::default()));
+        let profile = &mut save.profile;
+        profile.total_games += 1;
+        profile.push_history(marker.score, true, false);
+        profile.average_score = ((profile.average_score * (profile.total_games - 1) as f32) + marker.score as f32) / profile.total_games as f32;
+        if let Err(e) = store.write_slot(&save) {
+            eprintln!("Failed to save slot {}: {}", slot, e);
+        }
+    }
+    InterruptedRun { score: Some(marker.score) }
 }
 
-fn main_menu_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    settings: Res<GameSettings>,
-    mut commands: Commands,  // Add Commands parameter
+fn show_interrupted_run(
+    mut commands: Commands,
+    fonts: Res<UiFonts>,
+    mut interrupted: ResMut<InterruptedRun>,
+    toast_query: Query<Entity, With<Toast>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        if settings.current_slot.is_none() {
-            // Set origin to MainMenu before transitioning
-            commands.insert_resource(SaveSelectOrigin {
-                origin_state: GameState::MainMenu,
-            });
-            next_state.set(GameState::SaveSelect);
-        } else {
-            next_state.set(GameState::Playing);
+    if let Some(score) = interrupted.score.take() {
+        spawn_toast(&mut commands, &fonts, &toast_query, format!("Your last run ended unexpectedly at {} points", score), Color::srgb(1.0, 0.84, 0.0));
+    }
+}
+
+// ---------------------------- WEEKLY CHALLENGE ----------------------------
+// Mode, difficulty, mutators and seed for one ISO week
+#[derive(Clone, Debug, PartialEq)]
+struct WeeklyChallenge {
+    year: i64,
+    week: u32,
+    mode: GameMode,
+    difficulty: Difficulty,
+    mutators: Vec<&'static str>,
+    seed: u64,
+}
+
+// Pure: the challenge only depends on the week, so every player gets the same one.
+// splitmix64 on the week number keeps it stable across builds and platforms
+fn weekly_challenge(year: i64, week: u32) -> WeeklyChallenge {
+    let mut state = (year as u64).wrapping_mul(100).wrapping_add(week as u64);
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    let mode = WEEKLY_MODES[(next() % WEEKLY_MODES.len() as u64) as usize];
+    let difficulty = WEEKLY_DIFFICULTIES[(next() % WEEKLY_DIFFICULTIES.len() as u64) as usize];
+
+    let count = WEEKLY_MUTATORS.len() as u64;
+    let first = (next() % count) as usize;
+    let mut mutators = vec![WEEKLY_MUTATORS[first]];
+    if next() % 2 == 1 {
+        // Any mutator but the first; a second bird size is dropped instead
+        let second = (first + 1 + (next() % (count - 1)) as usize) % count as usize;
+        if first.max(second) > 1 {
+            mutators.push(WEEKLY_MUTATORS[second]);
         }
     }
 
-    if keyboard.just_pressed(KeyCode::KeyO) {
-        next_state.set(GameState::Options);
+    // Short seeds are easy to read out and compare
+    let seed = next() >> 48;
+    WeeklyChallenge { year, week, mode, difficulty, mutators, seed }
+}
+
+fn current_weekly_challenge() -> WeeklyChallenge {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (year, week) = iso_week((secs / 86_400) as i64);
+    weekly_challenge(year, week)
+}
+
+// Drops all but the newest WEEKLY_KEEP_WEEKS weeks; keys sort by date
+fn keep_recent_weeks<T>(weeks: &mut std::collections::HashMap<String, T>) {
+    if weeks.len() <= WEEKLY_KEEP_WEEKS {
+        return;
     }
+    let mut keys: Vec<String> = weeks.keys().cloned().collect();
+    keys.sort_unstable();
+    for key in &keys[..keys.len() - WEEKLY_KEEP_WEEKS] {
+        weeks.remove(key);
+    }
+}
 
-    if keyboard.just_pressed(KeyCode::KeyL) {
-        next_state.set(GameState::Leaderboard);
+// "2026-W07" back to (2026, 7)
+fn parse_week_key(key: &str) -> Option<(i64, u32)> {
+    let (year, week) = key.split_once("-W")?;
+    Some((year.parse().ok()?, week.parse().ok()?))
+}
+
+impl WeeklyChallenge {
+    fn key(&self) -> String {
+        format!("{}-W{:02}", self.year, self.week)
+    }
+
+    // Whether the run settings are exactly what apply() sets; anything else is a different run
+    fn matches(&self, settings: &GameSettings) -> bool {
+        let mut expected = settings.clone();
+        self.apply(&mut expected);
+        expected.selected_mode == settings.selected_mode
+            && expected.selected_difficulty == settings.selected_difficulty
+            && expected.seed == settings.seed
+            && expected.bird_size == settings.bird_size
+            && expected.modifiers == settings.modifiers
+            && expected.custom_tuning == settings.custom_tuning
+            && expected.spawn_strategy == settings.spawn_strategy
+            && expected.overtime == settings.overtime
+            && expected.start_score == settings.start_score
+            && expected.score_goal == settings.score_goal
+            && expected.gauntlet_start == settings.gauntlet_start
+            && expected.fill_window == settings.fill_window
+            && expected.camera_zoom == settings.camera_zoom
+            && !settings.gap_telegraph
+    }
+
+    fn describe(&self) -> String {
+        let mode = match self.mode {
+            GameMode::TimeAttack => "Time Attack".to_string(),
+            mode => format!("{:?}", mode),
+        };
+        format!("Week {}: {}, {:?}, {}, seed 0x{:04X}", self.week, mode, self.difficulty, self.mutators.join(" + "), self.seed)
+    }
+
+    // Everything that shapes the course is fixed; view and accessibility settings are kept
+    fn apply(&self, settings: &mut GameSettings) {
+        settings.selected_mode = self.mode;
+        settings.selected_difficulty = self.difficulty;
+        settings.seed = Some(self.seed);
+        settings.bird_size = BirdSize::Normal;
+        settings.modifiers = RunModifiers::default();
+        settings.custom_tuning = CustomTuning::default();
+        settings.spawn_strategy = SpawnStrategy::Recycle;
+        settings.overtime = false;
+        settings.start_score = 0;
+        settings.score_goal = None;
+        settings.gauntlet_start = false;
+        settings.selected_level = None;
+        settings.fill_window = false;
+        settings.camera_zoom = 1.0;
+        for mutator in &self.mutators {
+            match *mutator {
+                "Tiny Bird" => settings.bird_size = BirdSize::Tiny,
+                "Giant Bird" => settings.bird_size = BirdSize::Giant,
+                "Wind" => settings.modifiers.wind = true,
+                "Mirror" => settings.modifiers.mirror = true,
+                "Flap Cooldown" => settings.modifiers.flap_cooldown = true,
+                "Screen Wrap" => settings.modifiers.screen_wrap = true,
+                _ => settings.modifiers.gravity_flip = true,
+            }
+        }
     }
 }
 
-fn options_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut commands: Commands,  // Add Commands parameter
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::MainMenu);
-        return;
+// saves/weekly.json, loaded once at startup and written in the background after each new best
+#[derive(Resource, Serialize, Deserialize, Default)]
+struct WeeklyBoard {
+    #[serde(default)]
+    weeks: std::collections::HashMap<String, WeeklyRecord>,
+    // One write in flight at a time, so an older board can never land after a newer one
+    #[serde(skip)]
+    task: Option<Task<()>>,
+    // Changed since the in-flight write started
+    #[serde(skip)]
+    unsaved: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct WeeklyRecord {
+    score: u32,
+    name: String,
+    slot: u8,
+}
+
+fn load_weekly_board(saves: &SaveWriter) -> WeeklyBoard {
+    saves.store().read_file(WEEKLY_PATH)
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+impl WeeklyBoard {
+    // Keeps the record if it beats the week's best; returns whether it did
+    fn submit(&mut self, saves: &SaveWriter, key: &str, record: WeeklyRecord) -> bool {
+        if self.weeks.get(key).is_some_and(|best| best.score >= record.score) {
+            return false;
+        }
+        self.weeks.insert(key.to_string(), record);
+        self.unsaved = true;
+        self.write(saves);
+        true
     }
-    
-    for (key, state) in [
-        (KeyCode::Digit1, GameState::SaveSelect),
-        (KeyCode::Digit2, GameState::ModeSelect),
-        (KeyCode::Digit3, GameState::DifficultySelect),
-        (KeyCode::Digit4, GameState::ThemeSelect),
-        (KeyCode::Digit5, GameState::SkinSelect),
-    ] {
-        if keyboard.just_pressed(key) {
-            if state == GameState::SaveSelect {
-                // Set origin to Options before transitioning
-                commands.insert_resource(SaveSelectOrigin {
-                    origin_state: GameState::Options,
-                });
+
+    // Starts writing the board unless a write is still in flight; write_weekly_board picks
+    // up the change once it lands
+    fn write(&mut self, saves: &SaveWriter) {
+        if let Some(task) = &mut self.task {
+            if check_ready(task).is_none() {
+                return;
             }
-            next_state.set(state);
+            self.task = None;
+        }
+        if !self.unsaved {
             return;
         }
+        self.unsaved = false;
+        keep_recent_weeks(&mut self.weeks);
+        match serde_json::to_string_pretty(self) {
+            // Same backend as the slots, so a read-only saves folder keeps it for the session
+            Ok(json) => {
+                let store = saves.store.clone();
+                self.task = Some(IoTaskPool::get().spawn(async move {
+                    if let Err(e) = store.write_file(WEEKLY_PATH, json) {
+                        eprintln!("Failed to save weekly board: {}", e);
+                    }
+                }));
+            }
+            Err(e) => eprintln!("Failed to serialize weekly board: {}", e),
+        }
+    }
+
+    // Blocks until the newest board is on disk, for exit
+    fn flush(&mut self, saves: &SaveWriter) {
+        while let Some(task) = self.task.take() {
+            block_on(task);
+            self.write(saves);
+        }
     }
 }
 
-fn setup_options_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>, settings: Res<GameSettings>) {
-    let window = window_query.single().expect("Missing primary window");
-    let window_width = window.width();
-    let window_height = window.height();
+fn write_weekly_board(mut board: ResMut<WeeklyBoard>, saves: Res<SaveWriter>) {
+    let board = board.bypass_change_detection();
+    if board.unsaved {
+        board.write(&saves);
+    }
+}
 
-    commands.spawn((
-        Sprite {
-            image: asset_server.load("Background2.png"),
-            custom_size: Some(Vec2::new(window_width, window_height)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-        Background,
-        OptionsMarker,
-    ));
+// Every slot's best for the given week, highest first
+fn load_weekly_leaderboard(saves: &SaveWriter, challenge: &WeeklyChallenge) -> Vec<LeaderboardEntry> {
+    let key = challenge.key();
+    let mut entries: Vec<LeaderboardEntry> = (1..=3)
+        .filter_map(|slot| saves.load(slot))
+        .filter_map(|save| {
+            let score = *save.profile.weekly_bests.get(&key)?;
+            Some(LeaderboardEntry {
+                name: save.profile.name,
+                score,
+                mode: challenge.mode,
+                difficulty: challenge.difficulty,
+                flags: Vec::new(),
+                avatar: save.profile.avatar,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+    entries
+}
 
-    commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
-        OptionsMarker,
-    ))
-    .with_children(|parent| {
-        parent.spawn((
-            Text::new("OPTIONS"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(30.0)),
-                ..default()
-            },
-        ));
-        
-        // Display current settings
-        let slot_text = if let Some(slot_num) = settings.current_slot {
-            if let Some(save_data) = load_save_slot(slot_num as u32) {
-                format!("Slot {}: {}", slot_num, save_data.profile.name)
-            } else {
-                format!("Slot {}: New", slot_num)
-            }
-        } else {
-            "No slot selected".to_string()
-        };
-        
-        parent.spawn((
-            Text::new(format!("Saves:  {}", slot_text)),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new(format!("Game Mode:  {:?}", settings.selected_mode)),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new(format!("Difficulty:  {:?}", settings.selected_difficulty)),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new(format!("Theme:  {:?}", settings.selected_theme)),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new(format!("Skin:  {:?}", settings.selected_skin)),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("\nSelect Option [1/2/3/4/5]\nReturn to Main Menu [ESC]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::top(Val::Px(40.0)),
-                ..default()
-            },
-        ));
-    });
+// Re-applied on every start so retries keep the challenge (bird size is cleared after each run)
+fn apply_weekly_challenge(mut settings: ResMut<GameSettings>) {
+    let Some((year, week)) = settings.weekly.as_deref().and_then(parse_week_key) else { return; };
+    weekly_challenge(year, week).apply(&mut settings);
 }
 
-// Save Select UI - UPDATED with background and font
-fn setup_save_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>) {
+fn clear_weekly(mut commands: Commands, restore: Option<Res<WeeklyRestore>>, mut settings: ResMut<GameSettings>) {
+    if let Some(restore) = restore {
+        *settings = restore.0.clone();
+        commands.remove_resource::<WeeklyRestore>();
+    }
+}
+
+// Keeps the slot's best for the week played and submits it to the machine's weekly board.
+// A slot without a save gets one from the settings the challenge replaced
+fn record_weekly_result(
+    mut commands: Commands,
+    mut run_ended: MessageReader<RunEnded>,
+    settings: Res<GameSettings>,
+    restore: Option<Res<WeeklyRestore>>,
+    mut saves: ResMut<SaveWriter>,
+    mut board: ResMut<WeeklyBoard>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    for run in run_ended.read().filter(|run| !run.abandoned) {
+        let (Some(key), Some(slot)) = (settings.weekly.as_ref(), settings.current_slot) else { continue; };
+        if !run.weekly_match {
+            spawn_toast(&mut commands, &fonts, &toast_query, "Run doesn't match this week's challenge - not submitted".to_string(), Color::srgb(0.9, 0.35, 0.3));
+            continue;
+        }
+        let mut save = saves
+            .load(slot)
+            .unwrap_or_else(|| new_save_slot(slot, restore.as_ref().map_or(&*settings, |restore| &restore.0)));
+        let record = WeeklyRecord { score: run.score, name: save.profile.name.clone(), slot };
+        if board.submit(&saves, key, record) {
+            spawn_toast(&mut commands, &fonts, &toast_query, format!("Weekly board best: {}", run.score), Color::srgb(1.0, 0.84, 0.0));
+        }
+        let previous = save.profile.weekly_bests.get(key).copied();
+        if previous.is_some_and(|best| best >= run.score) {
+            continue;
+        }
+        save.profile.weekly_bests.insert(key.clone(), run.score);
+        save.profile.compact_history();
+        saves.write(save);
+        if previous.is_some() {
+            spawn_toast(&mut commands, &fonts, &toast_query, format!("New weekly best: {}", run.score), Color::srgb(1.0, 0.84, 0.0));
+        }
+    }
+}
+
+fn setup_weekly_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    saves: Res<SaveWriter>,
+    board: Res<WeeklyBoard>,
+    bindings: Res<KeyBindings>,
+) {
     let window = window_query.single().expect("Missing primary window");
     let window_width = window.width();
     let window_height = window.height();
@@ -970,31 +11266,53 @@ fn setup_save_select_ui(mut commands: Commands, asset_server: Res<AssetServer>,
     commands.spawn((
         Sprite {
             image: asset_server.load("Background2.png"),
-            custom_size: Some(Vec2::new(window_width, window_height)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-        Background,
-        SaveSelectMarker,
-    ));
-
-    commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
+            custom_size: Some(Vec2::new(window_width, window_height)),
             ..default()
         },
-        SaveSelectMarker,
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        WeeklyMarker,
+    ));
+
+    let challenge = current_weekly_challenge();
+    let key = challenge.key();
+    let bests = settings
+        .current_slot
+        .and_then(|slot| saves.load(slot))
+        .map(|save| save.profile.weekly_bests)
+        .unwrap_or_default();
+    let best = match (settings.current_slot, bests.get(&key)) {
+        (None, _) => "Select a save slot to play".to_string(),
+        (Some(_), Some(best)) => format!("Your best this week:  {}", best),
+        (Some(_), None) => "Not played this week yet".to_string(),
+    };
+    let best = match board.weeks.get(&key) {
+        Some(record) => format!("{}\nBest on this machine:  {} ({}, slot {})", best, record.score, record.name, record.slot),
+        None => best,
+    };
+
+    // Newest past weeks first; keys sort by date
+    let mut history: Vec<(&String, &u32)> = bests.iter().filter(|(week, _)| **week != key).collect();
+    history.sort_by(|a, b| b.0.cmp(a.0));
+    let history: Vec<String> = history
+        .into_iter()
+        .take(WEEKLY_HISTORY_LEN)
+        .map(|(week, score)| match parse_week_key(week) {
+            Some((year, number)) => format!("{} - {} pts   {}", week, score, weekly_challenge(year, number).describe()),
+            None => format!("{} - {} pts", week, score),
+        })
+        .collect();
+
+    commands.spawn((
+        menu_root_node(),
+        WeeklyMarker,
     ))
     .with_children(|parent| {
         parent.spawn((
-            Text::new("SAVES"),
+            Text::new("WEEKLY CHALLENGE"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
+                font: fonts.title.clone(),
+                font_size: fonts.heading(56.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
@@ -1004,38 +11322,46 @@ fn setup_save_select_ui(mut commands: Commands, asset_server: Res<AssetServer>,
                 ..default()
             },
         ));
-        
-        for slot_num in 1..=3 {
-            let save_data = load_save_slot(slot_num);
-            let text = if let Some(save) = save_data {
-                format!("[{}] Slot {}: {} - High Score: {}", 
-                    slot_num, slot_num, save.profile.name, save.profile.high_score)
-            } else {
-                format!("[{}] Slot {}: Empty (New Player)", slot_num, slot_num)
-            };
-            
+
+        for (entry, color) in [(challenge.describe(), AQUAMARINE.into()), (best, Color::srgb(1.0, 0.84, 0.0))] {
             parent.spawn((
-                Text::new(text),
+                Text::new(entry),
                 TextFont {
-                    font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                    font_size: 32.0,
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
                     ..default()
                 },
-                TextColor(Color::srgb(1.0, 0.992, 0.816)),
-                TextShadow::default(),
+                TextColor(color),
                 TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
                 Node {
-                    margin: UiRect::all(Val::Px(15.0)),
+                    margin: UiRect::all(fonts.margin(10.0)),
                     ..default()
                 },
             ));
         }
-        
+
+        parent.spawn((
+            Text::new(if history.is_empty() { "No past weeks played".to_string() } else { format!("Past weeks:\n{}", history.join("\n")) }),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(18.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+
         parent.spawn((
-            Text::new("\nSelect a slot [1/2/3]\nDelete a slot [CTRL + 1/2/3]\nReturn [ESC]"),
+            Text::new(format!("Play [{}]   Return [ESC]\nWeekly runs only count toward the weekly best", bindings.advance_hint())),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
@@ -1049,524 +11375,1033 @@ fn setup_save_select_ui(mut commands: Commands, asset_server: Res<AssetServer>,
     });
 }
 
-// Change delete_save_slot to return a boolean indicating if a slot was deleted
-fn delete_save_slot(slot: u32) -> bool {
-    let path = format!("saves/slot_{}.json", slot);
-    if Path::new(&path).exists() {
-        if let Ok(_) = std::fs::remove_file(&path) {
-            return true;
-        }
+fn weekly_system(
+    mut commands: Commands,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut fade: ResMut<ScreenFade>,
+    mut settings: ResMut<GameSettings>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        fade.request_transition(GameState::MainMenu);
+        return;
+    }
+
+    if bindings.consume(&mut keyboard, InputAction::Advance) {
+        start_weekly(&mut commands, &mut settings, &mut fade, &fonts, &toast_query);
     }
-    false
 }
 
-// Update save_select_system to handle this differently
-fn save_select_system(
+// Shared by the Weekly screen and the main menu's quick start
+fn start_weekly(
+    commands: &mut Commands,
+    settings: &mut GameSettings,
+    fade: &mut ScreenFade,
+    fonts: &UiFonts,
+    toast_query: &Query<Entity, With<Toast>>,
+) {
+    if settings.current_slot.is_none() {
+        spawn_toast(commands, fonts, toast_query, "Select a save slot first (Options > Saves)".to_string(), Color::srgb(0.9, 0.35, 0.3));
+        return;
+    }
+    commands.insert_resource(WeeklyRestore(settings.clone()));
+    settings.weekly = Some(current_weekly_challenge().key());
+    fade.request_transition(GameState::Playing);
+}
+
+// ---------------------------- ASSET RESOLUTION ----------------------------
+// "bird.png" -> "bird@2x.png"
+fn hidpi_name(file: &str) -> String {
+    match file.rsplit_once('.') {
+        Some((stem, ext)) => format!("{}{}.{}", stem, HIDPI_SUFFIX, ext),
+        None => format!("{}{}", file, HIDPI_SUFFIX),
+    }
+}
+
+// @2x variant of a sprite on high-DPI windows, or the standard one when it isn't shipped
+fn sprite_image(asset_server: &AssetServer, scale_factor: f32, file: &str) -> Handle<Image> {
+    let hidpi = hidpi_name(file);
+    if scale_factor >= HIDPI_SCALE_FACTOR && asset_file(&hidpi).is_file() {
+        asset_server.load(hidpi)
+    } else {
+        asset_server.load(file.to_string())
+    }
+}
+
+// Sprite drawn at its art size; the transform scale (PIXEL_RATIO) then takes it to world size
+fn art_sprite(image: Handle<Image>, size: Vec2) -> Sprite {
+    Sprite {
+        image,
+        custom_size: Some(size),
+        ..default()
+    }
+}
+
+// ---------------------------- GAME CONFIG ----------------------------
+// A missing file means the built-in defaults; parse errors keep ron's line:column prefix
+fn load_game_config() -> Result<GameConfig, String> {
+    if !Path::new(CONFIG_PATH).exists() {
+        return Ok(GameConfig::default());
+    }
+    let contents = fs::read_to_string(CONFIG_PATH).map_err(|e| e.to_string())?;
+    let config: GameConfig = ron::from_str(&contents).map_err(|e| e.to_string())?;
+    config.validate()?;
+    for warning in config_balance_warnings(&config) {
+        eprintln!("{}: {}", CONFIG_PATH, warning);
+    }
+    Ok(config)
+}
+
+// F5 re-reads the config in any state of a debug build. A live run is re-tuned in place: speed
+// and physics change at once, gaps only on newly recycled pipes, and the course RNG is left
+// alone; the run is no longer ranked
+fn reload_game_config(
+    mut commands: Commands,
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut settings: ResMut<GameSettings>,
-    mut flag: ResMut<SaveSlotChanged>,
-    origin: Res<SaveSelectOrigin>,  // Track where we came from
+    fonts: Res<UiFonts>,
+    mut config: ResMut<GameConfig>,
+    state: Res<State<GameState>>,
+    settings: Res<GameSettings>,
+    trainer: Res<TrainerTuning>,
+    score: Option<ResMut<Score>>,
+    replay: Option<Res<ReplayPlayback>>,
+    toast_query: Query<Entity, With<Toast>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        // Return to where we came from
-        next_state.set(origin.origin_state);
+    if !cfg!(debug_assertions) || !keyboard.just_pressed(KeyCode::F5) {
+        return;
+    }
+    // A replay plays back on the config it was recorded with; re-tuning it would desync the steps
+    if replay.is_some() {
+        spawn_toast(&mut commands, &fonts, &toast_query, "Config can't be reloaded during a replay".to_string(), Color::srgb(0.9, 0.35, 0.3));
         return;
     }
-    
-    let ctrl = keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
 
-    let mut slot_deleted = false;
-    
-    for (key, slot) in [(KeyCode::Digit1, 1), (KeyCode::Digit2, 2), (KeyCode::Digit3, 3)] {
-        if keyboard.just_pressed(key) {
-            if ctrl {
-                if delete_save_slot(slot as u32) {
-                    settings.current_slot = None;
-                    slot_deleted = true;
+    let (message, color) = match load_game_config() {
+        Ok(new_config) => {
+            *config = new_config;
+            if *state.get() == GameState::Playing {
+                commands.insert_resource(run_tuning(&settings, &trainer, &config));
+                if let Some(mut score) = score {
+                    score.custom_config = true;
                 }
-                continue;
             }
+            match config_balance_warnings(&config).first() {
+                Some(warning) => (format!("Config reloaded - {}", warning), Color::srgb(1.0, 0.84, 0.0)),
+                None => ("Config reloaded".to_string(), Color::srgb(0.4, 0.85, 0.4)),
+            }
+        }
+        Err(e) => (format!("{}: {}", CONFIG_PATH, e), Color::srgb(0.9, 0.35, 0.3)),
+    };
+    spawn_toast(&mut commands, &fonts, &toast_query, message, color);
+}
 
-            settings.current_slot = Some(slot);
-            
-            // Load existing save or use defaults
-            if let Some(save_data) = load_save_slot(slot as u32) {
-                settings.selected_mode = save_data.mode;
-                settings.selected_difficulty = save_data.difficulty;
-                settings.selected_theme = save_data.theme;
-                settings.selected_skin = save_data.skin;
-            } else {
-                // New slot - reset to defaults
-                settings.selected_mode = GameMode::Endless;
-                settings.selected_difficulty = Difficulty::Normal;
-                settings.selected_theme = Theme::Classic;
-                settings.selected_skin = Skin::Classic;
+// Replaces any toast still showing
+fn spawn_toast(commands: &mut Commands, fonts: &UiFonts, toast_query: &Query<Entity, With<Toast>>, message: String, color: Color) {
+    for entity in toast_query {
+        commands.entity(entity).despawn();
+    }
+    commands.spawn((
+        Text::new(message),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(18.0),
+            ..default()
+        },
+        TextColor(color),
+        TextBackgroundColor(Color::BLACK.with_alpha(0.6)),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        GlobalZIndex(10),
+        Toast { remaining: TOAST_DURATION },
+    ));
+}
+
+fn update_toast(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in toast_query.iter_mut() {
+        toast.remaining -= time.delta_secs();
+        if toast.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// ---------------------------- SAVE IO ----------------------------
+// Slot files are written on the IO task pool so a death never waits on the disk.
+// Each slot has at most one write in flight; newer data for it waits its turn
+#[derive(Resource, Default)]
+struct SaveWriter {
+    slots: std::collections::HashMap<u8, SlotWrite>,
+    // settings.json gets the same treatment as a slot: one write in flight, newest queued
+    settings: Option<Task<Result<(), String>>>,
+    queued_settings: Option<String>,
+    store: Arc<SaveStore>,
+}
+
+struct SlotWrite {
+    task: Task<Result<(), String>>,
+    // Serialized data to write once `task` finishes, newest wins
+    queued: Option<String>,
+    // Newest data handed over, so reads see it before it reaches the disk
+    latest: SaveSlot,
+}
+
+impl SaveWriter {
+    fn open() -> Self {
+        SaveWriter { store: Arc::new(SaveStore::open()), ..default() }
+    }
+
+    fn store(&self) -> &SaveStore {
+        &self.store
+    }
+
+    fn write(&mut self, slot: SaveSlot) {
+        let json = match serde_json::to_string_pretty(&slot) {
+            Ok(json) => json,
+            Err(e) => {
+                eprintln!("Failed to serialize slot {}: {}", slot.slot_number, e);
+                return;
             }
-            
-            // Different transition based on origin
-            match origin.origin_state {
-                GameState::MainMenu => {
-                    // Came from MainMenu (starting game without slot) → go to Playing
-                    next_state.set(GameState::Playing);
+        };
+        match self.slots.get_mut(&slot.slot_number) {
+            Some(write) => {
+                write.queued = Some(json);
+                write.latest = slot;
+            }
+            None => {
+                let task = spawn_slot_write(&self.store, slot.slot_number, json);
+                self.slots.insert(slot.slot_number, SlotWrite { task, queued: None, latest: slot });
+            }
+        }
+    }
+
+    // Same backend as the slots, so a read-only saves folder keeps settings for the session
+    fn write_settings(&mut self, json: String) {
+        match self.settings {
+            Some(_) => self.queued_settings = Some(json),
+            None => self.settings = Some(spawn_file_write(&self.store, SETTINGS_PATH, json)),
+        }
+    }
+
+    // The slot as it will be on disk, including writes that haven't landed yet
+    fn load(&self, slot: u8) -> Option<SaveSlot> {
+        match self.slots.get(&slot) {
+            Some(write) => Some(write.latest.clone()),
+            None => self.store().load_slot(slot),
+        }
+    }
+
+    // Blocks until every slot file is up to date, for code that reads or deletes them directly
+    fn flush(&mut self) {
+        for (slot, mut write) in self.slots.drain() {
+            loop {
+                if let Err(e) = block_on(&mut write.task) {
+                    eprintln!("Failed to save slot {}: {}", slot, e);
                 }
-                _ => {
-                    // Came from Options or elsewhere → go back to MainMenu
-                    next_state.set(GameState::MainMenu);
+                let Some(json) = write.queued.take() else { break; };
+                write.task = spawn_slot_write(&self.store, slot, json);
+            }
+        }
+        while let Some(task) = self.settings.take() {
+            if let Err(e) = block_on(task) {
+                eprintln!("Failed to save settings: {}", e);
+            }
+            if let Some(json) = self.queued_settings.take() {
+                self.settings = Some(spawn_file_write(&self.store, SETTINGS_PATH, json));
+            }
+        }
+    }
+}
+
+fn spawn_slot_write(store: &Arc<SaveStore>, slot: u8, json: String) -> Task<Result<(), String>> {
+    let store = store.clone();
+    IoTaskPool::get().spawn(async move { store.write_slot_file(slot, json) })
+}
+
+fn spawn_file_write(store: &Arc<SaveStore>, path: &'static str, json: String) -> Task<Result<(), String>> {
+    let store = store.clone();
+    IoTaskPool::get().spawn(async move { store.write_file(path, json) })
+}
+
+// Reports finished writes and starts the queued one for the same slot
+fn poll_save_writes(
+    mut commands: Commands,
+    fonts: Res<UiFonts>,
+    mut writer: ResMut<SaveWriter>,
+    toast_query: Query<Entity, With<Toast>>,
+    mut slots_changed: ResMut<SaveSlotChanged>,
+) {
+    let mut finished = Vec::new();
+    let store = writer.store.clone();
+    for (slot, write) in writer.slots.iter_mut() {
+        let Some(result) = check_ready(&mut write.task) else { continue; };
+        if let Err(e) = result {
+            eprintln!("Failed to save slot {}: {}", slot, e);
+            spawn_toast(&mut commands, &fonts, &toast_query, format!("Failed to save slot {}: {}", slot, e), Color::srgb(0.9, 0.35, 0.3));
+        }
+        match write.queued.take() {
+            Some(json) => write.task = spawn_slot_write(&store, *slot, json),
+            None => finished.push(*slot),
+        }
+    }
+    for slot in finished {
+        writer.slots.remove(&slot);
+        slots_changed.changed = true;
+    }
+    if let Some(task) = &mut writer.settings
+        && let Some(result) = check_ready(task)
+    {
+        if let Err(e) = result {
+            eprintln!("Failed to save settings: {}", e);
+        }
+        writer.settings = writer.queued_settings.take().map(|json| spawn_file_write(&store, SETTINGS_PATH, json));
+    }
+}
+
+fn flush_save_writes(mut writer: ResMut<SaveWriter>) {
+    writer.flush();
+}
+
+// ---------------------------- DIAGNOSTICS ----------------------------
+// Snapshot of the game the panic hook can read without touching the ECS
+static CRASH_CONTEXT: Mutex<String> = Mutex::new(String::new());
+
+// Writes <dir>/crash_<timestamp>.txt on panic, then hands over to the default hook
+fn install_crash_hook(dir: &'static str) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let context = CRASH_CONTEXT.lock().map(|context| context.clone()).unwrap_or_default();
+        let report = format!(
+            "Flappy Bird crash report\n\nPanic: {}\nOS: {} ({})\n{}\nBacktrace:\n{}\n",
+            info,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            context,
+            std::backtrace::Backtrace::force_capture(),
+        );
+        let path = format!("{}/crash_{}.txt", dir, utc_timestamp());
+        match fs::create_dir_all(dir).and_then(|_| fs::write(&path, report)) {
+            Ok(()) => eprintln!("Crash report written to {}", path),
+            Err(e) => eprintln!("Failed to write crash report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+fn update_crash_context(
+    state: Res<State<GameState>>,
+    settings: Res<GameSettings>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut last_window_size: Local<Option<Vec2>>,
+) {
+    let window_size = window_query.single().map(|window| window.size()).ok();
+    if !state.is_changed() && !settings.is_changed() && window_size == *last_window_size {
+        return;
+    }
+    *last_window_size = window_size;
+
+    let context = format!(
+        "Window size: {}\nGame state: {:?}\nSettings: {:#?}\n",
+        window_size.map_or("unknown".to_string(), |size| format!("{}x{}", size.x, size.y)),
+        state.get(),
+        *settings,
+    );
+    if let Ok(mut current) = CRASH_CONTEXT.lock() {
+        *current = context;
+    }
+}
+
+// F9 writes a readable snapshot of the running game for bug reports
+fn write_debug_dump(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    fonts: Res<UiFonts>,
+    toast_query: Query<Entity, With<Toast>>,
+    state: Res<State<GameState>>,
+    settings: Res<GameSettings>,
+    score: Option<Res<Score>>,
+    tuning: Option<Res<DifficultyTuning>>,
+    time_attack: Option<Res<TimeAttackState>>,
+    modifiers: Option<Res<ModifierState>>,
+    level: Option<Res<LevelRun>>,
+    checkpoints: Option<Res<CheckpointsState>>,
+    bird_query: Query<(&Bird, &Transform, Option<&Invulnerable>, Option<&GravityFlip>)>,
+    obstacle_query: Query<(&Obstacle, &Transform)>,
+    hud_stats: Res<HudStats>,
+) {
+    if !keyboard.just_pressed(KeyCode::F9) {
+        return;
+    }
+
+    let mut dump = format!(
+        "Flappy Bird debug dump ({} UTC)\n\nGame state: {:?}\nSettings: {:#?}\n",
+        utc_timestamp(),
+        state.get(),
+        *settings,
+    );
+    match score {
+        Some(score) => dump += &format!("Score: {:#?}\n", *score),
+        None => dump += "Score: none\n",
+    }
+    match tuning {
+        Some(tuning) => dump += &format!("Tuning: {:#?}\n", *tuning),
+        None => dump += "Tuning: none\n",
+    }
+    dump += &format!(
+        "HUD text updates/s: {}, worst frame {:.1}ms, run entities {}\n",
+        hud_stats.per_second,
+        hud_stats.last_worst_frame * 1000.0,
+        hud_stats.run_entities
+    );
+
+    dump += "\nTimers:\n";
+    if let Some(time_attack) = time_attack {
+        dump += &format!("  time attack: {:.2}s of {:.0}s left, {} pipes credited\n", time_attack.remaining, time_attack.duration, time_attack.credited_score);
+    }
+    if let Some(modifiers) = modifiers {
+        dump += &format!("  modifiers: {:.2}s elapsed, {:.2}s since flap\n", modifiers.elapsed, modifiers.since_flap);
+    }
+    if let Some(level) = level {
+        dump += &format!("  level: {:.2}s elapsed, next pipe {}, {} coins\n", level.elapsed, level.next_pipe, level.coins);
+    }
+    if let Some(checkpoints) = checkpoints {
+        dump += &format!("  checkpoints: {}/{} reached, last at {}\n", checkpoints.current_checkpoint_index, checkpoints.checkpoints.len(), checkpoints.last_checkpoint_score);
+    }
+
+    dump += "\nBird:\n";
+    for (bird, transform, invulnerable, flip) in &bird_query {
+        dump += &format!("  position ({:.1}, {:.1}), velocity {:.1}\n", transform.translation.x, transform.translation.y, bird.velocity);
+        if let Some(invulnerable) = invulnerable {
+            dump += &format!("  invulnerable for {:.2}s\n", invulnerable.timer.remaining_secs());
+        }
+        if let Some(flip) = flip {
+            dump += &format!("  gravity flipped for {:.2}s\n", flip.remaining);
+        }
+    }
+
+    // Pairs in course order, top pipe first
+    let mut pipes: Vec<_> = obstacle_query.iter().collect();
+    pipes.sort_by(|(a, _), (b, _)| a.index.cmp(&b.index).then(b.pipe_direction.total_cmp(&a.pipe_direction)));
+    dump += "\nObstacles:\n";
+    for (obstacle, transform) in pipes {
+        dump += &format!(
+            "  #{} {} at ({:.1}, {:.1}), half gap {:.1}{}\n",
+            obstacle.index,
+            if obstacle.pipe_direction > 0. { "top" } else { "bottom" },
+            transform.translation.x,
+            transform.translation.y,
+            obstacle.gap_size,
+            if obstacle.scored { ", scored" } else { "" },
+        );
+    }
+
+    let (message, color) = match fs::create_dir_all(SAVES_DIR).and_then(|_| fs::write(DEBUG_DUMP_PATH, dump)) {
+        Ok(()) => (format!("Debug dump written to {}", DEBUG_DUMP_PATH), Color::srgb(0.4, 0.85, 0.4)),
+        Err(e) => (format!("{}: {}", DEBUG_DUMP_PATH, e), Color::srgb(0.9, 0.35, 0.3)),
+    };
+    spawn_toast(&mut commands, &fonts, &toast_query, message, color);
+}
+
+// UTC "YYYYMMDD_HHMMSS" from the system clock (days-to-civil conversion)
+// Local hour from the system's `date`, falling back to UTC where that isn't available
+// The system's offset from UTC, asked for once at startup so building a menu never waits
+// on another process. Stays at UTC if the system can't tell
+#[derive(Resource, Clone, Copy, Default)]
+struct LocalClock {
+    utc_offset_secs: i64,
+}
+
+impl LocalClock {
+    fn detect() -> Self {
+        // "+0200" from date, "+02:00" from PowerShell
+        let command = if cfg!(windows) {
+            std::process::Command::new("powershell").args(["-NoProfile", "-Command", "Get-Date -Format zzz"]).output()
+        } else {
+            std::process::Command::new("date").arg("+%z").output()
+        };
+        let offset = command
+            .ok()
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .and_then(|offset| parse_utc_offset(&offset));
+        LocalClock { utc_offset_secs: offset.unwrap_or(0) }
+    }
+
+    fn hour(&self) -> u32 {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()) as i64;
+        ((secs + self.utc_offset_secs).rem_euclid(86_400) / 3600) as u32
+    }
+}
+
+// "+hhmm" or "+hh:mm" to seconds east of UTC
+fn parse_utc_offset(offset: &str) -> Option<i64> {
+    let offset = offset.trim().replace(':', "");
+    let (sign, digits) = match offset.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+fn is_evening(hour: u32) -> bool {
+    !(6..18).contains(&hour)
+}
+
+fn greeting_text(hour: u32, name: Option<&str>) -> String {
+    let part = match hour {
+        5..=11 => "Good morning",
+        12..=17 => "Good afternoon",
+        _ => "Good evening",
+    };
+    match name {
+        Some(name) => format!("{}, {}!", part, name),
+        None => format!("{}!", part),
+    }
+}
+
+fn utc_timestamp() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}{:02}{:02}_{:02}{:02}{:02}", year, month, day, rem / 3600, rem % 3600 / 60, rem % 60)
+}
+
+// (year, month, day) of a day count since 1970-01-01, proleptic Gregorian
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+// Inverse of civil_from_days
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    era * 146_097 + yoe * 365 + yoe / 4 - yoe / 100 + doy - 719_468
+}
+
+// ISO 8601 (year, week) of a day count since 1970-01-01: weeks start on Monday and
+// belong to the year their Thursday falls in
+fn iso_week(days: i64) -> (i64, u32) {
+    // 1970-01-01 was a Thursday
+    let weekday = (days + 3).rem_euclid(7);
+    let thursday = days - weekday + 3;
+    let (year, _, _) = civil_from_days(thursday);
+    (year, ((thursday - days_from_civil(year, 1, 1)) / 7 + 1) as u32)
+}
+
+// --validate-saves: read every slot, slot backup, the settings file and the weekly board the way the game does
+// and report on each, one line per entry, so a mangled save can be pinpointed without launching the game.
+// Returns false when any file exists but can't be read
+fn validate_saves(saves: &SaveWriter, report: &mut Vec<String>) -> bool {
+    let mut ok = true;
+    for slot in 1..=3u8 {
+        let path = slot_path(slot);
+        for (label, path) in [(format!("Slot {}", slot), path.clone()), (format!("Slot {} backup", slot), slot_backup_path(&path))] {
+            let Some(contents) = saves.store().read_file(&path) else {
+                report.push(format!("{} ({}): empty", label, path));
+                continue;
+            };
+            match parse_save_slot(&contents) {
+                Ok(save) => {
+                    report.push(format!("{} ({}): ok", label, path));
+                    report.push(format!("  profile: {}", save.profile.name));
+                    report.push(format!("  format: {}", save_format(&contents, &save)));
+                    let integrity = if save.slot_number == slot {
+                        "ok".to_string()
+                    } else {
+                        format!("file holds slot {} (loaded as slot {})", save.slot_number, slot)
+                    };
+                    report.push(format!("  integrity: {}", integrity));
+                    report.push(format!(
+                        "  history: {} run(s), {} mode best(s), {} weekly best(s), {} unlock(s)",
+                        save.profile.history.len(),
+                        save.profile.mode_bests.len(),
+                        save.profile.weekly_bests.len(),
+                        save.profile.unlocked.len(),
+                    ));
+                }
+                Err(e) => {
+                    ok = false;
+                    report.push(format!("{} ({}): UNREADABLE", label, path));
+                    report.push(format!("  line {}, column {}: {}", e.line(), e.column(), e));
                 }
             }
-            return;
-        }
+        }
+    }
+
+    match saves.store().read_file(SETTINGS_PATH) {
+        None => report.push(format!("Settings ({}): missing, defaults are used", SETTINGS_PATH)),
+        Some(contents) => match parse_audio_settings(&contents) {
+            Ok(audio) => report.push(format!("Settings ({}): ok, {} seed history entr(ies)", SETTINGS_PATH, audio.seed_history.len())),
+            Err(e) => {
+                ok = false;
+                report.push(format!("Settings ({}): UNREADABLE", SETTINGS_PATH));
+                report.push(format!("  line {}, column {}: {}", e.line(), e.column(), e));
+            }
+        },
+    }
+
+    match saves.store().read_file(WEEKLY_PATH) {
+        None => report.push(format!("Weekly board ({}): empty", WEEKLY_PATH)),
+        Some(contents) => match serde_json::from_str::<WeeklyBoard>(&contents) {
+            Ok(board) => report.push(format!("Weekly board ({}): ok, {} week(s)", WEEKLY_PATH, board.weeks.len())),
+            Err(e) => {
+                ok = false;
+                report.push(format!("Weekly board ({}): UNREADABLE", WEEKLY_PATH));
+                report.push(format!("  line {}, column {}: {}", e.line(), e.column(), e));
+            }
+        },
     }
-    
-    // Only set the flag if a slot was actually deleted
-    if slot_deleted {
-        flag.changed = true;
+
+    // The all-time leaderboard has no file of its own; it is rebuilt from the slots above
+    report.push(format!("Leaderboard: {} entr(ies) from the slots", load_leaderboard(saves).len()));
+    report.push(if ok { "All save files are readable" } else { "Some save files are unreadable" }.to_string());
+    ok
+}
+
+// Slots carry no version number, so the format is told from the fields present:
+// a save written by an older build is missing fields that were added since
+fn save_format(contents: &str, save: &SaveSlot) -> String {
+    let (Ok(file), Ok(current)) = (serde_json::from_str::<serde_json::Value>(contents), serde_json::to_value(save)) else {
+        return "unknown".to_string();
+    };
+    let missing = |file: &serde_json::Value, current: &serde_json::Value, prefix: &str| -> Vec<String> {
+        current
+            .as_object()
+            .map(|fields| {
+                fields
+                    .keys()
+                    .filter(|key| file.get(key.as_str()).is_none())
+                    .map(|key| format!("{}{}", prefix, key))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut absent = missing(&file, &current, "");
+    absent.extend(missing(&file["profile"], &current["profile"], "profile."));
+    if absent.is_empty() {
+        "current".to_string()
+    } else {
+        format!("legacy, defaults used for {}", absent.join(", "))
     }
 }
 
-fn refresh_save_select_ui(
-    mut commands: Commands,
-    query: Query<Entity, With<SaveSelectMarker>>,
-    mut flag: ResMut<SaveSlotChanged>,
-    asset_server: Res<AssetServer>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-) {
-    if flag.changed {
-        // Clean up old UI
-        for entity in &query {
-            commands.entity(entity).despawn();
-        }
+// --diagnostics: print where data lives and what's missing, then exit
+fn print_diagnostics() {
+    let saves = std::env::current_dir().map_or_else(|_| PathBuf::from(SAVES_DIR), |dir| dir.join(SAVES_DIR));
+    println!("Save directory: {} ({})", saves.display(), if saves.is_dir() { "present" } else { "missing" });
+    for slot in 1..=3 {
+        let path = format!("{}/slot_{}.json", SAVES_DIR, slot);
+        println!("  slot {}: {}", slot, if Path::new(&path).exists() { "saved" } else { "empty" });
+    }
 
-        // Recreate the UI
-        setup_save_select_ui(commands, asset_server, window_query);
+    match fs::read_to_string(SETTINGS_PATH) {
+        Ok(contents) => println!("Settings file {}:\n{}", SETTINGS_PATH, contents),
+        Err(e) => println!("Settings file {}: {}", SETTINGS_PATH, e),
+    }
 
-        // Reset flag
-        flag.changed = false;
+    println!("Assets:");
+    for asset in REQUIRED_ASSETS {
+        let present = asset_file(asset).exists();
+        println!("  {}: {}", asset, if present { "ok" } else { "MISSING" });
     }
+    let hidpi: Vec<String> = HIDPI_ASSETS
+        .iter()
+        .map(|asset| hidpi_name(asset))
+        .filter(|asset| asset_file(asset).is_file())
+        .collect();
+    println!("High-DPI variants: {}/{} ({})", hidpi.len(), HIDPI_ASSETS.len(), if hidpi.is_empty() { "none".to_string() } else { hidpi.join(", ") });
+    println!("Levels: {} file(s) in {}", list_levels().len(), LEVELS_DIR);
 }
 
-// Mode Select UI - UPDATED with background and font
-fn setup_mode_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let window = window_query.single().expect("Missing primary window");
-    let window_width = window.width();
-    let window_height = window.height();
-
-    // Add background image
-    commands.spawn((
-        Sprite {
-            image: asset_server.load("Background2.png"),
-            custom_size: Some(Vec2::new(window_width, window_height)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-        Background,
-        ModeSelectMarker,
-    ));
-
-    commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
-        ModeSelectMarker,
-    ))
-    .with_children(|parent| {
-        parent.spawn((
-            Text::new("GAME MODE"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(30.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Endless"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 40.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Time Attack"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 40.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Checkpoints"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 40.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Select [1/2/3]\nReturn [ESC]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::top(Val::Px(40.0)),
-                ..default()
-            },
-        ));
-    });
+// ---------------------------- DEATH REPLAY ----------------------------
+// Keeps a rolling window of bird poses so the death can be replayed on Game Over
+fn record_death_trail(
+    step: Res<SimStep>,
+    mut trail: ResMut<DeathTrail>,
+    bird_query: Query<&Transform, With<Bird>>,
+) {
+    let Ok(transform) = bird_query.single() else { return; };
+    trail.elapsed += step.dt;
+    let now = trail.elapsed;
+    trail.samples.push_back((now, transform.translation, transform.rotation));
+    while trail.samples.front().is_some_and(|&(time, _, _)| now - time > DEATH_REPLAY_SECONDS) {
+        trail.samples.pop_front();
+    }
 }
 
-fn mode_select_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut settings: ResMut<GameSettings>,
+// Runs before cleanup_game, while the bird and pipes still sit where the run ended
+fn capture_death_replay(
+    mut commands: Commands,
+    mut trail: ResMut<DeathTrail>,
+    bird_query: Query<(&Sprite, &Transform), With<Bird>>,
+    obstacle_query: Query<&Transform, With<Obstacle>>,
+    game_manager: Option<Res<GameManager>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::Options);
+    commands.remove_resource::<DeathReplay>();
+    let samples: Vec<_> = std::mem::take(&mut trail.samples).into();
+    let (Ok((sprite, bird)), Some(game_manager)) = (bird_query.single(), game_manager) else { return; };
+    if samples.is_empty() {
         return;
     }
-    
-    for (key, mode) in [
-        (KeyCode::Digit1, GameMode::Endless),
-        (KeyCode::Digit2, GameMode::TimeAttack),
-        (KeyCode::Digit3, GameMode::Checkpoints),
-    ] {
-        if keyboard.just_pressed(key) {
-            settings.selected_mode = mode;
-            next_state.set(GameState::Options);
-            return;
-        }
-    }
+
+    // Only pipes inside the playfield fit in the corner view
+    let half_width = game_manager.window_dimensions.x / 2.;
+    commands.insert_resource(DeathReplay {
+        samples,
+        pipes: obstacle_query
+            .iter()
+            .filter(|pipe| pipe.translation.x.abs() <= half_width)
+            .copied()
+            .collect(),
+        pipe_image: game_manager.pipe_image.clone(),
+        bird_image: sprite.image.clone(),
+        bird_scale: bird.scale,
+        field: game_manager.window_dimensions,
+    });
 }
 
-// Difficulty Select UI - UPDATED with background and font
-fn setup_difficulty_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>) {
+// Shrunken copy of the playfield in the bottom-right corner of Game Over
+fn setup_death_replay(
+    mut commands: Commands,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    replay: Option<Res<DeathReplay>>,
+    settings: Res<GameSettings>,
+    images: Res<Assets<Image>>,
+) {
+    let Some(replay) = replay else { return; };
     let window = window_query.single().expect("Missing primary window");
-    let window_width = window.width();
-    let window_height = window.height();
+    let size = window.size();
+    let view = replay.field * DEATH_REPLAY_SCALE;
+    let corner = Vec2::new(
+        size.x / 2. - view.x / 2. - DEATH_REPLAY_MARGIN,
+        -size.y / 2. + view.y / 2. + DEATH_REPLAY_MARGIN,
+    );
 
-    // Add background image
-    commands.spawn((
-        Sprite {
-            image: asset_server.load("Background2.png"),
-            custom_size: Some(Vec2::new(window_width, window_height)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-        Background,
-        DifficultySelectMarker,
-    ));
+    // Reduce motion shows only the final frame
+    let (_, translation, rotation) = *replay.samples.last().expect("Death replay without samples");
+    let start = if settings.reduce_motion { replay.samples.len() - 1 } else { 0 };
+    let (time, _, _) = replay.samples[start];
 
     commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
-        DifficultySelectMarker,
+        Transform::from_translation(corner.extend(10.)).with_scale(Vec3::splat(DEATH_REPLAY_SCALE)),
+        Visibility::default(),
+        GameOverMarker,
     ))
     .with_children(|parent| {
         parent.spawn((
-            Text::new("DIFFICULTY"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(30.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Easy [Large Gaps, Slow, Low Gravity]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(0.5, 1.0, 0.5)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Normal [Standard difficulty]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 1.0, 0.5)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Hard [Smaller Gaps, Fast, High Gravity]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.5, 0.5)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
+            Sprite::from_color(Color::BLACK.with_alpha(0.6), replay.field),
+            Transform::default(),
         ));
-        
-        parent.spawn((
-            Text::new("Select [1/2/3]\nReturn [ESC]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::top(Val::Px(40.0)),
-                ..default()
-            },
+        // Pipes are cut to the box, so the ones half off screen don't spill over the menu
+        let texture = images.get(&replay.pipe_image).map_or(PIPE_ART_SIZE, |image| image.size_f32());
+        for pipe in &replay.pipes {
+            if let Some((sprite, transform)) = clip_pipe(replay.pipe_image.clone(), texture, pipe, replay.field) {
+                parent.spawn((sprite, transform.with_translation(transform.translation.with_z(1.))));
+            }
+        }
+        let mut bird = parent.spawn((
+            art_sprite(replay.bird_image.clone(), BIRD_ART_SIZE),
+            Transform::from_translation(translation.with_z(2.))
+                .with_rotation(rotation)
+                .with_scale(replay.bird_scale),
         ));
+        if !settings.reduce_motion {
+            bird.insert(DeathReplayBird { time });
+        }
     });
-}
 
-fn difficulty_select_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut settings: ResMut<GameSettings>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::Options);
-        return;
-    }
-    
-    for (key, difficulty) in [
-        (KeyCode::Digit1, Difficulty::Easy),
-        (KeyCode::Digit2, Difficulty::Normal),
-        (KeyCode::Digit3, Difficulty::Hard),
-    ] {
-        if keyboard.just_pressed(key) {
-            settings.selected_difficulty = difficulty;
-            next_state.set(GameState::Options);
-            return;
-        }
+    commands.spawn((
+        Text::new(if settings.reduce_motion { "FINAL MOMENT" } else { "LAST 3 SECONDS" }),
+        TextFont {
+            font: fonts.body.clone(),
+            font_size: fonts.scaled(16.0),
+            ..default()
+        },
+        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+        TextShadow::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(DEATH_REPLAY_MARGIN),
+            bottom: Val::Px(view.y + DEATH_REPLAY_MARGIN + 4.0),
+            ..default()
+        },
+        GameOverMarker,
+    ));
+}
+
+// The part of a pipe inside a field-sized box around the origin, as a cropped sprite and
+// its moved transform; None when the pipe is entirely outside
+fn clip_pipe(image: Handle<Image>, texture: Vec2, pipe: &Transform, field: Vec2) -> Option<(Sprite, Transform)> {
+    let scale = pipe.scale.truncate();
+    let half = PIPE_ART_SIZE / 2.;
+    // Box edges in the pipe's art space; a top pipe is flipped, so its edges swap
+    let a = (-field / 2. - pipe.translation.truncate()) / scale;
+    let b = (field / 2. - pipe.translation.truncate()) / scale;
+    let min = a.min(b).max(-half);
+    let max = a.max(b).min(half);
+    if min.x >= max.x || min.y >= max.y {
+        return None;
     }
+    // Texture pixels run down from the top-left corner
+    let to_texture = |point: Vec2| Vec2::new(point.x + half.x, half.y - point.y) / PIPE_ART_SIZE * texture;
+    let sprite = Sprite {
+        image,
+        custom_size: Some(max - min),
+        rect: Some(Rect::from_corners(to_texture(min), to_texture(max))),
+        ..default()
+    };
+    let center = (min + max) / 2. * scale;
+    Some((sprite, pipe.with_translation(pipe.translation + center.extend(0.))))
 }
 
-// Theme Select UI - UPDATED with background and font
-fn setup_theme_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>) {
+// Slow-motion playback that holds on the final frame, then loops
+fn update_death_replay(
+    time: Res<Time>,
+    replay: Option<Res<DeathReplay>>,
+    mut bird_query: Query<(&mut DeathReplayBird, &mut Transform)>,
+) {
+    let Some(replay) = replay else { return; };
+    let Ok((mut bird, mut transform)) = bird_query.single_mut() else { return; };
+    let (start, _, _) = replay.samples[0];
+    let (end, _, _) = *replay.samples.last().expect("Death replay without samples");
+
+    bird.time += time.delta_secs() * DEATH_REPLAY_SPEED;
+    if bird.time > end + DEATH_REPLAY_HOLD * DEATH_REPLAY_SPEED {
+        bird.time = start;
+    }
+
+    let index = replay.samples.partition_point(|&(sample, _, _)| sample < bird.time).min(replay.samples.len() - 1);
+    let (_, translation, rotation) = replay.samples[index];
+    transform.translation = translation.with_z(2.);
+    transform.rotation = rotation;
+}
+
+// ---------------------------- RUN DETAILS ----------------------------
+// Last RUN_LOG_LEN events of the run, newest first; reached with D on Game Over
+fn setup_run_details_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    score: Res<Score>,
+) {
     let window = window_query.single().expect("Missing primary window");
     let window_width = window.width();
     let window_height = window.height();
 
-    // Add background image
     commands.spawn((
         Sprite {
-            image: asset_server.load("Background2.png"),
+            image: asset_server.load("cookd.png"),
             custom_size: Some(Vec2::new(window_width, window_height)),
             ..default()
         },
         Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
         Background,
-        ThemeSelectMarker,
+        RunDetailsMarker,
     ));
 
+    let log = &score.log;
+    let summary = format!(
+        "FLAPS: {}   NEAR MISSES: {}   CLOSEST PASS: {}",
+        log.flaps,
+        log.near_misses,
+        log.closest.map_or("-".to_string(), |closest| format!("{:.0}px", closest)),
+    );
+
     commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
-        ThemeSelectMarker,
+        menu_root_node(),
+        RunDetailsMarker,
     ))
     .with_children(|parent| {
         parent.spawn((
-            Text::new("THEME"),
+            Text::new("RUN DETAILS"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
+                font: fonts.title.clone(),
+                font_size: fonts.heading(56.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
             TextShadow::default(),
             Node {
-                margin: UiRect::all(Val::Px(30.0)),
+                margin: UiRect::all(Val::Px(20.0)),
                 ..default()
             },
         ));
-        
+
         parent.spawn((
-            Text::new("Classic [Original Look]"),
+            Text::new(summary),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextColor(Color::srgb(0.85, 0.95, 1.0)),
             TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
             TextShadow::default(),
             Node {
-                margin: UiRect::all(Val::Px(15.0)),
+                margin: UiRect::bottom(fonts.margin(15.0)),
                 ..default()
             },
         ));
-        
+
         parent.spawn((
-            Text::new("High Contrast [Enhanced Visibility]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
             Node {
-                margin: UiRect::all(Val::Px(15.0)),
+                width: Val::Percent(80.0),
+                height: Val::Percent(55.0),
+                flex_direction: FlexDirection::Column,
+                overflow: Overflow::scroll_y(),
                 ..default()
             },
-        ));
-        
+            BackgroundColor(Color::BLACK.with_alpha(0.4)),
+            ScrollPosition::default(),
+            RunDetailsList,
+        ))
+        .with_children(|list| {
+            if log.events.is_empty() {
+                list.spawn((
+                    Text::new("Nothing happened this run"),
+                    TextFont {
+                        font: fonts.body.clone(),
+                        font_size: fonts.scaled(20.0),
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                ));
+            }
+            for (time, event) in log.events.iter().rev() {
+                let color = match event {
+                    RunEvent::NearMiss { .. } => Color::srgb(1.0, 0.75, 0.2),
+                    RunEvent::Death { .. } => Color::srgb(1.0, 0.4, 0.4),
+                    _ => Color::srgb(0.9, 0.9, 0.9),
+                };
+                list.spawn((
+                    Text::new(format!("[{:>6.2}s] {}", time, event.describe())),
+                    TextFont {
+                        font: fonts.body.clone(),
+                        font_size: fonts.scaled(20.0),
+                        ..default()
+                    },
+                    TextColor(color),
+                    Node {
+                        margin: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                ));
+            }
+        });
+
         parent.spawn((
-            Text::new("Minimal [Basic]"),
+            Text::new("SCROLL [UP/DOWN]   BACK [D/ESC]"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
             TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
             TextShadow::default(),
             Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Select [1/2/3]\nReturn [ESC]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::top(Val::Px(40.0)),
+                margin: UiRect::all(Val::Px(20.0)),
                 ..default()
             },
         ));
     });
 }
 
-fn theme_select_system(
+fn run_details_system(
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut settings: ResMut<GameSettings>,
+    mut fade: ResMut<ScreenFade>,
+    mut list_query: Query<&mut ScrollPosition, With<RunDetailsList>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::Options);
+    if keyboard.just_pressed(KeyCode::KeyD) || keyboard.just_pressed(KeyCode::Escape) {
+        fade.request_transition(GameState::GameOver);
         return;
     }
-    
-    for (key, theme) in [
-        (KeyCode::Digit1, Theme::Classic),
-        (KeyCode::Digit2, Theme::HighContrast),
-        (KeyCode::Digit3, Theme::Minimal),
-    ] {
-        if keyboard.just_pressed(key) {
-            settings.selected_theme = theme;
-            next_state.set(GameState::Options);
-            return;
-        }
+
+    // The layout clamps the offset to the list's height
+    let step = if keyboard.just_pressed(KeyCode::ArrowDown) {
+        RUN_DETAILS_SCROLL_STEP
+    } else if keyboard.just_pressed(KeyCode::ArrowUp) {
+        -RUN_DETAILS_SCROLL_STEP
+    } else {
+        return;
+    };
+    for mut scroll in list_query.iter_mut() {
+        scroll.y = (scroll.y + step).max(0.0);
     }
 }
 
-fn setup_skin_select_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let window = window_query.single().expect("Missing primary window");
-    let window_width = window.width();
-    let window_height = window.height();
+// The event log is never saved, so it goes away once Game Over is left for the menu
+fn clear_run_log(score: Option<ResMut<Score>>) {
+    if let Some(mut score) = score {
+        score.log = RunLog::default();
+    }
+}
 
-    // Add background image
-    commands.spawn((
-        Sprite {
-            image: asset_server.load("Background2.png"),
-            custom_size: Some(Vec2::new(window_width, window_height)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-        Background,
-        SkinSelectMarker,
-    ));
+// ---------------------------- PAUSE ----------------------------
+// ESC/P pauses a run; the menu is navigated with the shared MenuCursor
+const PAUSE_ROWS: usize = 8;
+// Pause menu row that opens the in-run settings, and where the cursor lands coming back
+const PAUSE_SETTINGS_ROW: usize = 6;
+// Only settings that are safe to change mid-run: each is read live, so a change applies straight away
+const PAUSE_SETTINGS_ROWS: usize = 6;
+
+// Also pauses when the window loses focus, including mid-countdown
+fn pause_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut focus_events: MessageReader<WindowFocused>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+    mut cursor: ResMut<MenuCursor>,
+) {
+    let lost_focus = focus_events.read().any(|event| !event.focused);
+    if lost_focus || keyboard.just_pressed(KeyCode::Escape) || keyboard.just_pressed(KeyCode::KeyP) {
+        cursor.index = 0;
+        next_pause.set(PauseState::Paused);
+    }
+}
+
+fn pause_row_text(row: usize, audio: &AudioSettings) -> String {
+    match row {
+        0 => "Resume".to_string(),
+        1 => "Restart Run".to_string(),
+        2 => format!("Music Volume:  < {:.0}% >", audio.music_volume * 100.0),
+        3 => format!("SFX Volume:  < {:.0}% >", audio.sfx_volume * 100.0),
+        4 => format!("Controller Rumble:  < {:.0}% >", audio.rumble_strength * 100.0),
+        5 => format!("Flap Pitch Variation:  < {} >", if audio.flap_pitch_variation { "On" } else { "Off" }),
+        6 => "Settings".to_string(),
+        _ => "Quit to Menu".to_string(),
+    }
+}
 
+fn setup_pause_menu_ui(mut commands: Commands, fonts: Res<UiFonts>, audio: Res<AudioSettings>) {
     commands.spawn((
         Node {
+            position_type: PositionType::Absolute,
             width: Val::Percent(100.0),
             height: Val::Percent(100.0),
             justify_content: JustifyContent::Center,
@@ -1574,14 +12409,16 @@ fn setup_skin_select_ui(mut commands: Commands, asset_server: Res<AssetServer>,
             flex_direction: FlexDirection::Column,
             ..default()
         },
-        SkinSelectMarker,
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        GlobalZIndex(10),
+        PauseMenuMarker,
     ))
     .with_children(|parent| {
         parent.spawn((
-            Text::new("BIRD SKIN"),
+            Text::new("PAUSED"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
@@ -1591,626 +12428,731 @@ fn setup_skin_select_ui(mut commands: Commands, asset_server: Res<AssetServer>,
                 ..default()
             },
         ));
-        
-        parent.spawn((
-            Text::new("Classic [Yellow Bird]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Red [Red Bird]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Blue [Blue Bird]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
-        parent.spawn((
-            Text::new("Green [Green Bird]"),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
-        
+
+        for row in 0..PAUSE_ROWS {
+            parent.spawn((
+                Text::new(pause_row_text(row, &audio)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(32.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+                PauseRow(row),
+            ));
+        }
+
         parent.spawn((
-            Text::new("Select [1/2/3/4]\nReturn [ESC]"),
+            Text::new("Navigate [UP/DOWN]  Adjust [LEFT/RIGHT]\nSelect [ENTER/SPACE]  Resume [ESC]"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
             TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
             TextShadow::default(),
             Node {
-                margin: UiRect::top(Val::Px(40.0)),
+                margin: UiRect::top(Val::Px(30.0)),
                 ..default()
             },
         ));
     });
 }
 
-fn skin_select_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
+fn pause_menu_system(
+    mut commands: Commands,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut next_pause: ResMut<NextState<PauseState>>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut settings: ResMut<GameSettings>,
+    mut cursor: ResMut<MenuCursor>,
+    mut audio: ResMut<AudioSettings>,
+    mut rows: Query<(&PauseRow, &mut Text, &mut TextColor)>,
+    mut saves: ResMut<SaveWriter>,
 ) {
     if keyboard.just_pressed(KeyCode::Escape) {
-        next_state.set(GameState::Options);
+        next_pause.set(PauseState::Resuming);
         return;
     }
-    
-    for (key, skin) in [
-        (KeyCode::Digit1, Skin::Classic),
-        (KeyCode::Digit2, Skin::Red),
-        (KeyCode::Digit3, Skin::Blue),
-        (KeyCode::Digit4, Skin::Green),
-    ] {
-        if keyboard.just_pressed(key) {
-            settings.selected_skin = skin;
-            next_state.set(GameState::Options);
-            return;
-        }
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        cursor.index = (cursor.index + PAUSE_ROWS - 1) % PAUSE_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        cursor.index = (cursor.index + 1) % PAUSE_ROWS;
     }
-}
 
-fn handle_escape_in_checkpoint(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    settings: Res<GameSettings>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    if settings.selected_mode == GameMode::Checkpoints {
-        if keyboard.just_pressed(KeyCode::Escape) {
-            next_state.set(GameState::MainMenu);
-        }
+    // Volume rows adjust in place and are written to the settings file right away
+    let step = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        VOLUME_STEP
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -VOLUME_STEP
+    } else {
+        0.0
+    };
+    if step != 0.0 && (2..=4).contains(&cursor.index) {
+        let volume = match cursor.index {
+            2 => &mut audio.music_volume,
+            3 => &mut audio.sfx_volume,
+            _ => &mut audio.rumble_strength,
+        };
+        *volume = ((*volume + step) * 10.0).round().clamp(0.0, 10.0) / 10.0;
+        save_audio_settings(&mut saves, &audio);
     }
-}
 
-fn difficulty_tuning(difficulty: Difficulty) -> DifficultyTuning {
-    match difficulty {
-        Difficulty::Easy => DifficultyTuning {
-            gap_size: OBSTACLE_GAP_SIZE * 1.3,
-            scroll_speed: OBSTACLE_SCROLL_SPEED * 0.85,
-            gravity_mult: 0.75,
-            flap_mult: 1.2,
-            vertical_offset: OBSTACLE_VERTICAL_OFFSET * 0.7,
-        },
-        Difficulty::Normal => DifficultyTuning {
-            gap_size: OBSTACLE_GAP_SIZE,
-            scroll_speed: OBSTACLE_SCROLL_SPEED,
-            gravity_mult: 1.0,
-            flap_mult: 1.0,
-            vertical_offset: OBSTACLE_VERTICAL_OFFSET,
-        },
-        Difficulty::Hard => DifficultyTuning {
-            gap_size: OBSTACLE_GAP_SIZE * 0.75,
-            scroll_speed: OBSTACLE_SCROLL_SPEED * 1.25,
-            gravity_mult: 1.3,
-            flap_mult: 1.05,
-            vertical_offset: OBSTACLE_VERTICAL_OFFSET * 1.2,
-        },
+    // On/off row: either arrow or confirm flips it
+    if cursor.index == 5 && (step != 0.0 || bindings.consume(&mut keyboard, InputAction::MenuConfirm)) {
+        audio.flap_pitch_variation = !audio.flap_pitch_variation;
+        save_audio_settings(&mut saves, &audio);
     }
-}
 
-fn setup_level(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    settings: Res<GameSettings>,
-) {
-    // Load core assets and cache window info used by obstacle wrap logic
-    let pipe_image = asset_server.load("pipe.png");
-    let window = window_query.single().expect("Missing primary window");
-    let window_width = window.width();
-    let window_height = window.height();
-    commands.insert_resource(GameManager {
-        pipe_image: pipe_image.clone(),
-        window_dimensions: Vec2::new(window_width, window_height),
-    });
-    
-    // Load sound effects (OGG format)
-    commands.insert_resource(SoundEffects {
-        flap: asset_server.load("flap.ogg"),
-        point: asset_server.load("point.ogg"),
-        die: asset_server.load("die.ogg"),
-        swoosh: asset_server.load("swoosh.ogg"),
-    });
-    
-    let tuning = difficulty_tuning(settings.selected_difficulty);
-    commands.insert_resource(tuning);
+    if bindings.consume(&mut keyboard, InputAction::MenuConfirm) {
+        match cursor.index {
+            0 => next_pause.set(PauseState::Resuming),
+            1 => {
+                commands.insert_resource(AbandonedRun);
+                next_state.set(GameState::Restarting);
+            }
+            PAUSE_SETTINGS_ROW => {
+                cursor.index = 0;
+                next_pause.set(PauseState::Settings);
+            }
+            7 => {
+                commands.insert_resource(AbandonedRun);
+                next_state.set(GameState::MainMenu);
+            }
+            _ => {}
+        }
+    }
 
-    commands.insert_resource(Score::default());
+    for (row, mut text, mut color) in rows.iter_mut() {
+        text.0 = pause_row_text(row.0, &audio);
+        color.0 = if row.0 == cursor.index {
+            AQUAMARINE.into()
+        } else {
+            Color::srgb(1.0, 0.992, 0.816)
+        };
+    }
+}
 
-    // Time Attack setup: start a 60s countdown and show UI
-    if settings.selected_mode == GameMode::TimeAttack {
-        commands.insert_resource(TimeAttackState { remaining: 60.0 });
+fn pause_settings_row_text(row: usize, settings: &GameSettings, audio: &AudioSettings) -> String {
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    match row {
+        0 => format!("Photosensitive Safe:  < {} >", on_off(settings.photosensitive_safe)),
+        1 => format!("Reduce Motion:  < {} >", on_off(settings.reduce_motion)),
+        2 => format!("Flap Key:  < {} >", audio.flap_key.label()),
+        3 => format!("Leaderboard Name Prompt:  < {} >", on_off(audio.name_prompt)),
+        4 => format!("Auto Score Card:  < {} >", on_off(audio.auto_score_card)),
+        _ => "Back".to_string(),
+    }
+}
 
-        commands.spawn((
-            Text::new("Time: 60"),
+// Same overlay as the pause menu; the run stays frozen underneath since nothing gated on Running runs
+fn setup_pause_settings_ui(mut commands: Commands, fonts: Res<UiFonts>, settings: Res<GameSettings>, audio: Res<AudioSettings>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        GlobalZIndex(10),
+        PauseSettingsMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new("SETTINGS"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 22.0,
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
             TextShadow::default(),
             Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(15.0),
-                left: Val::Percent(45.0),
+                margin: UiRect::all(Val::Px(30.0)),
                 ..default()
             },
-            TimeDisplay,
         ));
-    }
 
-    // Checkpoints setup: initialize checkpoint state and show UI
-    if settings.selected_mode == GameMode::Checkpoints {
-        let checkpoints_state = CheckpointsState::new(settings.selected_difficulty);
-        let target = checkpoints_state.target_score();
-        commands.insert_resource(checkpoints_state);
+        for row in 0..PAUSE_SETTINGS_ROWS {
+            parent.spawn((
+                Text::new(pause_settings_row_text(row, &settings, &audio)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(32.0),
+                    ..default()
+                },
+                TextColor(if row == 0 { AQUAMARINE.into() } else { Color::srgb(1.0, 0.992, 0.816) }),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+                PauseSettingsRow(row),
+            ));
+        }
 
-        commands.spawn((
-            Text::new(format!("Checkpoint: 0/{}", target)),
+        parent.spawn((
+            Text::new("Navigate [UP/DOWN]  Change [LEFT/RIGHT/ENTER]\nBack to Pause [ESC]"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 22.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(20.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.5)),
             TextShadow::default(),
             Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(15.0),
-                left: Val::Percent(45.0),
+                margin: UiRect::top(Val::Px(30.0)),
                 ..default()
             },
-            CheckpointDisplay,
         ));
+    });
+}
+
+fn pause_settings_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut bindings: ResMut<KeyBindings>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+    mut cursor: ResMut<MenuCursor>,
+    mut settings: ResMut<GameSettings>,
+    mut audio: ResMut<AudioSettings>,
+    mut rows: Query<(&PauseSettingsRow, &mut Text, &mut TextColor)>,
+    mut saves: ResMut<SaveWriter>,
+) {
+    let back = cursor.index == PAUSE_SETTINGS_ROWS - 1 && bindings.consume(&mut keyboard, InputAction::MenuConfirm);
+    if back || keyboard.just_pressed(KeyCode::Escape) {
+        cursor.index = PAUSE_SETTINGS_ROW;
+        next_pause.set(PauseState::Paused);
+        return;
     }
-    
-    // Apply theme background; Classic uses a full-screen texture instead of a flat color
-    match settings.selected_theme {
-        Theme::Classic => {
-            commands.insert_resource(ClearColor(Color::BLACK));
-            commands.spawn((
-                Sprite {
-                    image: asset_server.load("Background2.png"),
-                    custom_size: Some(Vec2::new(window_width, window_height)),
-                    ..Default::default()
-                },
-                Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-                Background,
-            ));
-        }
-        Theme::HighContrast => {
-            commands.insert_resource(ClearColor(Color::BLACK));
-            commands.spawn((
-                Sprite {
-                    image: asset_server.load("Background1.png"),
-                    custom_size: Some(Vec2::new(window_width, window_height)),
-                    ..Default::default()
-                },
-                Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
-                Background,
-            ));
-        }
-        Theme::Minimal => {
-            commands.insert_resource(ClearColor(Color::srgb(0.95, 0.95, 0.95)));
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        cursor.index = (cursor.index + PAUSE_SETTINGS_ROWS - 1) % PAUSE_SETTINGS_ROWS;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        cursor.index = (cursor.index + 1) % PAUSE_SETTINGS_ROWS;
+    }
+
+    // Every row is a toggle or a cycle, so either arrow or confirm changes it
+    let change = keyboard.just_pressed(KeyCode::ArrowLeft)
+        || keyboard.just_pressed(KeyCode::ArrowRight)
+        || bindings.consume(&mut keyboard, InputAction::MenuConfirm);
+    if change {
+        match cursor.index {
+            // Slot settings, saved with the run like the Accessibility screen's
+            0 => settings.photosensitive_safe = !settings.photosensitive_safe,
+            1 => settings.reduce_motion = !settings.reduce_motion,
+            _ => {
+                match cursor.index {
+                    2 => {
+                        audio.flap_key = audio.flap_key.next();
+                        *bindings = KeyBindings::new(audio.flap_key);
+                    }
+                    3 => audio.name_prompt = !audio.name_prompt,
+                    _ => audio.auto_score_card = !audio.auto_score_card,
+                }
+                save_audio_settings(&mut saves, &audio);
+            }
         }
     }
 
-    // Spawn bird with selected skin
-    let bird_sprite = match settings.selected_skin {
-        Skin::Classic => "bird.png",
-        Skin::Red => "red_bird.png",
-        Skin::Blue => "blue_bird.png",
-        Skin::Green => "green_bird.png",
-    };
+    for (row, mut text, mut color) in rows.iter_mut() {
+        text.0 = pause_settings_row_text(row.0, &settings, &audio);
+        color.0 = if row.0 == cursor.index {
+            AQUAMARINE.into()
+        } else {
+            Color::srgb(1.0, 0.992, 0.816)
+        };
+    }
+}
 
-    commands.spawn((
-        Sprite {
-            image: asset_server.load(bird_sprite),
-            ..Default::default()
-        },
-        Transform::IDENTITY.with_scale(Vec3::splat(PIXEL_RATIO)),
-        Bird { velocity: 0. },
-    ));
+// Running systems are gated on PauseState, so time spent paused or counting down
+// never reaches the run timers
+fn setup_resume_countdown(mut commands: Commands, fonts: Res<UiFonts>) {
+    commands.insert_resource(ResumeCountdown {
+        remaining: RESUME_COUNTDOWN,
+        buffered_flap: false,
+    });
 
-    // Best Score UI - Top Right
     commands.spawn((
-        Text::new("Best: 0"),
-        TextFont {
-            font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-            font_size: 20.0,
-            ..default()
-        },
-        TextColor(Color::srgb(1.0, 0.992, 0.816)),
-        TextShadow::default(),
         Node {
             position_type: PositionType::Absolute,
-            top: Val::Px(15.0),
-            right: Val::Px(15.0),
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
             ..default()
         },
-        BestScoreDisplay,
-    ));
+        GlobalZIndex(10),
+        ResumeCountdownMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new(format!("{:.0}", RESUME_COUNTDOWN)),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(96.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            ResumeCountdownMarker,
+        ));
+    });
+}
 
-    // Current Score UI - Top Left
-    commands.spawn((
-        Text::new("Score: 0"),
-        TextFont {
-            font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-            font_size: 20.0,
-            ..default()
-        },
-        TextColor(Color::srgb(1.0, 0.992, 0.816)),
-        TextShadow::default(),
-        Node {
-            position_type: PositionType::Absolute,
-            top: Val::Px(15.0),
-            left: Val::Px(15.0),
-            ..default()
-        },
-        ScoreDisplay,
-    ));
+fn resume_countdown_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut countdown: ResMut<ResumeCountdown>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+    mut text_query: Query<&mut Text, With<ResumeCountdownMarker>>,
+) {
+    countdown.remaining -= time.delta_secs();
+    let shown = countdown.remaining.ceil().max(1.0);
 
-    let mut rand = thread_rng();
-    spawn_obstacles(&mut commands, &mut rand, window_width, &pipe_image, tuning);
-}
+    // Flapping on "1" skips the rest and flaps right away; earlier presses are held
+    if bindings.just_pressed(&keyboard, InputAction::Flap) {
+        if shown <= 1.0 {
+            countdown.remaining = 0.0;
+        }
+        countdown.buffered_flap = true;
+    }
 
-fn get_centered_pipe_position(gap_size: f32) -> f32 {
-    return (OBSTACLE_HEIGHT / 2. + gap_size) * PIXEL_RATIO;
+    if countdown.remaining <= 0.0 {
+        if countdown.buffered_flap {
+            commands.insert_resource(HeldFlap);
+        }
+        next_pause.set(PauseState::Running);
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = format!("{:.0}", shown);
+    }
 }
 
-fn spawn_obstacles(
-    commands: &mut Commands,
-    rand: &mut ThreadRng,
-    window_width: f32,
-    pipe_image: &Handle<Image>,
-    tuning: DifficultyTuning,
+// Flaps spawned by update_bird get a small random speed change before their sink is created.
+// Drawn from the run's seeded pitch stream, so a replay hears the same flaps
+fn vary_flap_pitch(
+    audio: Res<AudioSettings>,
+    course: Option<ResMut<CourseRng>>,
+    mut flaps: Query<(&SfxKind, &mut PlaybackSettings), Added<SfxKind>>,
 ) {
-    // Spawn paired top/bottom pipes spaced across the screen
-    for i in 0..OBSTACLE_AMOUNT {
-        let y_offset = generate_offset(rand, tuning.vertical_offset);
-        let x_pos = window_width / 2. + (OBSTACLE_SPACING * PIXEL_RATIO * i as f32);
-        spawn_obstacle(
-            Vec3::X * x_pos + Vec3::Y * (get_centered_pipe_position(tuning.gap_size) + y_offset),
-            1.,
-            commands,
-            pipe_image,
-        );
+    let Some(mut course) = course.filter(|_| audio.flap_pitch_variation) else { return; };
+    for (kind, mut playback) in flaps.iter_mut() {
+        if *kind == SfxKind::Flap {
+            playback.speed = 1.0 + course.pitch.gen_range(-FLAP_PITCH_VARIATION..=FLAP_PITCH_VARIATION);
+        }
+    }
+}
 
-        spawn_obstacle(
-            Vec3::X * x_pos + Vec3::Y * (-get_centered_pipe_position(tuning.gap_size) + y_offset),
-            -1.,
-            commands,
-            pipe_image,
-        );
+// Leans the world camera toward where the bird is heading; cleanup_game re-centers it
+fn drift_camera(
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    bird_query: Query<&Bird>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    let target = match bird_query.single() {
+        Ok(bird) if !settings.reduce_motion => {
+            (bird.velocity / CAMERA_DRIFT_VELOCITY).clamp(-1.0, 1.0) * CAMERA_DRIFT_MAX
+        }
+        _ => 0.0,
+    };
+    let ease = 1.0 - (-CAMERA_DRIFT_EASE * time.delta_secs()).exp();
+    for mut camera in camera_query.iter_mut() {
+        camera.translation.y += (target - camera.translation.y) * ease;
     }
 }
 
-fn spawn_obstacle(
-    translation: Vec3,
-    pipe_direction: f32,
-    commands: &mut Commands,
-    pipe_image: &Handle<Image>,
+// Sinks only exist a frame after the sound spawns, so a ducked voice re-applies its
+// volume every frame until the duck runs out and it is restored to its own level
+fn update_sfx_bus(
+    time: Res<Time<Real>>,
+    config: Res<GameConfig>,
+    mut bus: ResMut<SfxBus>,
+    started: Query<(Entity, &SfxKind), Added<SfxKind>>,
+    mut voices: Query<(Option<&mut AudioSink>, &PlaybackSettings), With<SfxKind>>,
 ) {
-    commands.spawn((
-        Sprite {
-            image: pipe_image.clone(),
-            ..Default::default()
-        },
-        Transform::from_translation(translation).with_scale(Vec3::new(
-            PIXEL_RATIO,
-            PIXEL_RATIO * -pipe_direction,
-            PIXEL_RATIO,
-        )),
-        Obstacle { 
-            pipe_direction,
-            scored: false,
-        },
-    ));
+    let policy = config.ducking;
+    for (entity, kind) in &started {
+        bus.voices.push(SfxVoice { entity, kind: *kind, ducked: 0.0 });
+    }
+    for (_, kind) in &started {
+        let priority = policy.priority(*kind);
+        for voice in bus.voices.iter_mut().filter(|voice| policy.priority(voice.kind) < priority) {
+            voice.ducked = policy.duration;
+        }
+    }
+
+    let dt = time.delta_secs();
+    bus.voices.retain_mut(|voice| {
+        let Ok((sink, playback)) = voices.get_mut(voice.entity) else { return false; };
+        if let Some(scale) = voice.fade(dt, policy.amount)
+            && let Some(mut sink) = sink
+        {
+            sink.set_volume(Volume::Linear(playback.volume.to_linear() * scale));
+        }
+        true
+    });
+}
+
+fn apply_music_volume(audio: Res<AudioSettings>, mut music: Query<&mut AudioSink, With<MusicTrack>>) {
+    if !audio.is_changed() {
+        return;
+    }
+    for mut sink in music.iter_mut() {
+        sink.set_volume(audio.music());
+    }
 }
 
-fn generate_offset(rand: &mut ThreadRng, vertical_offset: f32) -> f32 {
-    return rand.gen_range(-vertical_offset..vertical_offset) * PIXEL_RATIO;
+fn restart_run(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Playing);
 }
 
-fn update_obstacles(
-    time: Res<Time>,
-    game_manager: Res<GameManager>,
-    tuning: Res<DifficultyTuning>,
-    mut obstacle_query: Query<(&mut Obstacle, &mut Transform)>,
+fn load_audio_settings(saves: &SaveWriter) -> AudioSettings {
+    saves
+        .store()
+        .read_file(SETTINGS_PATH)
+        .and_then(|contents| parse_audio_settings(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn parse_audio_settings(contents: &str) -> Result<AudioSettings, serde_json::Error> {
+    serde_json::from_str(contents)
+}
+
+// Hands the settings to the save writer, which writes them off the main thread
+fn save_audio_settings(saves: &mut SaveWriter, audio: &AudioSettings) {
+    match serde_json::to_string_pretty(audio) {
+        Ok(json) => saves.write_settings(json),
+        Err(e) => eprintln!("Failed to serialize settings: {}", e),
+    }
+}
+
+// ---------------------------- TOURNAMENT ----------------------------
+// Rounds chain Playing -> RoundSummary -> Playing until all are played
+fn record_tournament_round(
+    score: Res<Score>,
+    settings: Res<GameSettings>,
+    tournament: Option<ResMut<TournamentState>>,
+    mut saves: ResMut<SaveWriter>,
 ) {
-    // Scroll pipes and recycle them when they exit left
-    for (mut obstacle, mut transform) in obstacle_query.iter_mut() {
-        transform.translation.x -= time.delta_secs() * tuning.scroll_speed;
+    let Some(mut tournament) = tournament else { return; };
+    tournament.scores.push(score.current);
+    tournament.round = tournament.scores.len();
 
-        if transform.translation.x + OBSTACLE_WIDTH * PIXEL_RATIO / 2.
-            < -game_manager.window_dimensions.x / 2.
-        {
-            transform.translation.x += OBSTACLE_AMOUNT as f32 * OBSTACLE_SPACING * PIXEL_RATIO;
-            let mut rand = thread_rng();
-            let y_offset = generate_offset(&mut rand, tuning.vertical_offset);
-            transform.translation.y =
-                get_centered_pipe_position(tuning.gap_size) * obstacle.pipe_direction + y_offset;
-            obstacle.scored = false;
-        }
+    if !tournament.is_finished() {
+        return;
+    }
+
+    // Persist the tournament best separately from regular high scores; modified tournaments aren't ranked
+    let Some(slot_num) = settings.current_slot.filter(|_| !settings.modifiers.any() && !settings.is_challenge() && !settings.is_custom()) else { return; };
+    let mut save = saves.load(slot_num).unwrap_or_else(|| new_save_slot(slot_num, &settings));
+    save.profile.tournament_best = save.profile.tournament_best.max(tournament.total());
+    saves.write(save);
+}
+
+// Save for a slot that has none yet, holding only the current settings
+fn new_save_slot(slot_num: u8, settings: &GameSettings) -> SaveSlot {
+    SaveSlot {
+        slot_number: slot_num,
+        profile: PlayerProfile {
+            name: format!("Player {}", slot_num),
+            ..default()
+        },
+        mode: settings.selected_mode,
+        difficulty: settings.selected_difficulty,
+        theme: settings.selected_theme,
+        skin: settings.selected_skin,
+        score: 0,
+        survival_time: 0.0,
+        photosensitive_safe: settings.photosensitive_safe,
+        bird_size: settings.bird_size,
+        floor_warning: settings.floor_warning,
+        floor_warning_sound: settings.floor_warning_sound,
+        gap_tone: settings.gap_tone,
+        blind_run: settings.blind_run,
+        spawn_strategy: settings.spawn_strategy,
+        level: settings.selected_level.clone(),
+        reduce_motion: settings.reduce_motion,
+        overtime: settings.overtime,
+        record_marker: settings.record_marker,
+        gap_telegraph: settings.gap_telegraph,
+        fill_window: settings.fill_window,
+        camera_zoom: settings.camera_zoom,
+        custom_tuning: settings.custom_tuning,
+        flags: run_flags(settings),
+        run_name: None,
     }
 }
 
-fn update_bird(
+fn setup_round_summary_ui(
     mut commands: Commands,
-    mut bird_query: Query<(&mut Bird, &mut Transform), Without<Obstacle>>,
-    mut obstacle_query: Query<(&mut Obstacle, &Transform, Entity)>,
-    time: Res<Time>,
-    keys: Res<ButtonInput<KeyCode>>,
-    game_manager: Res<GameManager>,
-    sound_effects: Res<SoundEffects>,
-    mut score: ResMut<Score>,
-    mut state: ResMut<NextState<GameState>>,
-    settings: Res<GameSettings>,
-    tuning: Res<DifficultyTuning>,
-    checkpoints_state: Option<ResMut<CheckpointsState>>,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    tournament: Option<Res<TournamentState>>,
+    bindings: Res<KeyBindings>,
+    saves: Res<SaveWriter>,
 ) {
-    if let Ok((mut bird, mut transform)) = bird_query.single_mut() {
-        // Input + physics
-        if keys.just_pressed(KeyCode::Space) {
-            bird.velocity = FLAP_FORCE * tuning.flap_mult;
-            commands.spawn((
-            AudioPlayer::new(sound_effects.flap.clone()),
-            PlaybackSettings {
-                volume: Volume::Linear(0.1),
-                ..PlaybackSettings::DESPAWN
-        }
-        ));
-        }
+    let window = window_query.single().expect("Missing primary window");
+    let window_width = window.width();
+    let window_height = window.height();
+    let Some(tournament) = tournament else { return; };
+
+    commands.spawn((
+        Sprite {
+            image: asset_server.load("Background2.png"),
+            custom_size: Some(Vec2::new(window_width, window_height)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, -50.0)),
+        Background,
+        RoundSummaryMarker,
+    ));
+
+    let finished = tournament.is_finished();
+    let title = if finished {
+        "TOURNAMENT COMPLETE".to_string()
+    } else {
+        format!("ROUND {} COMPLETE", tournament.round)
+    };
 
-        bird.velocity -= time.delta_secs() * GRAVITY * tuning.gravity_mult;
-        transform.translation.y += bird.velocity * time.delta_secs();
+    let mut lines: Vec<String> = tournament
+        .scores
+        .iter()
+        .enumerate()
+        .map(|(i, score)| format!("Round {}: {} pts", i + 1, score))
+        .collect();
+    lines.push(format!("Total: {} pts", tournament.total()));
+
+    // Final ranking against every slot's best tournament
+    if finished {
+        let mut ranking: Vec<(String, u32)> = (1..=3)
+            .filter_map(|slot| saves.load(slot))
+            .map(|save| (save.profile.name, save.profile.tournament_best))
+            .filter(|(_, best)| *best > 0)
+            .collect();
+        ranking.sort_by_key(|r| std::cmp::Reverse(r.1));
+        for (i, (name, best)) in ranking.iter().enumerate() {
+            lines.push(format!("#{} {} - {} pts", i + 1, name, best));
+        }
+    }
 
-        transform.rotation = Quat::from_axis_angle(
-            Vec3::Z,
-            f32::clamp(bird.velocity / VELOCITY_TO_ROTATION_RATIO, -90., 90.).to_radians(),
-        );
+    let prompt = if finished {
+        format!("RETURN TO MAIN MENU [{}]", bindings.advance_hint())
+    } else {
+        format!("START ROUND {} [{}]\nABANDON TOURNAMENT [ESC]", tournament.round + 1, bindings.advance_hint())
+    };
 
-        // Collision and scoring
-        let mut dead = false;
-        if transform.translation.y <= -game_manager.window_dimensions.y / 2. {
-            dead = true;
-        } else {
-            for (mut obstacle, pipe_transform, _) in obstacle_query.iter_mut() {
-                if !obstacle.scored && transform.translation.x > pipe_transform.translation.x {
-                    if obstacle.pipe_direction == 1.0 {
-                        score.current += 1;
-                        if score.current > score.best {
-                            score.best = score.current;
-                        }
-                        obstacle.scored = true;
-                        commands.spawn((
-                            AudioPlayer::new(sound_effects.point.clone()),
-                            PlaybackSettings {
-                                volume: Volume::Linear(0.1),
-                                ..PlaybackSettings::DESPAWN // Fix for overlapping sounds and volume adjusted
-                            }     
-                        ));
-                    }
-                }
+    commands.spawn((
+        menu_root_node(),
+        RoundSummaryMarker,
+    ))
+    .with_children(|parent| {
+        parent.spawn((
+            Text::new(title),
+            TextFont {
+                font: fonts.title.clone(),
+                font_size: fonts.heading(56.0),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.992, 0.816)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::all(Val::Px(25.0)),
+                ..default()
+            },
+        ));
 
-                if (pipe_transform.translation.y - transform.translation.y).abs()
-                    < OBSTACLE_HEIGHT * PIXEL_RATIO / 2.
-                    && (pipe_transform.translation.x - transform.translation.x).abs()
-                        < OBSTACLE_WIDTH * PIXEL_RATIO / 2.
-                {
-                    dead = true;
-                    break;
-                }
-            }
-        }
-        
-        if dead {
-            commands.spawn((
-                AudioPlayer::new(sound_effects.die.clone()),
-                PlaybackSettings {
-                    volume: Volume::Linear(0.1),
-                    ..PlaybackSettings::DESPAWN
-            }
+        for line in lines {
+            parent.spawn((
+                Text::new(line),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(28.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.992, 0.816)),
+                TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+                TextShadow::default(),
+                Node {
+                    margin: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
             ));
+        }
 
-            // Handle Checkpoints mode respawn
-            if settings.selected_mode == GameMode::Checkpoints {
-                if let Some(cp_state) = checkpoints_state {
-                    // Reset score to last checkpoint and respawn
-                    score.current = cp_state.last_checkpoint_score;
-                    bird.velocity = 0.0;
-                    transform.translation.y = 0.0;
-                    return; // Don't go to game over, just respawn
-                }
-            }
+        parent.spawn((
+            Text::new(prompt),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
+            TextShadow::default(),
+            Node {
+                margin: UiRect::top(Val::Px(30.0)),
+                ..default()
+            },
+        ));
+    });
+}
 
-            // Save game data
-            if let Some(slot_num) = settings.current_slot {
-                 let save_data = load_save_slot(slot_num as u32);
-                    let mut profile = save_data.map(|s| s.profile).unwrap_or_else(|| PlayerProfile {
-                    name: format!("Player {}", slot_num),
-                    high_score: 0,
-                    total_games: 0,
-                    average_score: 0.0,
-                    longest_survival: 0.0,
-                });
-                
-                profile.total_games += 1;
-                if score.current > profile.high_score {
-                    profile.high_score = score.current;
-                }
-                profile.average_score = ((profile.average_score * (profile.total_games - 1) as f32) 
-                    + score.current as f32) / profile.total_games as f32;
-                
-                let save_slot = SaveSlot {
-                    slot_number: slot_num,
-                    profile,
-                    mode: settings.selected_mode,
-                    difficulty: settings.selected_difficulty,
-                    theme: settings.selected_theme,
-                    skin: settings.selected_skin,
-                    score: score.current,
-                    survival_time: 0.0,
-                };
-                
-                let _ = save_to_slot(&save_slot);
-            }
-            
-            state.set(GameState::GameOver);
-        }
+fn round_summary_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut fade: ResMut<ScreenFade>,
+    tournament: Option<Res<TournamentState>>,
+) {
+    let finished = tournament.is_none_or(|t| t.is_finished());
+
+    if bindings.consume(&mut keyboard, InputAction::Advance) {
+        fade.request_transition(if finished { GameState::MainMenu } else { GameState::Playing });
+    } else if keyboard.just_pressed(KeyCode::Escape) {
+        fade.request_transition(GameState::MainMenu);
     }
 }
 
-fn update_ui(
-    mut score_query: Query<&mut Text, (With<ScoreDisplay>, Without<BestScoreDisplay>)>,
-    mut best_score_query: Query<&mut Text, With<BestScoreDisplay>>,
+fn clear_tournament(mut commands: Commands) {
+    commands.remove_resource::<TournamentState>();
+}
+
+fn handle_game_over(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut fade: ResMut<ScreenFade>,
+    mut settings: ResMut<GameSettings>,
+    course: Option<Res<CourseRng>>,
+    mut status: ResMut<RunCodeStatus>,
     score: Res<Score>,
+    recording: Res<RunRecording>,
+    trainer: Res<TrainerTuning>,
+    prompt: Option<Res<NamePrompt>>,
+    config: Res<GameConfig>,
 ) {
-    for mut text in score_query.iter_mut() {
-        text.0 = format!("Score: {}", score.current);
+    // Keys type the name until the prompt is answered
+    if prompt.is_some() {
+        return;
     }
 
-    for mut text in best_score_query.iter_mut() {
-        text.0 = format!("Best: {}", score.best);
+    if bindings.consume(&mut keyboard, InputAction::Advance) {
+        fade.request_transition(GameState::MainMenu);
     }
-}
 
-fn update_time_attack(
-    time: Res<Time>,
-    settings: Res<GameSettings>,
-    timer: Option<ResMut<TimeAttackState>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut time_ui: Query<&mut Text, With<TimeDisplay>>,
-) {
-    if settings.selected_mode != GameMode::TimeAttack {
-        return;
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        fade.request_transition(GameState::Restarting);
     }
 
-    // Count down and end the run at zero
-    let Some(mut timer) = timer else { return; };
-    timer.remaining -= time.delta_secs();
-    if let Some(mut txt) = time_ui.iter_mut().next() {
-        txt.0 = format!("Time: {:.0}", timer.remaining.max(0.0));
+    if keyboard.just_pressed(KeyCode::KeyD) {
+        fade.request_transition(GameState::RunDetails);
     }
 
-    if timer.remaining <= 0.0 {
-        next_state.set(GameState::GameOver);
+    // Pins this run's seed, which flags the retry as a known layout
+    if keyboard.just_pressed(KeyCode::KeyL)
+        && settings.selected_mode != GameMode::Level
+        && let Some(course) = &course
+    {
+        settings.seed = Some(course.seed);
+        fade.request_transition(GameState::Restarting);
     }
-}
 
-fn update_checkpoints(
-    mut commands: Commands,
-    settings: Res<GameSettings>,
-    checkpoints: Option<ResMut<CheckpointsState>>,
-    score: Res<Score>,
-    sound_effects: Res<SoundEffects>,
-    mut checkpoint_ui: Query<&mut Text, With<CheckpointDisplay>>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    if settings.selected_mode != GameMode::Checkpoints {
-        return;
+    if keyboard.just_pressed(KeyCode::KeyC)
+        && let Some(course) = &course
+    {
+        let code = encode_run_code(settings.selected_mode, settings.selected_difficulty, course.seed);
+        status.message = Some(copy_run_code(&code));
     }
 
-    let Some(mut checkpoints_state) = checkpoints else { return; };
-    
-    // Update UI
-    if let Some(mut txt) = checkpoint_ui.iter_mut().next() {
-        let target = checkpoints_state.target_score();
-        txt.0 = format!("Checkpoint: {}/{}", score.current, target);
+    if keyboard.just_pressed(KeyCode::KeyS) {
+        status.message = Some(match (&course, settings.selected_mode) {
+            (_, GameMode::Tournament) => "Tournament rounds can't be exported as replays".to_string(),
+            (None, _) => "Nothing to export".to_string(),
+            (Some(course), _) => match save_replay(&settings, &config, trainer.gap_size, course.seed, score.current, &recording) {
+                Ok(path) => format!("Replay saved to {}", path.display()),
+                Err(e) => format!("Failed to save replay: {}", e),
+            },
+        });
     }
+}
 
-    // Check if player reached current checkpoint
-    if !checkpoints_state.completed && score.current >= checkpoints_state.target_score() {
-        // Play checkpoint sound
-        commands.spawn((
-            AudioPlayer::new(sound_effects.swoosh.clone()),
-            PlaybackSettings {
-                volume: Volume::Linear(0.2),
-                ..PlaybackSettings::DESPAWN
-            }
-        ));
+// S/A/B/C for a finished run. Score is ranked against the slot's recent history and survival
+// against its longest run, so the curve rises as the player improves
+fn grade_run(score: &Score, profile: Option<&PlayerProfile>) -> char {
+    let history = profile.map_or(&[][..], |profile| &profile.history[..]);
+    let standing = if history.len() >= GRADE_MIN_HISTORY {
+        // Share of recent runs this one beat, ties counting half
+        let beaten: f32 = history
+            .iter()
+            .map(|&past| match past.cmp(&score.current) {
+                std::cmp::Ordering::Less => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Greater => 0.0,
+            })
+            .sum();
+        beaten / history.len() as f32
+    } else {
+        (score.current as f32 / GRADE_STARTER_SCORE).min(1.0)
+    };
+    let longest = profile.map_or(0.0, |profile| profile.longest_survival).max(score.log.elapsed);
+    let survival = if longest > 0.0 { score.log.elapsed / longest } else { 0.0 };
 
-        // Save this checkpoint as the respawn point
-        checkpoints_state.last_checkpoint_score = checkpoints_state.target_score();
+    let (score_weight, survival_weight, precision_weight) = GRADE_WEIGHTS;
+    let composite = match score.precision.average() {
+        Some(precision) => standing * score_weight + survival * survival_weight + precision / 100.0 * precision_weight,
+        None => standing * (score_weight + precision_weight) + survival * survival_weight,
+    };
+    GRADE_THRESHOLDS
+        .iter()
+        .find(|(_, threshold)| composite >= *threshold)
+        .map_or('C', |(grade, _)| *grade)
+}
 
-        if checkpoints_state.is_final_checkpoint() {
-            // Final checkpoint reached - Victory!
-            checkpoints_state.completed = true;
-            
-            // Transition to victory screen
-            next_state.set(GameState::Victory);
-        } else {
-            // Move to next checkpoint
-            checkpoints_state.current_checkpoint_index += 1;
-            
-            // Update UI with new target
-            if let Some(mut txt) = checkpoint_ui.iter_mut().next() {
-                let new_target = checkpoints_state.target_score();
-                txt.0 = format!("Checkpoint: {}/{}", score.current, new_target);
-            }
-        }
+fn grade_color(grade: char) -> Color {
+    match grade {
+        'S' => Color::srgb(1.0, 0.84, 0.0),
+        'A' => Color::srgb(0.4, 0.9, 0.45),
+        'B' => Color::srgb(0.45, 0.75, 1.0),
+        _ => Color::srgb(0.75, 0.75, 0.75),
     }
 }
 
-fn handle_game_over(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
+fn setup_game_over_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fonts: Res<UiFonts>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    score: Res<Score>,
+    settings: Res<GameSettings>,
+    course: Option<Res<CourseRng>>,
+    replay: Option<Res<ReplayPlayback>>,
+    saves: Res<SaveWriter>,
+    palette: Option<Res<ThemePalette>>,
+    bindings: Res<KeyBindings>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        next_state.set(GameState::MainMenu);
-    }
-}
+    // Colors of the theme just played, so its text stays readable
+    let palette = palette.map_or_else(|| settings.selected_theme.palette(), |palette| *palette);
 
-fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>, score: Res<Score>) {
     let window = window_query.single().expect("Missing primary window");
     let window_width = window.width();
     let window_height = window.height();
@@ -2229,74 +13171,277 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, wi
 
     // Simple summary screen after a run ends
     commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
+        menu_root_node(),
         GameOverMarker,
     ))
     .with_children(|parent| {
         parent.spawn((
             Text::new("GAME OVER"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
                 ..default()
             },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextShadow::default(),
+            TextColor(palette.hud_text),
+            palette.text_shadow(),
             Node {
                 margin: UiRect::all(Val::Px(30.0)),
                 ..default()
             },
         ));
 
-        parent.spawn((
-            Text::new(format!("SCORE: {}", score.current)),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 40.0,
-                ..default()
-            },
-            TextColor(Color::srgb(1.0, 0.992, 0.816)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(15.0)),
-                ..default()
-            },
-        ));
+        let save = settings.current_slot.and_then(|slot| saves.load(slot));
+        if let Some(save) = &save {
+            parent.spawn(Node {
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|row| {
+                spawn_avatar(row, &asset_server, save.profile.avatar, 32.0);
+                row.spawn((
+                    Text::new(save.profile.name.clone()),
+                    TextFont {
+                        font: fonts.body.clone(),
+                        font_size: fonts.scaled(28.0),
+                        ..default()
+                    },
+                    TextColor(palette.hud_text),
+                    palette.text_shadow(),
+                ));
+            });
+        }
+
+        parent.spawn((
+            Text::new(format!("SCORE: {}", score.current)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
+                ..default()
+            },
+            TextColor(palette.hud_text),
+            TextBackgroundColor(palette.panel),
+            palette.text_shadow(),
+            Node {
+                margin: UiRect::all(fonts.margin(15.0)),
+                ..default()
+            },
+        ));
+
+        // Replays are someone's earlier run, so they aren't graded. An unranked run never
+        // reached the slot, so the slot as it is now is the profile from before the run
+        if replay.is_none() {
+            let grade = score.grade.unwrap_or_else(|| grade_run(&score, save.as_ref().map(|save| &save.profile)));
+            parent.spawn((
+                Text::new(format!("GRADE {}", grade)),
+                TextFont {
+                    font: fonts.title.clone(),
+                    font_size: fonts.heading(56.0),
+                    ..default()
+                },
+                TextColor(grade_color(grade)),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        parent.spawn((
+            Text::new(format!("BEST: {}", score.best)),
+            TextFont {
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
+                ..default()
+            },
+            TextColor(palette.muted),
+            TextBackgroundColor(palette.panel),
+            palette.text_shadow(),
+            Node {
+                margin: UiRect::all(fonts.margin(10.0)),
+                ..default()
+            },
+        ));
+
+        if let Some(precision) = score.precision.average() {
+            let best = save
+                .as_ref()
+                .map(|save| format!("   BEST: {:.0}%", save.profile.best_precision))
+                .unwrap_or_default();
+            parent.spawn((
+                Text::new(format!(
+                    "PRECISION: {:.0}%{}\n{} PERFECT / {} GOOD / {} OK   PERFECT STREAK: {}",
+                    precision, best, score.precision.perfect, score.precision.good, score.precision.ok, score.precision.best_streak,
+                )),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.muted),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        if settings.is_challenge() {
+            parent.spawn((
+                Text::new(format!("CHALLENGE RUN - NOT RANKED ({})", settings.challenge_label())),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        if settings.zoomed_out() {
+            parent.spawn((
+                Text::new(format!("ZOOMED OUT - NOT RANKED ({:.0}%)", settings.camera_zoom * 100.0)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        if settings.gap_telegraph {
+            parent.spawn((
+                Text::new("ASSISTED RUN - NOT RANKED (Gap Telegraph)"),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        if score.custom_config && replay.is_none() {
+            parent.spawn((
+                Text::new("CUSTOM CONFIG - NOT RANKED (config.ron)"),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        if settings.modifiers.any() {
+            parent.spawn((
+                Text::new(format!("MODIFIED RUN - NOT RANKED ({})", settings.modifiers.names().join(", "))),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        // Replays show whether the re-simulation matched the recorded run
+        if let Some(replay) = &replay {
+            let verdict = if replay.recorded_score == score.current { "MATCH" } else { "MISMATCH" };
+            parent.spawn((
+                Text::new(format!("REPLAY - RECORDED SCORE: {} ({})", replay.recorded_score, verdict)),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
+        }
+
+        if let Some(course) = &course {
+            parent.spawn((
+                Text::new(format!(
+                    "RUN CODE: {} [C]   RETRY THIS EXACT LAYOUT [L]",
+                    encode_run_code(settings.selected_mode, settings.selected_difficulty, course.seed)
+                )),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.muted),
+                TextBackgroundColor(palette.panel),
+                palette.text_shadow(),
+                Node {
+                    margin: UiRect::all(fonts.margin(10.0)),
+                    ..default()
+                },
+            ));
 
-        parent.spawn((
-            Text::new(format!("BEST: {}", score.best)),
-            TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
-                ..default()
-            },
-            TextColor(Color::srgb(0.85, 0.95, 1.0)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
-            Node {
-                margin: UiRect::all(Val::Px(10.0)),
-                ..default()
-            },
-        ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font: fonts.body.clone(),
+                    font_size: fonts.scaled(20.0),
+                    ..default()
+                },
+                TextColor(palette.accent),
+                palette.text_shadow(),
+                RunCodeStatusDisplay,
+            ));
+        }
 
         parent.spawn((
-            Text::new("RETURN TO MAIN MENU [SPACE]"),
+            Text::new(format!("RETRY [R]   SAVE REPLAY [S]   RUN DETAILS [D]   SCORE CARD [K]\nRETURN TO MAIN MENU [{}]", bindings.advance_hint())),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
-            TextColor(Color::srgb(0.9, 0.9, 0.9)),
-            TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
-            TextShadow::default(),
+            TextColor(palette.muted),
+            TextBackgroundColor(palette.panel),
+            palette.text_shadow(),
             Node {
                 margin: UiRect::top(Val::Px(40.0)),
                 ..default()
@@ -2305,7 +13450,7 @@ fn setup_game_over_ui(mut commands: Commands, asset_server: Res<AssetServer>, wi
     });
 }
 
-fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window, With<PrimaryWindow>>, score: Res<Score>) {
+fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>, fonts: Res<UiFonts>, window_query: Query<&Window, With<PrimaryWindow>>, score: Res<Score>, audio: Res<AudioSettings>, settings: Res<GameSettings>, bindings: Res<KeyBindings>) {
     let window = window_query.single().expect("Missing primary window");
     let window_width = window.width();
     let window_height = window.height();
@@ -2326,30 +13471,24 @@ fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>, wind
     commands.spawn((
         AudioPlayer::new(asset_server.load("35-Lost-Woods.ogg")),
         PlaybackSettings {
-            volume: Volume::Linear(0.1),
+            volume: audio.music(),
             ..PlaybackSettings::LOOP
         },
+        MusicTrack,
         VictoryScreenMarker,
     ));
 
     // Victory screen UI
     commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
+        menu_root_node(),
         VictoryScreenMarker,
     ))
     .with_children(|parent| {
         parent.spawn((
             Text::new("🎉 VICTORY! 🎉"),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 64.0,
+                font: fonts.title.clone(),
+                font_size: fonts.heading(64.0),
                 ..default()
             },
             TextColor(Color::srgb(1.0, 0.84, 0.0)),
@@ -2361,10 +13500,13 @@ fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>, wind
         ));
 
         parent.spawn((
-            Text::new("ALL CHECKPOINTS COMPLETED!"),
+            Text::new(match settings.score_goal {
+                Some(goal) if score.current >= goal => "GOAL REACHED!",
+                _ => "ALL CHECKPOINTS COMPLETED!",
+            }),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 32.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(32.0),
                 ..default()
             },
             TextColor(Color::srgb(0.2, 1.0, 0.4)),
@@ -2378,24 +13520,24 @@ fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>, wind
         parent.spawn((
             Text::new(format!("FINAL SCORE: {}", score.current)),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 40.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(40.0),
                 ..default()
             },
             TextColor(Color::srgb(0.85, 0.95, 1.0)),
             TextBackgroundColor(Color::BLACK.with_alpha(0.2)),
             TextShadow::default(),
             Node {
-                margin: UiRect::all(Val::Px(10.0)),
+                margin: UiRect::all(fonts.margin(10.0)),
                 ..default()
             },
         ));
 
         parent.spawn((
-            Text::new("RETURN TO MAIN MENU [SPACE]"),
+            Text::new(format!("RETURN TO MAIN MENU [{}]", bindings.advance_hint())),
             TextFont {
-                font: asset_server.load("fonts/BBHHegarty-Regular.ttf"),
-                font_size: 24.0,
+                font: fonts.body.clone(),
+                font_size: fonts.scaled(24.0),
                 ..default()
             },
             TextColor(Color::srgb(0.9, 0.9, 0.9)),
@@ -2410,54 +13552,1239 @@ fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>, wind
 }
 
 fn handle_victory(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut fade: ResMut<ScreenFade>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        next_state.set(GameState::MainMenu);
+    if bindings.consume(&mut keyboard, InputAction::Advance) {
+        fade.request_transition(GameState::MainMenu);
+    }
+}
+
+// A second bird makes every bird_query.single_mut() fail and the run freezes,
+// so keep the first one and despawn the rest
+fn ensure_single_bird(mut commands: Commands, bird_query: Query<Entity, With<Bird>>) {
+    let mut birds: Vec<Entity> = bird_query.iter().collect();
+    if birds.len() <= 1 {
+        return;
+    }
+    // Entity's own ordering isn't spawn order; in a fresh run the lowest index is the first bird
+    birds.sort_by_key(|bird| bird.index());
+    eprintln!("Found {} birds on run start, despawning the extras", birds.len());
+    for extra in birds.into_iter().skip(1) {
+        commands.entity(extra).despawn();
     }
 }
 
 fn reset_on_play_start(
     mut commands: Commands,
     mut bird_query: Query<(&mut Bird, &mut Transform)>,
-    obstacle_query: Query<Entity, With<Obstacle>>,
+    obstacle_query: Query<Entity, Or<(With<Obstacle>, With<Coin>, With<FlipPickup>)>>,
     game_manager: Option<Res<GameManager>>,
     tuning: Option<Res<DifficultyTuning>>,
+    course: Option<ResMut<CourseRng>>,
+    level: Option<Res<LevelRun>>,
     sound_effects: Res<SoundEffects>,
     mut score: ResMut<Score>,
+    audio: Res<AudioSettings>,
+    settings: Res<GameSettings>,
+    mut recording: ResMut<RunRecording>,
+    replay: Option<ResMut<ReplayPlayback>>,
+    mut trail: ResMut<DeathTrail>,
+    mut progress: Option<ResMut<GapProgress>>,
+    config: Res<GameConfig>,
 ) {
     // Reset player state and respawn pipes before a new run
+    commands.insert_resource(GameOverSeen(false));
     commands.spawn((
         AudioPlayer::new(sound_effects.swoosh.clone()),
         PlaybackSettings {
-            volume: Volume::Linear(0.1),
+            volume: audio.sfx(0.1),
             ..PlaybackSettings::DESPAWN
         },
     ));
 
-    score.current = 0;
+    score.current = settings.start_score;
     score.scored_pipes.clear();
+    score.combo = 0;
+    score.combo_timer = 0.0;
+    score.combo_flash = 0.0;
+    score.precision = PrecisionStats::default();
+
+    recording.bird_size = settings.bird_size;
+    recording.steps.clear();
+    *trail = DeathTrail::default();
+    if let Some(mut replay) = replay {
+        replay.cursor = 0;
+    }
     
+    let Some(game_manager) = game_manager else { return; };
     if let Ok((mut bird, mut transform)) = bird_query.single_mut() {
         bird.velocity = 0.;
-        transform.translation = Vec3::ZERO;
+        bird.tilt = 0.;
+        bird.flap_ticks = 0;
+        transform.translation = Vec3::X * config.bird_x(game_manager.window_dimensions.x);
         transform.rotation = Quat::IDENTITY;
     }
 
-    let Some(game_manager) = game_manager else { return; };
     let Some(tuning) = tuning else { return; };
+    let Some(mut course) = course else { return; };
 
     for entity in obstacle_query.iter() {
         commands.entity(entity).despawn();
     }
 
-    let mut rand = thread_rng();
-    spawn_obstacles(
+    // Level courses are streamed from their first pipe
+    if level.is_some() {
+        return;
+    }
+
+    // A raised start score starts with the gaps already shrunk to match, without the notice
+    let mut tuning = *tuning;
+    if let Some(progress) = progress.as_mut() {
+        progress.sync(score.current);
+        tuning.gap_size = progress.gap_size(&tuning);
+    }
+
+    // Replay the seed from the start so the course matches the run code
+    course.restart();
+    let last_offset = spawn_obstacles(
         &mut commands,
-        &mut rand,
+        &mut course.rng,
         game_manager.window_dimensions.x,
         &game_manager.pipe_image,
-        *tuning,
+        tuning,
+        settings.gauntlet_start,
     );
-}
\ No newline at end of file
+    if let Some(mut progress) = progress {
+        progress.last_offset = last_offset;
+    }
+}
+
+// ---------------------------- TESTS ----------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    // Headless course: the starting pairs plus everything update_obstacles reads
+    fn course_world(settings: GameSettings, seed: u64) -> World {
+        let mut world = World::new();
+        let config = GameConfig::default();
+        let tuning = difficulty_tuning(settings.selected_difficulty, &config);
+        let mut course = CourseRng::new(seed);
+        let mut progress = GapProgress::new(None);
+        let mut commands = world.commands();
+        progress.last_offset = spawn_obstacles(
+            &mut commands,
+            &mut course.rng,
+            PLAYFIELD_WIDTH,
+            &Handle::default(),
+            tuning,
+            settings.gauntlet_start,
+        );
+        world.flush();
+        world.insert_resource(GameManager {
+            pipe_image: Handle::default(),
+            window_dimensions: Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT),
+        });
+        world.insert_resource(UiFonts { title: Handle::default(), body: Handle::default(), scale: 1.0 });
+        world.insert_resource(SimStep { dt: 1. / 60., flap: false });
+        world.insert_resource(tuning);
+        world.insert_resource(config);
+        world.insert_resource(settings);
+        world.insert_resource(course);
+        world.insert_resource(progress);
+        world
+    }
+
+    fn step_course(world: &mut World, steps: usize) {
+        for _ in 0..steps {
+            world.run_system_once(update_obstacles).unwrap();
+        }
+    }
+
+    // (x, gap offset) of every live pair, left to right; both pipes of a pair must agree
+    fn pairs(world: &mut World) -> Vec<(f32, f32)> {
+        // (index, direction, gap size, position)
+        let pipes: Vec<(u32, f32, f32, Vec3)> = world
+            .query::<(&Obstacle, &Transform)>()
+            .iter(world)
+            .filter(|(obstacle, _)| !obstacle.parked)
+            .map(|(obstacle, transform)| (obstacle.index, obstacle.pipe_direction, obstacle.gap_size, transform.translation))
+            .collect();
+        let mut pairs: Vec<(f32, f32)> = pipes
+            .iter()
+            .filter(|(_, direction, _, _)| *direction == 1.)
+            .map(|&(index, _, top_gap, top_at)| {
+                let &(_, _, bottom_gap, bottom_at) = pipes
+                    .iter()
+                    .find(|(other, direction, _, _)| *other == index && *direction == -1.)
+                    .expect("pair without a bottom pipe");
+                assert_eq!(top_at.x, bottom_at.x, "pair {} split apart", index);
+                let top_offset = top_at.y - get_centered_pipe_position(top_gap);
+                let bottom_offset = bottom_at.y + get_centered_pipe_position(bottom_gap);
+                assert!((top_offset - bottom_offset).abs() < 1e-3, "pair {} has two gaps", index);
+                (top_at.x, top_offset)
+            })
+            .collect();
+        pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+        pairs
+    }
+
+    fn variance(samples: &[f32]) -> f32 {
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        samples.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / samples.len() as f32
+    }
+
+    // Mean of two uniform samples has half the variance of one (v^2/6 against v^2/3)
+    #[test]
+    fn offsets_are_tighter_than_uniform() {
+        let v = OBSTACLE_VERTICAL_OFFSET;
+        let mut rng = StdRng::seed_from_u64(42);
+        let triangular: Vec<f32> = (0..20_000).map(|_| generate_offset(&mut rng, v)).collect();
+        let mut rng = StdRng::seed_from_u64(42);
+        let uniform: Vec<f32> = (0..20_000).map(|_| rng.gen_range(-v..v) * PIXEL_RATIO).collect();
+
+        let ratio = variance(&triangular) / variance(&uniform);
+        assert!((0.45..0.55).contains(&ratio), "variance ratio {}", ratio);
+        assert!(triangular.iter().all(|x| x.abs() <= v * PIXEL_RATIO));
+    }
+
+    #[test]
+    fn offsets_follow_the_seed() {
+        let draw = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..16).map(|_| generate_offset(&mut rng, OBSTACLE_VERTICAL_OFFSET)).collect::<Vec<f32>>()
+        };
+        assert_eq!(draw(7), draw(7));
+        assert_ne!(draw(7), draw(8));
+    }
+
+    #[test]
+    fn recycled_pairs_draw_one_offset() {
+        let mut world = course_world(GameSettings::default(), 11);
+        for _ in 0..200 {
+            step_course(&mut world, 10);
+            pairs(&mut world);
+        }
+    }
+
+    // The first recycled gap leans toward the rightmost starting pair, not toward 0
+    #[test]
+    fn gap_progress_starts_from_the_rightmost_pair() {
+        let mut world = course_world(GameSettings::default(), 5);
+        let rightmost = *pairs(&mut world).last().unwrap();
+        assert!((world.resource::<GapProgress>().last_offset - rightmost.1).abs() < 1e-3);
+    }
+
+    // A respawn shift pushes only the pairs ahead of the bird; the ring must close up again
+    // without two pairs landing on top of each other
+    #[test]
+    fn respawn_shift_keeps_pairs_apart() {
+        let mut world = course_world(GameSettings::default(), 9);
+        let spacing = world.resource::<DifficultyTuning>().spacing * PIXEL_RATIO;
+        for round in 0..6 {
+            step_course(&mut world, 97);
+            let bird_x = pairs(&mut world)[1].0;
+            world
+                .run_system_once(move |mut query: Query<(&mut Obstacle, &mut Transform, Entity)>| {
+                    clear_respawn_area(bird_x, spacing / PIXEL_RATIO, &mut query)
+                })
+                .unwrap();
+            for _ in 0..100 {
+                step_course(&mut world, 5);
+                let pairs = pairs(&mut world);
+                for window in pairs.windows(2) {
+                    assert!(window[1].0 - window[0].0 >= spacing - 1., "round {}: pairs {:?} overlap", round, window);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn combo_meter_only_flags_score_when_it_breaks() {
+        let mut world = World::new();
+        world.insert_resource(SimStep { dt: 0.1, flap: false });
+        world.insert_resource(GameSettings::default());
+        world.insert_resource(Score { combo: 2, combo_timer: 0.15, ..default() });
+        world.clear_trackers();
+
+        world.run_system_once(update_combo).unwrap();
+        assert!(!world.is_resource_changed::<Score>());
+        assert_eq!(world.resource::<Score>().combo, 2);
+
+        world.run_system_once(update_combo).unwrap();
+        assert!(world.is_resource_changed::<Score>());
+        assert_eq!(world.resource::<Score>().combo, 0);
+    }
+
+    // A pipe hanging off the top of the replay box keeps only the part inside it
+    #[test]
+    fn death_replay_pipes_are_cut_to_the_box() {
+        let field = Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT);
+        let texture = PIPE_ART_SIZE * 2.;
+        let top = Transform::from_xyz(0., 250., 0.).with_scale(Vec3::new(PIXEL_RATIO, -PIXEL_RATIO, PIXEL_RATIO));
+        let (sprite, transform) = clip_pipe(Handle::default(), texture, &top, field).unwrap();
+        let size = sprite.custom_size.unwrap() * PIXEL_RATIO;
+        assert!((transform.translation.y + size.y / 2. - field.y / 2.).abs() < 1e-3);
+        assert!(transform.translation.y - size.y / 2. >= 250. - PIPE_ART_SIZE.y * PIXEL_RATIO / 2. - 1e-3);
+        // The flipped pipe shows the cap end of its texture, which starts at the top
+        let rect = sprite.rect.unwrap();
+        assert_eq!(rect.min.y, 0.);
+        assert!(rect.max.y < texture.y);
+
+        let gone = Transform::from_xyz(field.x, 0., 0.).with_scale(Vec3::splat(PIXEL_RATIO));
+        assert!(clip_pipe(Handle::default(), texture, &gone, field).is_none());
+    }
+
+    #[test]
+    fn consecutive_gaps_stay_within_max_shift() {
+        let tuning = difficulty_tuning(Difficulty::Insane, &GameConfig::default());
+        let max_shift = max_gap_shift(&tuning, tuning.gap_size);
+        let mut progress = GapProgress::new(None);
+        progress.last_offset = tuning.vertical_offset * PIXEL_RATIO;
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut last = progress.last_offset;
+        for _ in 0..1_000 {
+            let offset = progress.next_offset(&mut rng, &tuning);
+            assert!((offset - last).abs() <= max_shift + f32::EPSILON);
+            last = offset;
+        }
+    }
+
+    // A read-only saves folder: each write reports the failure, but the data stays readable
+    // for the rest of the session
+    #[test]
+    fn session_only_saves_fail_but_read_back() {
+        IoTaskPool::get_or_init(bevy::tasks::TaskPool::new);
+        let mut saves = SaveWriter::default();
+        saves.store().keep_in_memory("saves is not writable".to_string());
+        let mut save = new_save_slot(2, &GameSettings::default());
+        save.profile.name = "Offline".to_string();
+        saves.write(save);
+        assert_eq!(saves.load(2).unwrap().profile.name, "Offline");
+
+        let write = saves.slots.remove(&2).unwrap();
+        let error = block_on(write.task).unwrap_err();
+        assert!(error.contains("session only"), "{}", error);
+        assert_eq!(saves.load(2).unwrap().profile.name, "Offline");
+        save_audio_settings(&mut saves, &AudioSettings::default());
+        let error = block_on(saves.settings.take().unwrap()).unwrap_err();
+        assert!(error.contains("session only"), "{}", error);
+        assert!(saves.store().read_file(SETTINGS_PATH).is_some());
+    }
+
+    #[test]
+    fn utc_offsets_parse_from_date_and_powershell() {
+        assert_eq!(parse_utc_offset("+0200\n"), Some(7200));
+        assert_eq!(parse_utc_offset("-05:30\r\n"), Some(-19800));
+        assert_eq!(parse_utc_offset("%z"), None);
+        assert_eq!(parse_utc_offset(""), None);
+    }
+
+    // Years of runs in one file: loading cuts the history back to its cap without changing
+    // anything the Leaderboard or Scoreboard shows
+    #[test]
+    fn oversized_history_compacts_without_changing_the_screens() {
+        let mut save = new_save_slot(1, &GameSettings::default());
+        save.score = 77;
+        save.profile.high_score = 77;
+        save.profile.history = (0..50_000).map(|i| i % 90).collect();
+        // Flags only for the newest runs, like a save that predates them
+        save.profile.history_interrupted = (0..30).map(|i| i % 7 == 0).collect();
+        for month in 1..=12 {
+            save.profile.monthly.insert(format!("2025-{:02}", month), MonthStats { runs: 4_000, best: 70 + month, total: 180_000 });
+        }
+        let json = serde_json::to_string_pretty(&save).unwrap();
+        assert!(json.len() > 200_000);
+
+        let loaded = parse_save_slot(&json).unwrap();
+        assert_eq!(loaded.profile.history.len(), RUN_HISTORY_LEN);
+        assert_eq!(loaded.profile.history[..], save.profile.history[50_000 - RUN_HISTORY_LEN..]);
+        assert_eq!(loaded.profile.history_interrupted[..], save.profile.history_interrupted[30 - RUN_HISTORY_LEN..]);
+        assert_eq!(monthly_lines(&loaded.profile), monthly_lines(&save.profile));
+        assert_eq!(entry_flags(&loaded), entry_flags(&save));
+        assert_eq!((loaded.score, loaded.profile.high_score), (save.score, save.profile.high_score));
+        // Compacting again is a no-op
+        let once = serde_json::to_string(&loaded).unwrap();
+        assert_eq!(serde_json::to_string(&parse_save_slot(&once).unwrap()).unwrap(), once);
+    }
+
+    #[test]
+    fn monthly_best_skips_crashed_and_custom_runs() {
+        let mut profile = PlayerProfile::default();
+        profile.push_history(40, false, false);
+        profile.push_history(90, true, false);
+        profile.push_history(80, false, true);
+        let month = profile.monthly[&month_key()];
+        assert_eq!((month.runs, month.best, month.total), (3, 40, 210));
+        assert_eq!(profile.history_interrupted, [false, true, false]);
+    }
+
+    // The file is swapped in whole: readers see the old contents or the new, and no temp is left
+    #[test]
+    fn atomic_writes_replace_the_whole_file() {
+        let dir = std::env::temp_dir().join(format!("flappy-atomic-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("slot_1.json");
+        let path = path.to_str().unwrap();
+        fs::write(path, "x".repeat(100_000)).unwrap();
+        write_atomic(path, "{}").unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "{}");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn graded_score(current: u32, elapsed: f32) -> Score {
+        let mut score = Score { current, ..default() };
+        score.log.elapsed = elapsed;
+        score
+    }
+
+    #[test]
+    fn grades_follow_history_and_survival() {
+        let profile = PlayerProfile { history: vec![5, 10, 15, 20, 25], longest_survival: 40., ..default() };
+        // Beats every run and matches the longest
+        assert_eq!(grade_run(&graded_score(30, 40.), Some(&profile)), 'S');
+        // Beats two, ties one, half the longest: 0.5 * 0.75 + 0.5 * 0.25
+        assert_eq!(grade_run(&graded_score(15, 20.), Some(&profile)), 'B');
+        assert_eq!(grade_run(&graded_score(0, 1.), Some(&profile)), 'C');
+        // Too little history: measured against the starter score, and the run is its own longest
+        assert_eq!(grade_run(&graded_score(10, 5.), None), 'B');
+        assert_eq!(grade_run(&graded_score(20, 5.), None), 'S');
+
+        let mut precise = graded_score(15, 20.);
+        // 50% precision takes a quarter of the weight: 0.5 * 0.5 + 0.5 * 0.25 + 0.5 * 0.25
+        precise.precision = PrecisionStats { good: 4, total: 2., ..default() };
+        assert_eq!(grade_run(&precise, Some(&profile)), 'B');
+        precise.current = 30;
+        assert_eq!(grade_run(&precise, Some(&profile)), 'A');
+    }
+
+    // Four runs are below GRADE_MIN_HISTORY, so the run is graded on the starter scale. Graded
+    // after it was saved, it would be the fifth run and rank last among them
+    #[test]
+    fn runs_are_graded_against_the_profile_before_them() {
+        IoTaskPool::get_or_init(bevy::tasks::TaskPool::new);
+        let mut saves = SaveWriter::default();
+        saves.store().keep_in_memory("test".to_string());
+        let mut save = new_save_slot(1, &GameSettings::default());
+        save.profile.history = vec![30; 4];
+        save.profile.longest_survival = 100.;
+        saves.write(save);
+
+        let settings = GameSettings { current_slot: Some(1), ..default() };
+        let mut score = graded_score(20, 50.);
+        save_run_result(&mut saves, &settings, &mut score, false);
+        assert_eq!(saves.load(1).unwrap().profile.history.len(), 5);
+        assert_eq!(score.grade, Some('S'));
+        assert_eq!(grade_run(&score, saves.load(1).map(|save| save.profile).as_ref()), 'C');
+    }
+
+    #[test]
+    fn iso_weeks_match_the_calendar() {
+        let week = |year, month, day| iso_week(days_from_civil(year, month, day));
+        assert_eq!(week(1970, 1, 1), (1970, 1));
+        // Early January can still be the previous year's last week, late December the next year's first
+        assert_eq!(week(2016, 1, 3), (2015, 53));
+        assert_eq!(week(2021, 1, 3), (2020, 53));
+        assert_eq!(week(2021, 1, 4), (2021, 1));
+        assert_eq!(week(2024, 12, 30), (2025, 1));
+        assert_eq!(week(2027, 1, 1), (2026, 53));
+        assert_eq!(week(2026, 10, 16), (2026, 42));
+
+        // Monday to Sunday share a week, which Monday ends
+        let mut days = days_from_civil(2026, 10, 12);
+        let (year, monday_week) = iso_week(days);
+        for _ in 0..6 {
+            days += 1;
+            assert_eq!(iso_week(days), (year, monday_week));
+        }
+        assert_eq!(iso_week(days + 1), (year, monday_week + 1));
+
+        // A week's key leads back to the same challenge
+        let challenge = weekly_challenge(2026, 42);
+        let (year, week) = parse_week_key(&challenge.key()).unwrap();
+        assert_eq!(weekly_challenge(year, week).seed, challenge.seed);
+    }
+
+    // Two bests in quick succession: the second waits for the first write, so the file ends
+    // up with both
+    #[test]
+    fn weekly_board_writes_land_in_order() {
+        IoTaskPool::get_or_init(bevy::tasks::TaskPool::new);
+        let saves = SaveWriter::default();
+        saves.store().keep_in_memory("test".to_string());
+        let mut board = WeeklyBoard::default();
+        assert!(board.submit(&saves, "2026-W42", WeeklyRecord { score: 10, name: "A".to_string(), slot: 1 }));
+        assert!(board.submit(&saves, "2026-W43", WeeklyRecord { score: 12, name: "B".to_string(), slot: 2 }));
+        assert!(!board.submit(&saves, "2026-W43", WeeklyRecord { score: 11, name: "C".to_string(), slot: 3 }));
+        board.flush(&saves);
+        assert!(board.task.is_none() && !board.unsaved);
+
+        let written: WeeklyBoard = serde_json::from_str(&saves.store().read_file(WEEKLY_PATH).unwrap()).unwrap();
+        assert_eq!(written.weeks.len(), 2);
+        assert_eq!(written.weeks["2026-W43"].name, "B");
+    }
+
+    // Twenty years of weekly bests: the next write keeps only the newest weeks, on the board
+    // and in the profile, and the Weekly screen's history is unchanged
+    #[test]
+    fn oversized_weekly_history_keeps_the_newest_weeks() {
+        IoTaskPool::get_or_init(bevy::tasks::TaskPool::new);
+        let keys: Vec<String> = (2006..2026).flat_map(|year| (1..=52).map(move |week| format!("{}-W{:02}", year, week))).collect();
+        let newest = &keys[keys.len() - WEEKLY_KEEP_WEEKS..];
+        let weeks = keys.iter().map(|key| (key.clone(), WeeklyRecord { score: 5, name: "A".to_string(), slot: 1 })).collect();
+        let json = serde_json::to_string_pretty(&WeeklyBoard { weeks, ..default() }).unwrap();
+        assert!(json.len() > 50_000);
+
+        let saves = SaveWriter::default();
+        saves.store().keep_in_memory("test".to_string());
+        saves.store().write_file(WEEKLY_PATH, json).unwrap_err();
+        let mut board = load_weekly_board(&saves);
+        assert_eq!(board.weeks.len(), keys.len());
+        assert!(board.submit(&saves, "2026-W01", WeeklyRecord { score: 9, name: "B".to_string(), slot: 2 }));
+        board.flush(&saves);
+        let written: WeeklyBoard = serde_json::from_str(&saves.store().read_file(WEEKLY_PATH).unwrap()).unwrap();
+        let mut kept: Vec<&String> = written.weeks.keys().collect();
+        kept.sort();
+        assert_eq!(kept.len(), WEEKLY_KEEP_WEEKS);
+        assert_eq!(kept[WEEKLY_KEEP_WEEKS - 1], "2026-W01");
+        assert_eq!(kept[..WEEKLY_KEEP_WEEKS - 1], newest.iter().skip(1).collect::<Vec<_>>()[..]);
+
+        let mut save = new_save_slot(1, &GameSettings::default());
+        save.profile.weekly_bests = keys.iter().enumerate().map(|(i, key)| (key.clone(), i as u32)).collect();
+        let loaded = parse_save_slot(&serde_json::to_string(&save).unwrap()).unwrap();
+        let mut kept: Vec<&String> = loaded.profile.weekly_bests.keys().collect();
+        kept.sort();
+        assert_eq!(kept, newest.iter().collect::<Vec<_>>());
+        assert_eq!(loaded.profile.weekly_bests[&keys[keys.len() - 1]], keys.len() as u32 - 1);
+    }
+
+    // The gauntlet packs the starting pairs tighter, then every recycled pair lands one full
+    // spacing past the rightmost one still on the course
+    #[test]
+    fn gauntlet_start_packs_only_the_first_pairs() {
+        assert_eq!(start_pair_offset(0, true), 0.);
+        assert_eq!(start_pair_offset(1, true), 0.5);
+        assert_eq!(start_pair_offset(OBSTACLE_AMOUNT as usize - 1, true), 2.25);
+        assert_eq!(start_pair_offset(3, false), 3.);
+
+        let settings = GameSettings { gauntlet_start: true, ..default() };
+        let mut world = course_world(settings, 4);
+        let spacing = world.resource::<DifficultyTuning>().spacing * PIXEL_RATIO;
+        let start: Vec<f32> = pairs(&mut world).iter().map(|pair| pair.0).collect();
+        for (i, window) in start.windows(2).enumerate() {
+            assert!((window[1] - window[0] - GAUNTLET_SPACING[i] * spacing).abs() < 1e-3);
+        }
+
+        // Long enough for the whole starting ring to go round at least twice
+        for _ in 0..300 {
+            step_course(&mut world, 10);
+            let pairs = pairs(&mut world);
+            for window in pairs.windows(2) {
+                assert!(window[1].0 - window[0].0 >= spacing * 0.5 - 1e-3, "pairs {:?} overlap", window);
+            }
+        }
+        let pairs = pairs(&mut world);
+        for window in pairs.windows(2) {
+            assert!((window[1].0 - window[0].0 - spacing).abs() < 1., "recycled pairs {:?} not one spacing apart", window);
+        }
+    }
+
+    // Classic flaps still snap the speed to the flap force, so the bird follows the same
+    // trajectory to the bit as before the impulse model existed
+    #[test]
+    fn classic_flaps_match_the_old_snap() {
+        let mut world = bird_world();
+        let tuning = *world.resource::<DifficultyTuning>();
+        assert!(matches!(tuning.flap, FlapStyle::Classic));
+
+        let dt = 1. / 60.;
+        let (mut velocity, mut y) = (0f32, 0f32);
+        for i in 0..600 {
+            let flap = i % 24 == 0;
+            step_bird(&mut world, dt, flap);
+
+            if flap {
+                velocity = FLAP_FORCE * tuning.flap_mult;
+            }
+            velocity -= dt * GRAVITY * tuning.gravity_mult;
+            y += velocity * dt;
+            let (bird, transform) = world.query::<(&Bird, &Transform)>().single(&world).unwrap();
+            assert_eq!(bird.velocity.to_bits(), velocity.to_bits(), "step {}", i);
+            assert_eq!(transform.translation.y.to_bits(), y.to_bits(), "step {}", i);
+        }
+    }
+
+    // Headless bird on Normal with no pipes. Rotation smoothing is off, so the tilt is a pure
+    // function of the velocity and trajectory checks never depend on the frame history
+    fn bird_world() -> World {
+        let config = GameConfig { rotation_smoothing: 0.0, ..default() };
+        let mut world = World::new();
+        world.insert_resource(difficulty_tuning(Difficulty::Normal, &config));
+        world.insert_resource(config);
+        world.insert_resource(GameManager {
+            pipe_image: Handle::default(),
+            window_dimensions: Vec2::new(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT),
+        });
+        world.insert_resource(SoundEffects {
+            flap: Handle::default(),
+            point: Handle::default(),
+            die: Handle::default(),
+            swoosh: Handle::default(),
+            jingle: Vec::new(),
+        });
+        world.insert_resource(Score::default());
+        world.init_resource::<NextState<GameState>>();
+        world.insert_resource(GameSettings::default());
+        world.insert_resource(AudioSettings::default());
+        let saves = SaveWriter::default();
+        saves.store().keep_in_memory("test".to_string());
+        world.insert_resource(saves);
+        world.init_resource::<Messages<GamepadRumbleRequest>>();
+        world.spawn((Bird { velocity: 0., tilt: 0., flap_boost: 0., flap_ticks: 0 }, Transform::default()));
+        world
+    }
+
+    // One sim step of the bird, in the order the Playing schedule runs them
+    fn step_bird(world: &mut World, dt: f32, flap: bool) {
+        world.insert_resource(SimStep { dt, flap });
+        world.run_system_once(update_bird).unwrap();
+        world.run_system_once(tilt_bird).unwrap();
+    }
+
+    // With smoothing off the shown tilt is exactly the velocity's angle on every step, however
+    // long the step is
+    #[test]
+    fn harness_tilt_follows_the_velocity() {
+        let mut world = bird_world();
+        for (i, dt) in [1. / 240., 1. / 60., 1. / 15., 1. / 60.].into_iter().cycle().take(200).enumerate() {
+            step_bird(&mut world, dt, i % 20 == 0);
+            let bird = world.query::<&Bird>().single(&world).unwrap();
+            assert_eq!(bird.tilt, f32::clamp(bird.velocity / VELOCITY_TO_ROTATION_RATIO, -90., 90.), "step {}", i);
+        }
+    }
+
+    fn crashed_into_pipe(world: &World) -> bool {
+        world.resource::<Score>().log.events.iter().any(|(_, event)| matches!(event, RunEvent::Death { pipe: Some(_), .. }))
+    }
+
+    // A pipe just out of a normal bird's reach: the giant bird hits it, and the tiny bird
+    // fits where a normal one would clip
+    #[test]
+    fn bird_size_scales_the_pipe_hitbox() {
+        let crashes = |size: BirdSize, pipe_y: f32| {
+            let mut world = bird_world();
+            world.resource_mut::<GameSettings>().bird_size = size;
+            world.spawn((
+                Obstacle { pipe_direction: 1., scored: false, gap_size: 25., index: 1, whooshed: false, parked: false },
+                Transform::from_xyz(0., pipe_y, 0.),
+            ));
+            step_bird(&mut world, 1. / 60., false);
+            crashed_into_pipe(&world)
+        };
+        let reach = pipe_extents(1.0).y;
+        let giant = reach + bird_extra_extents(GIANT_BIRD_SCALE).y / 2.;
+        let tiny = reach + bird_extra_extents(TINY_BIRD_SCALE).y / 2.;
+        assert!(!crashes(BirdSize::Normal, giant));
+        assert!(crashes(BirdSize::Giant, giant));
+        assert!(crashes(BirdSize::Normal, tiny));
+        assert!(!crashes(BirdSize::Tiny, tiny));
+    }
+
+    // A three second stall (a minimized window) only moves the bird one clamped step, so a
+    // pipe right where that step ends still catches it
+    #[test]
+    fn long_frames_are_clamped_before_physics() {
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs(3));
+        let dt = physics_delta(&time);
+        assert_eq!(dt, MAX_PHYSICS_DELTA);
+
+        let mut world = bird_world();
+        let fall = dt * dt * GRAVITY * world.resource::<DifficultyTuning>().gravity_mult;
+        world.spawn((
+            Obstacle { pipe_direction: 1., scored: false, gap_size: 25., index: 1, whooshed: false, parked: false },
+            Transform::from_xyz(0., -fall + pipe_extents(1.0).y - 1., 0.),
+        ));
+        step_bird(&mut world, dt, false);
+        let y = world.query::<(&Bird, &Transform)>().single(&world).unwrap().1.translation.y;
+        assert!((y + fall).abs() < 1e-3, "bird fell {} instead of {}", -y, fall);
+        assert!(crashed_into_pipe(&world));
+    }
+
+    // Runs itself again as a scratch process that installs the hook and panics, so the
+    // hook never replaces the test runner's own
+    #[test]
+    fn crash_hook_writes_a_report() {
+        const CHILD: &str = "FLAPPY_CRASH_TEST_DIR";
+        if let Ok(dir) = std::env::var(CHILD) {
+            install_crash_hook(Box::leak(dir.into_boxed_str()));
+            *CRASH_CONTEXT.lock().unwrap() = "Game state: Playing\n".to_string();
+            panic!("controlled test panic");
+        }
+
+        let dir = std::env::temp_dir().join(format!("flappy_crash_test_{}", std::process::id()));
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["tests::crash_hook_writes_a_report", "--exact", "--test-threads=1"])
+            .env(CHILD, &dir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(!status.success());
+
+        let reports: Vec<_> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+        let report = fs::read_to_string(&reports[0]).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].file_name().unwrap().to_str().unwrap().starts_with("crash_"));
+        assert!(report.contains("controlled test panic"));
+        assert!(report.contains("Game state: Playing"));
+        assert!(report.contains("Backtrace:"));
+    }
+
+    // Starting pairs and recycled ones sit the same per-difficulty spacing apart
+    #[test]
+    fn spawn_and_recycle_share_the_difficulty_spacing() {
+        let mut spacings = Vec::new();
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            let mut world = course_world(GameSettings { selected_difficulty: difficulty, ..default() }, 2);
+            world.resource_mut::<GameConfig>().rest_stop_interval = 0;
+            let spacing = world.resource::<DifficultyTuning>().spacing * PIXEL_RATIO;
+            for round in 0..4 {
+                for window in pairs(&mut world).windows(2) {
+                    assert!((window[1].0 - window[0].0 - spacing).abs() < 1., "{:?} round {}: {:?}", difficulty, round, window);
+                }
+                step_course(&mut world, 400);
+            }
+            spacings.push(spacing);
+        }
+        assert!(spacings[0] > spacings[1] && spacings[1] > spacings[2]);
+    }
+
+    #[test]
+    fn duplicate_birds_are_reduced_to_one() {
+        let mut world = World::new();
+        let bird = || Bird { velocity: 0., tilt: 0., flap_boost: 0., flap_ticks: 0 };
+        let first = world.spawn(bird()).id();
+        world.spawn(bird());
+        world.spawn(bird());
+
+        world.run_system_once(ensure_single_bird).unwrap();
+        let birds: Vec<Entity> = world.query_filtered::<Entity, With<Bird>>().iter(&world).collect();
+        assert_eq!(birds, vec![first]);
+
+        // A lone bird is left alone
+        world.run_system_once(ensure_single_bird).unwrap();
+        assert_eq!(world.query::<&Bird>().iter(&world).count(), 1);
+    }
+
+    // A flipped bird flies the mirror image of a normal one until the flip runs out, then
+    // falls down again
+    #[test]
+    fn gravity_flip_mirrors_the_bird_until_it_expires() {
+        let flip_world = |remaining: Option<f32>| {
+            let mut world = bird_world();
+            world.resource_mut::<GameSettings>().modifiers.gravity_flip = true;
+            world.insert_resource(ModifierState { elapsed: 0.0, since_flap: FLAP_COOLDOWN, flip_pickup_score: 0 });
+            if let Some(remaining) = remaining {
+                let bird = world.query_filtered::<Entity, With<Bird>>().single(&world).unwrap();
+                world.entity_mut(bird).insert(GravityFlip { remaining });
+            }
+            world
+        };
+        let step = |world: &mut World, flap: bool| {
+            step_bird(world, 1. / 60., flap);
+            world.run_system_once(update_gravity_flip).unwrap();
+            let (bird, transform) = world.query::<(&Bird, &Transform)>().single(world).unwrap();
+            (bird.velocity, transform.translation.y)
+        };
+
+        let mut normal = flip_world(None);
+        let mut flipped = flip_world(Some(10.));
+        for i in 0..120 {
+            let (velocity, y) = step(&mut normal, i % 30 == 0);
+            let (flipped_velocity, flipped_y) = step(&mut flipped, i % 30 == 0);
+            assert_eq!((flipped_velocity, flipped_y), (-velocity, -y), "step {}", i);
+        }
+
+        // Six 1/60 s steps run out a 0.09 s flip
+        let mut world = flip_world(Some(0.09));
+        for _ in 0..6 {
+            assert!(step(&mut world, false).0 > 0.);
+        }
+        assert!(world.query::<&GravityFlip>().iter(&world).next().is_none());
+        let (before, _) = step(&mut world, false);
+        let (after, _) = step(&mut world, false);
+        assert!(after < before);
+    }
+
+    #[test]
+    fn improvement_rate_compares_the_last_two_windows() {
+        // Only the last 2 * TREND_WINDOW runs count
+        assert_eq!(improvement_rate(&[100, 100, 10, 10, 10, 10, 10, 15, 15, 15, 15, 15]), 50.0);
+        assert_eq!(improvement_rate(&[20, 20, 20, 20, 20, 10, 10, 10, 10, 10]), -50.0);
+        // Short histories compare what they have, and never divide by zero
+        assert_eq!(improvement_rate(&[]), 0.0);
+        assert_eq!(improvement_rate(&[7]), 0.0);
+        assert_eq!(improvement_rate(&[4, 6]), 50.0);
+        assert_eq!(improvement_rate(&[0, 0, 3, 3]), 100.0);
+        assert_eq!(improvement_rate(&[0, 0]), 0.0);
+
+        assert_eq!(trend_label(&[1; 9]).0, "Trend: 9/10 runs");
+        assert_eq!(trend_label(&[10, 10, 10, 10, 10, 15, 15, 15, 15, 15]).0, "Trend: ^ 50%");
+        assert_eq!(trend_label(&[20, 20, 20, 20, 20, 10, 10, 10, 10, 10]).0, "Trend: v 50%");
+    }
+
+    // The frame the bird crosses a pair's center while clipping its bottom pipe: collision
+    // wins whichever pipe the query visits first, but a pass from an earlier frame stays
+    #[test]
+    fn clipping_the_pipe_being_passed_never_scores() {
+        let pair_world = |gap_offset: f32, top_first: bool| {
+            let mut world = bird_world();
+            let centered = get_centered_pipe_position(25.);
+            let mut pipes = [(1., centered + gap_offset), (-1., -centered + gap_offset)];
+            if !top_first {
+                pipes.reverse();
+            }
+            for (pipe_direction, y) in pipes {
+                world.spawn((
+                    Obstacle { pipe_direction, scored: false, gap_size: 25., index: 1, whooshed: false, parked: false },
+                    Transform::from_xyz(-0.5, y, 0.),
+                ));
+            }
+            world
+        };
+
+        for top_first in [true, false] {
+            let mut world = pair_world(110., top_first);
+            step_bird(&mut world, 1. / 60., false);
+            assert!(crashed_into_pipe(&world));
+            assert_eq!(world.resource::<Score>().current, 0, "top first: {}", top_first);
+        }
+
+        let mut world = pair_world(0., true);
+        step_bird(&mut world, 1. / 60., false);
+        assert_eq!(world.resource::<Score>().current, 1);
+        for mut transform in world.query_filtered::<&mut Transform, With<Obstacle>>().iter_mut(&mut world) {
+            transform.translation.y += 110.;
+        }
+        step_bird(&mut world, 1. / 60., false);
+        assert!(crashed_into_pipe(&world));
+        assert_eq!(world.resource::<Score>().current, 1);
+    }
+
+    // A die ducks the quieter kinds for the configured time, then hands back exactly their
+    // own volume and stops touching them
+    #[test]
+    fn ducked_voices_are_restored() {
+        let mut world = World::new();
+        world.insert_resource(GameConfig::default());
+        world.init_resource::<Time<Real>>();
+        world.init_resource::<SfxBus>();
+        let policy = world.resource::<GameConfig>().ducking;
+        let volume = PlaybackSettings::DESPAWN.with_volume(Volume::Linear(0.8));
+        for kind in [SfxKind::Flap, SfxKind::Point, SfxKind::Die] {
+            world.spawn((kind, volume));
+        }
+        world.run_system_once(update_sfx_bus).unwrap();
+
+        let bus = world.resource::<SfxBus>();
+        let ducked: Vec<SfxKind> = bus.voices.iter().filter(|voice| voice.ducked > 0.0).map(|voice| voice.kind).collect();
+        assert_eq!(ducked, vec![SfxKind::Flap, SfxKind::Point]);
+
+        let mut voice = SfxVoice { entity: Entity::PLACEHOLDER, kind: SfxKind::Point, ducked: policy.duration };
+        let scales: Vec<Option<f32>> = (0..5).map(|_| voice.fade(0.125, policy.amount)).collect();
+        assert_eq!(scales, vec![Some(0.5), Some(0.5), Some(1.0), None, None]);
+        assert_eq!(voice.ducked, 0.0);
+    }
+
+    // Over many seeds, no Insane course asks for a climb between neighbouring gaps that a
+    // flap can't make, and no gap leaves the offset range
+    #[test]
+    fn insane_courses_stay_reachable_for_any_seed() {
+        let json = serde_json::to_string(&Difficulty::Insane).unwrap();
+        assert_eq!(json, "\"Insane\"");
+        assert_eq!(serde_json::from_str::<Difficulty>(&json).unwrap(), Difficulty::Insane);
+
+        let tuning = difficulty_tuning(Difficulty::Insane, &GameConfig::default());
+        assert!(is_course_passable(&tuning));
+        let band = 2. * tuning.gap_size * PIXEL_RATIO;
+        let climb = FLAP_FORCE * tuning.flap_mult * tuning.spacing * PIXEL_RATIO / tuning.scroll_speed;
+        let range = tuning.vertical_offset * PIXEL_RATIO;
+        for seed in 0..500 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut progress = GapProgress::new(None);
+            progress.last_offset = generate_offset(&mut rng, tuning.vertical_offset);
+            let mut last = progress.last_offset;
+            for pair in 0..200 {
+                let offset = progress.next_offset(&mut rng, &tuning);
+                assert!(offset - last - band <= climb, "seed {} pair {}: {} -> {}", seed, pair, last, offset);
+                assert!(offset.abs() <= range, "seed {} pair {}: {}", seed, pair, offset);
+                last = offset;
+            }
+        }
+    }
+
+    // Game Over and the main menu wired the way main() does, with 60 fps frames and a
+    // keyboard fed through Bevy's input plugin
+    fn menu_app() -> App {
+        use bevy::state::app::StatesPlugin;
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, bevy::input::InputPlugin))
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(1. / 60.)))
+            .insert_state(GameState::GameOver)
+            .insert_resource(KeyBindings::new(FlapKey::default()))
+            .insert_resource(GameSettings { reduce_motion: true, ..default() })
+            .insert_resource(SaveWriter::default())
+            .insert_resource(UiFonts { title: Handle::default(), body: Handle::default(), scale: 1.0 })
+            .insert_resource(TrainerTuning::default())
+            .insert_resource(GameConfig::default())
+            .init_resource::<ScreenFade>()
+            .init_resource::<SaveBanner>()
+            .init_resource::<AudioPrewarm>()
+            .init_resource::<RunCodeStatus>()
+            .init_resource::<Score>()
+            .init_resource::<RunRecording>()
+            .init_resource::<InputDebounce>()
+            .add_systems(PreUpdate, apply_input_debounce.after(bevy::input::InputSystems))
+            .add_systems(StateTransition, start_input_debounce
+                .run_if(state_changed::<GameState>)
+                .after(bevy::state::state::StateTransitionSystems::EnterSchedules))
+            .add_systems(Update, (
+                handle_game_over.run_if(in_state(GameState::GameOver)),
+                main_menu_system.run_if(in_state(GameState::MainMenu)),
+                update_screen_fade,
+            ));
+        app.world().resource::<SaveWriter>().store().keep_in_memory("test".to_string());
+        app
+    }
+
+    fn send_space(app: &mut App, state: bevy::input::ButtonState, repeat: bool) {
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state,
+            text: None,
+            repeat,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    // Space held on Game Over goes to the main menu and stops there, even with the key
+    // repeat some platforms send as a release and a press in the same frame. Released and
+    // pressed again, it carries on to save select
+    #[test]
+    fn held_space_stops_at_the_main_menu() {
+        use bevy::input::ButtonState;
+        let mut app = menu_app();
+        let state = |app: &App| *app.world().resource::<State<GameState>>().get();
+        for _ in 0..20 {
+            app.update();
+        }
+
+        send_space(&mut app, ButtonState::Pressed, false);
+        for frame in 0..60 {
+            app.update();
+            if frame % 2 == 0 {
+                send_space(&mut app, ButtonState::Released, true);
+                send_space(&mut app, ButtonState::Pressed, true);
+            }
+        }
+        assert_eq!(state(&app), GameState::MainMenu);
+
+        send_space(&mut app, ButtonState::Released, false);
+        app.update();
+        send_space(&mut app, ButtonState::Pressed, false);
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(state(&app), GameState::SaveSelect);
+    }
+
+    // The shown tilt trails a sudden change of velocity, closes in every step and settles on
+    // the raw angle, in about the same time at any frame rate
+    #[test]
+    fn smoothed_tilt_lags_then_converges() {
+        let settle = |dt: f32| {
+            let mut world = World::new();
+            world.insert_resource(GameConfig::default());
+            world.insert_resource(SimStep { dt, flap: false });
+            world.spawn((Bird { velocity: FLAP_FORCE, tilt: 0., flap_boost: 0., flap_ticks: 0 }, Transform::default()));
+            let target = FLAP_FORCE / VELOCITY_TO_ROTATION_RATIO;
+            let mut tilts = Vec::new();
+            for _ in 0..(0.5 / dt).round() as usize {
+                world.run_system_once(tilt_bird).unwrap();
+                tilts.push(world.query::<&Bird>().single(&world).unwrap().tilt);
+            }
+            assert!(tilts[0] > 0. && tilts[0] < target);
+            assert!(tilts.windows(2).all(|pair| pair[0] <= pair[1] && pair[1] <= target));
+            assert!((target - tilts.last().unwrap()).abs() < 0.01, "{} after 0.5s at dt {}", tilts.last().unwrap(), dt);
+            tilts[(0.1 / dt).round() as usize - 1]
+        };
+        let (slow, fast) = (settle(1. / 30.), settle(1. / 144.));
+        assert!((slow - fast).abs() < 1., "{} at 30 fps, {} at 144 fps", slow, fast);
+    }
+
+    #[test]
+    fn shipped_difficulties_are_passable() {
+        let config = GameConfig::default();
+        for difficulty in DIFFICULTIES {
+            assert!(is_course_passable(&difficulty_tuning(difficulty, &config)), "{:?}", difficulty);
+        }
+        assert!(config_balance_warnings(&config).is_empty());
+
+        let normal = difficulty_tuning(Difficulty::Normal, &config);
+        // A gap thinner than the arc it takes to cross a pipe
+        let narrow = DifficultyTuning { gap_size: 2., ..normal };
+        assert!(!is_course_passable(&narrow));
+        assert!(course_problem(&narrow).unwrap().starts_with("crossing a pipe"));
+        // Neighbouring gaps further apart than a flap can climb before the next pipe
+        let steep = DifficultyTuning { vertical_offset: 200., ..normal };
+        assert!(!is_course_passable(&steep));
+        assert!(course_problem(&steep).unwrap().starts_with("gaps can rise"));
+
+        let warnings = config_balance_warnings(&GameConfig { gap_size: 2., ..config });
+        assert_eq!(warnings.len(), DIFFICULTIES.len());
+        assert!(warnings[0].starts_with("Easy is not passable"));
+    }
+
+    // Endless keeps shrinking gaps to the floor; the layout must still be reachable there, even
+    // on a course whose full gaps only just allow the climb
+    #[test]
+    fn gaps_shrunk_to_the_floor_stay_passable() {
+        let config = GameConfig { gap_shrink: GapShrink { enabled: true, ..default() }, ..default() };
+        assert!(config_balance_warnings(&config).is_empty());
+
+        let normal = difficulty_tuning(Difficulty::Normal, &config);
+        let steep = DifficultyTuning { vertical_offset: max_gap_shift(&normal, normal.gap_size) / (2. * PIXEL_RATIO), ..normal };
+        assert!(is_course_passable(&steep));
+        let tunings = DIFFICULTIES.map(|difficulty| difficulty_tuning(difficulty, &config));
+        for tuning in tunings.iter().chain([&steep]) {
+            let mut progress = GapProgress::new(Some(config.gap_shrink.floor));
+            progress.sync(10_000);
+            assert_eq!(progress.factor, config.gap_shrink.floor);
+
+            let band = 2. * progress.gap_size(tuning) * PIXEL_RATIO;
+            let climb = FLAP_FORCE * tuning.flap_mult * tuning.spacing * PIXEL_RATIO / tuning.scroll_speed;
+            for seed in 0..200 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut last = progress.last_offset;
+                for pair in 0..200 {
+                    let offset = progress.next_offset(&mut rng, tuning);
+                    assert!((offset - last).abs() - band <= climb + 0.01, "seed {} pair {}: {} -> {}", seed, pair, last, offset);
+                    last = offset;
+                }
+            }
+        }
+
+        // A floor so low that a pipe can't be crossed any more is reported on its own line
+        let tight = GameConfig { gap_shrink: GapShrink { enabled: true, floor: 0.5 }, gap_size: 14., ..config };
+        let warnings = config_balance_warnings(&tight);
+        assert_eq!(warnings.len(), DIFFICULTIES.len());
+        assert!(warnings.iter().all(|warning| warning.contains("not passable once gaps shrink to 50%: crossing a pipe")), "{:?}", warnings);
+        assert!(config_balance_warnings(&GameConfig { gap_shrink: GapShrink { enabled: false, floor: 0.5 }, ..tight }).is_empty());
+    }
+
+    // --validate-saves on three fixtures: a slot from this build, one written before any of
+    // the optional fields existed, and one cut off halfway through
+    #[test]
+    fn validate_saves_reports_each_fixture() {
+        let mut current = new_save_slot(1, &GameSettings::default());
+        current.profile.name = "Ada".to_string();
+        current.profile.history = vec![3, 9, 14];
+        let current = serde_json::to_string_pretty(&current).unwrap();
+        let mut legacy: serde_json::Value = serde_json::from_str(&current).unwrap();
+        legacy["slot_number"] = 2.into();
+        legacy.as_object_mut().unwrap().retain(|key, _| {
+            ["slot_number", "profile", "mode", "difficulty", "theme", "skin", "score", "survival_time"].contains(&key.as_str())
+        });
+        legacy["profile"].as_object_mut().unwrap().retain(|key, _| {
+            ["name", "high_score", "total_games", "average_score", "longest_survival"].contains(&key.as_str())
+        });
+        let legacy = legacy.to_string();
+        let corrupted = &current[..current.len() / 2];
+
+        let saves = SaveWriter::default();
+        {
+            let store = saves.store();
+            store.keep_in_memory("test".to_string());
+            // Session-only writes land in memory but still report that the disk wasn't touched
+            for (slot, contents) in [(1, current.as_str()), (2, legacy.as_str()), (3, corrupted)] {
+                store.write_file(&slot_path(slot), contents.to_string()).unwrap_err();
+                store.write_file(&slot_backup_path(&slot_path(slot)), legacy.clone()).unwrap_err();
+            }
+            store.write_file(SETTINGS_PATH, serde_json::to_string(&AudioSettings::default()).unwrap()).unwrap_err();
+            store.write_file(WEEKLY_PATH, "{\"weeks\": {}}".to_string()).unwrap_err();
+        }
+
+        let mut report = Vec::new();
+        assert!(!validate_saves(&saves, &mut report));
+        let line = |prefix: &str| report.iter().position(|line| line.starts_with(prefix)).unwrap_or_else(|| panic!("no {:?} in {:#?}", prefix, report));
+
+        let slot = line("Slot 1 (");
+        assert!(report[slot].ends_with(": ok"));
+        assert_eq!(report[slot + 1], "  profile: Ada");
+        assert_eq!(report[slot + 2], "  format: current");
+        assert_eq!(report[slot + 3], "  integrity: ok");
+        assert!(report[slot + 4].starts_with("  history: 3 run(s)"));
+
+        let slot = line("Slot 2 (");
+        assert!(report[slot].ends_with(": ok"));
+        assert!(report[slot + 2].starts_with("  format: legacy, defaults used for "));
+        assert!(report[slot + 2].contains("profile.history"));
+
+        // The backup holds slot 2, so slot 1's copy is flagged
+        assert!(report[line("Slot 1 backup") + 3].starts_with("  integrity: file holds slot 2"));
+
+        let slot = line("Slot 3 (");
+        assert!(report[slot].ends_with(": UNREADABLE"));
+        assert!(report[slot + 1].starts_with(&format!("  line {}, column", corrupted.lines().count())));
+
+        assert!(report[line("Settings (")].contains(": ok"));
+        assert!(report[line("Weekly board (")].contains(": ok, 0 week(s)"));
+        assert_eq!(report.last().unwrap(), "Some save files are unreadable");
+    }
+
+    // Entering and leaving Playing with the same OnEnter/OnExit chains main() registers
+    // (minus the run marker, which would touch the real saves folder). Anything the run
+    // spawns without RunScoped outlives it and shows up in the entity count
+    #[test]
+    fn runs_leave_no_entities_behind() {
+        use bevy::state::app::StatesPlugin;
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<AudioSource>()
+            .init_asset::<Pitch>()
+            .insert_state(GameState::MainMenu)
+            .add_message::<RunEnded>()
+            .insert_resource(GameSettings::default())
+            .insert_resource(GameConfig::default())
+            .insert_resource(TrainerTuning::default())
+            .insert_resource(AudioSettings::default())
+            .insert_resource(SaveWriter::default())
+            .insert_resource(UiFonts { title: Handle::default(), body: Handle::default(), scale: 1.0 })
+            .insert_resource(SoundEffects {
+                flap: Handle::default(),
+                point: Handle::default(),
+                die: Handle::default(),
+                swoosh: Handle::default(),
+                jingle: Vec::new(),
+            })
+            .init_resource::<Score>()
+            .init_resource::<RunRecording>()
+            .init_resource::<DeathTrail>()
+            .add_systems(OnEnter(GameState::Playing), (apply_weekly_challenge, setup_level, ensure_single_bird, reset_on_play_start).chain())
+            .add_systems(OnExit(GameState::Playing), (record_run_end, capture_death_replay, cleanup_game).chain());
+        app.world().resource::<SaveWriter>().store().keep_in_memory("test".to_string());
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        app.world_mut().spawn((Camera2d, Transform::default()));
+        app.update();
+
+        // One-shot sounds despawn themselves once played, which never happens without an audio device
+        let count = |app: &mut App| {
+            app.world_mut()
+                .query::<Option<&PlaybackSettings>>()
+                .iter(app.world())
+                .filter(|playback| !playback.is_some_and(|playback| matches!(playback.mode, bevy::audio::PlaybackMode::Despawn)))
+                .count()
+        };
+        let baseline = count(&mut app);
+        for run in 0..2 {
+            app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Playing);
+            app.update();
+            assert!(count(&mut app) > baseline, "run {} spawned nothing", run);
+            app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::MainMenu);
+            app.update();
+            assert_eq!(count(&mut app), baseline, "run {} left entities behind", run);
+        }
+    }
+
+    // A save file stuck on a slow disk must not stall the frames reading other slots
+    #[cfg(unix)]
+    #[test]
+    fn blocked_file_io_does_not_block_the_store() {
+        use std::io::Write;
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let dir = std::env::temp_dir().join(format!("flappy-slow-disk-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let fifo = dir.join("slot.fifo");
+        let _ = fs::remove_file(&fifo);
+        let made = std::process::Command::new("mkfifo").arg(&fifo).status();
+        if !made.is_ok_and(|status| status.success()) {
+            return;
+        }
+        let fifo = fifo.to_string_lossy().into_owned();
+
+        let saves = Arc::new(SaveWriter::default());
+        saves.store().keep_in_memory("test".to_string());
+        // Reading a FIFO blocks until something writes to it, like a read from a stalled disk
+        let blocked = {
+            let (saves, fifo) = (saves.clone(), fifo.clone());
+            std::thread::spawn(move || saves.store().read_file(&fifo))
+        };
+        std::thread::sleep(Duration::from_millis(50));
+
+        let (done, finished) = mpsc::channel();
+        let reader = saves.clone();
+        std::thread::spawn(move || {
+            reader.load(2);
+            let _ = done.send(reader.store().session_only_reason());
+        });
+        let result = finished.recv_timeout(Duration::from_secs(5));
+
+        fs::OpenOptions::new().write(true).open(&fifo).unwrap().write_all(b"x").unwrap();
+        assert_eq!(blocked.join().unwrap().as_deref(), Some("x"));
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(result.expect("the store stayed locked while a file was read"), Some("test".to_string()));
+    }
+
+    // Replaying a remembered seed passes the same slot checks as SPACE on the Main Menu
+    #[test]
+    fn seed_launch_goes_through_the_start_checks() {
+        let mut saves = SaveWriter::default();
+        saves.store().keep_in_memory("test".to_string());
+        saves.write(new_save_slot(1, &GameSettings::default()));
+        let record = SeedRecord { seed: 7, mode: GameMode::Endless, difficulty: Difficulty::Hard, score: 3 };
+        let launch = |settings: &mut GameSettings, saves: &SaveWriter, prewarm: &AudioPrewarm| {
+            let mut fade = ScreenFade::default();
+            launch_seed(settings, saves, prewarm, record, &mut fade);
+            fade.target
+        };
+        let warming = AudioPrewarm { pending: vec![Handle::default()], ..default() };
+
+        let mut settings = GameSettings::default();
+        assert_eq!(launch(&mut settings, &saves, &warming), None);
+        assert_eq!(settings.seed, None);
+        assert_eq!(launch(&mut settings, &saves, &AudioPrewarm::default()), Some(GameState::SaveSelect));
+
+        // Slot 1 was saved on Normal, so relaunching a Hard seed asks first
+        settings.current_slot = Some(1);
+        assert_eq!(launch(&mut settings, &saves, &AudioPrewarm::default()), Some(GameState::OverwriteConfirm));
+        assert_eq!(settings.seed, Some(7));
+
+        let mut save = new_save_slot(1, &GameSettings::default());
+        save.difficulty = Difficulty::Hard;
+        saves.write(save);
+        assert_eq!(launch(&mut settings, &saves, &AudioPrewarm::default()), Some(GameState::Playing));
+    }
+
+    // Minimal ships its own soft clicks for every sound; the other themes fall back to the defaults
+    #[test]
+    fn minimal_theme_ships_every_sound() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default())).init_asset::<AudioSource>();
+        let asset_server = app.world().resource::<AssetServer>();
+        for file in PREWARM_SOUNDS {
+            let path = |theme: Theme| theme.sound(asset_server, file).path().unwrap().path().to_string_lossy().into_owned();
+            assert!(path(Theme::Minimal).starts_with("sfx/minimal/"), "{}", path(Theme::Minimal));
+            assert_eq!(path(Theme::Classic), file);
+        }
+    }
+}